@@ -6,18 +6,22 @@ use napi::{JsFunction, bindgen_prelude::External};
 use next_api::{
     operation::OptionEndpoint,
     paths::ServerPath,
+    project::ProjectContainer,
     route::{
         EndpointOutputPaths, endpoint_client_changed_operation, endpoint_server_changed_operation,
         endpoint_write_to_disk_operation,
     },
 };
 use tracing::Instrument;
-use turbo_tasks::{Completion, Effects, OperationVc, ReadRef, Vc};
+use turbo_tasks::{Completion, Effects, OperationVc, ReadRef, ResolvedVc, Vc};
 use turbopack_core::{diagnostics::PlainDiagnostic, issue::PlainIssue};
 
-use super::utils::{
-    DetachedVc, NapiDiagnostic, NapiIssue, RootTask, TurbopackResult,
-    strongly_consistent_catch_collectables, subscribe,
+use super::{
+    turbopack_ctx::NextTurbopackContext,
+    utils::{
+        DetachedVc, NapiDiagnostic, NapiIssue, RootTask, TurbopackResult,
+        strongly_consistent_catch_collectables, subscribe,
+    },
 };
 
 #[napi(object)]
@@ -87,13 +91,31 @@ impl From<Option<EndpointOutputPaths>> for NapiWrittenEndpoint {
 //    some async functions (in this case `endpoint_write_to_disk`) can cause
 //    higher-ranked lifetime errors. See https://github.com/rust-lang/rust/issues/102211
 // 2. the type_complexity clippy lint.
-pub struct ExternalEndpoint(pub DetachedVc<OptionEndpoint>);
+pub struct ExternalEndpoint {
+    endpoint: DetachedVc<OptionEndpoint>,
+    /// The container of the project the endpoint belongs to. Used to read project-wide settings
+    /// like the issue rules.
+    container: ResolvedVc<ProjectContainer>,
+}
+
+impl ExternalEndpoint {
+    pub fn new(
+        turbopack_ctx: NextTurbopackContext,
+        container: ResolvedVc<ProjectContainer>,
+        endpoint: OperationVc<OptionEndpoint>,
+    ) -> Self {
+        ExternalEndpoint {
+            endpoint: DetachedVc::new(turbopack_ctx, endpoint),
+            container,
+        }
+    }
+}
 
 impl Deref for ExternalEndpoint {
     type Target = DetachedVc<OptionEndpoint>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.endpoint
     }
 }
 
@@ -108,10 +130,12 @@ struct WrittenEndpointWithIssues {
 #[turbo_tasks::function(operation)]
 async fn get_written_endpoint_with_issues_operation(
     endpoint_op: OperationVc<OptionEndpoint>,
+    container: ResolvedVc<ProjectContainer>,
 ) -> Result<Vc<WrittenEndpointWithIssues>> {
     let write_to_disk_op = endpoint_write_to_disk_operation(endpoint_op);
     let (written, issues, diagnostics, effects) =
-        strongly_consistent_catch_collectables(write_to_disk_op).await?;
+        strongly_consistent_catch_collectables(write_to_disk_op, container.project().issue_rules())
+            .await?;
     Ok(WrittenEndpointWithIssues {
         written,
         issues,
@@ -128,12 +152,13 @@ pub async fn endpoint_write_to_disk(
 ) -> napi::Result<TurbopackResult<NapiWrittenEndpoint>> {
     let ctx = endpoint.turbopack_ctx();
     let endpoint_op = ***endpoint;
+    let container = endpoint.container;
     let (written, issues, diags) = endpoint
         .turbopack_ctx()
         .turbo_tasks()
         .run_once(async move {
             let written_entrypoint_with_issues_op =
-                get_written_endpoint_with_issues_operation(endpoint_op);
+                get_written_endpoint_with_issues_operation(endpoint_op, container);
             let WrittenEndpointWithIssues {
                 written,
                 issues,
@@ -162,13 +187,15 @@ pub fn endpoint_server_changed_subscribe(
     func: JsFunction,
 ) -> napi::Result<External<RootTask>> {
     let turbopack_ctx = endpoint.turbopack_ctx().clone();
+    let container = endpoint.container;
     let endpoint = ***endpoint;
     subscribe(
         turbopack_ctx,
         func,
         move || {
             async move {
-                let issues_and_diags_op = subscribe_issues_and_diags_operation(endpoint, issues);
+                let issues_and_diags_op =
+                    subscribe_issues_and_diags_operation(endpoint, container, issues);
                 let result = issues_and_diags_op.read_strongly_consistent().await?;
                 result.effects.apply().await?;
                 Ok(result)
@@ -219,13 +246,15 @@ impl Eq for EndpointIssuesAndDiags {}
 #[turbo_tasks::function(operation)]
 async fn subscribe_issues_and_diags_operation(
     endpoint_op: OperationVc<OptionEndpoint>,
+    container: ResolvedVc<ProjectContainer>,
     should_include_issues: bool,
 ) -> Result<Vc<EndpointIssuesAndDiags>> {
     let changed_op = endpoint_server_changed_operation(endpoint_op);
 
     if should_include_issues {
         let (changed_value, issues, diagnostics, effects) =
-            strongly_consistent_catch_collectables(changed_op).await?;
+            strongly_consistent_catch_collectables(changed_op, container.project().issue_rules())
+                .await?;
         Ok(EndpointIssuesAndDiags {
             changed: changed_value,
             issues,
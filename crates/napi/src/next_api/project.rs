@@ -42,11 +42,13 @@ use turbo_tasks_fs::{
     DiskFileSystem, FileContent, FileSystem, FileSystemPath, util::uri_from_file,
 };
 use turbo_unix_path::{get_relative_path_to, sys_to_unix};
+use turbopack_cli_utils::issue_report::{IssueReportFormat, write_issue_report};
 use turbopack_core::{
     PROJECT_FILESYSTEM_NAME, SOURCE_URL_PROTOCOL,
     diagnostics::PlainDiagnostic,
     error::PrettyPrintError,
-    issue::PlainIssue,
+    issue::{IssueDescriptionExt, PlainIssue},
+    module_graph::graph_export::ModuleGraphExport,
     output::{OutputAsset, OutputAssets},
     source_map::{OptionStringifiedSourceMap, SourceMap, Token},
     version::{PartialUpdate, TotalUpdate, Update, VersionState},
@@ -68,8 +70,8 @@ use crate::{
             NextTurbopackContext, create_turbo_tasks,
         },
         utils::{
-            DetachedVc, NapiDiagnostic, NapiIssue, RootTask, TurbopackResult, get_diagnostics,
-            get_issues, subscribe,
+            NapiDiagnostic, NapiIssue, RootTask, TurbopackResult, get_diagnostics, get_issues,
+            subscribe,
         },
     },
     register,
@@ -686,12 +688,14 @@ impl NapiRoute {
         pathname: String,
         value: RouteOperation,
         turbopack_ctx: &NextTurbopackContext,
+        container: ResolvedVc<ProjectContainer>,
     ) -> Self {
         let convert_endpoint = |endpoint: OperationVc<OptionEndpoint>| {
-            Some(External::new(ExternalEndpoint(DetachedVc::new(
+            Some(External::new(ExternalEndpoint::new(
                 turbopack_ctx.clone(),
+                container,
                 endpoint,
-            ))))
+            )))
         };
         match value {
             RouteOperation::Page {
@@ -753,12 +757,14 @@ impl NapiMiddleware {
     fn from_middleware(
         value: &MiddlewareOperation,
         turbopack_ctx: &NextTurbopackContext,
+        container: ResolvedVc<ProjectContainer>,
     ) -> Result<Self> {
         Ok(NapiMiddleware {
-            endpoint: External::new(ExternalEndpoint(DetachedVc::new(
+            endpoint: External::new(ExternalEndpoint::new(
                 turbopack_ctx.clone(),
+                container,
                 value.endpoint,
-            ))),
+            )),
         })
     }
 }
@@ -773,16 +779,19 @@ impl NapiInstrumentation {
     fn from_instrumentation(
         value: &InstrumentationOperation,
         turbopack_ctx: &NextTurbopackContext,
+        container: ResolvedVc<ProjectContainer>,
     ) -> Result<Self> {
         Ok(NapiInstrumentation {
-            node_js: External::new(ExternalEndpoint(DetachedVc::new(
+            node_js: External::new(ExternalEndpoint::new(
                 turbopack_ctx.clone(),
+                container,
                 value.node_js,
-            ))),
-            edge: External::new(ExternalEndpoint(DetachedVc::new(
+            )),
+            edge: External::new(ExternalEndpoint::new(
                 turbopack_ctx.clone(),
+                container,
                 value.edge,
-            ))),
+            )),
         })
     }
 }
@@ -801,34 +810,38 @@ impl NapiEntrypoints {
     fn from_entrypoints_op(
         entrypoints: &EntrypointsOperation,
         turbopack_ctx: &NextTurbopackContext,
+        container: ResolvedVc<ProjectContainer>,
     ) -> Result<Self> {
         let routes = entrypoints
             .routes
             .iter()
-            .map(|(k, v)| NapiRoute::from_route(k.to_string(), v.clone(), turbopack_ctx))
+            .map(|(k, v)| NapiRoute::from_route(k.to_string(), v.clone(), turbopack_ctx, container))
             .collect();
         let middleware = entrypoints
             .middleware
             .as_ref()
-            .map(|m| NapiMiddleware::from_middleware(m, turbopack_ctx))
+            .map(|m| NapiMiddleware::from_middleware(m, turbopack_ctx, container))
             .transpose()?;
         let instrumentation = entrypoints
             .instrumentation
             .as_ref()
-            .map(|i| NapiInstrumentation::from_instrumentation(i, turbopack_ctx))
+            .map(|i| NapiInstrumentation::from_instrumentation(i, turbopack_ctx, container))
             .transpose()?;
-        let pages_document_endpoint = External::new(ExternalEndpoint(DetachedVc::new(
+        let pages_document_endpoint = External::new(ExternalEndpoint::new(
             turbopack_ctx.clone(),
+            container,
             entrypoints.pages_document_endpoint,
-        )));
-        let pages_app_endpoint = External::new(ExternalEndpoint(DetachedVc::new(
+        ));
+        let pages_app_endpoint = External::new(ExternalEndpoint::new(
             turbopack_ctx.clone(),
+            container,
             entrypoints.pages_app_endpoint,
-        )));
-        let pages_error_endpoint = External::new(ExternalEndpoint(DetachedVc::new(
+        ));
+        let pages_error_endpoint = External::new(ExternalEndpoint::new(
             turbopack_ctx.clone(),
+            container,
             entrypoints.pages_error_endpoint,
-        )));
+        ));
         Ok(NapiEntrypoints {
             routes,
             middleware,
@@ -855,7 +868,7 @@ async fn get_entrypoints_with_issues_operation(
     let entrypoints_operation =
         EntrypointsOperation::new(project_container_entrypoints_operation(container));
     let entrypoints = entrypoints_operation.read_strongly_consistent().await?;
-    let issues = get_issues(entrypoints_operation).await?;
+    let issues = get_issues(entrypoints_operation, container.project().issue_rules()).await?;
     let diagnostics = get_diagnostics(entrypoints_operation).await?;
     let effects = Arc::new(get_effects(entrypoints_operation).await?);
     Ok(EntrypointsWithIssues {
@@ -915,6 +928,11 @@ pub async fn project_write_all_entrypoints_to_disk(
             // Write the files to disk
             effects.apply().await?;
 
+            // Write the emit manifest last, once all the files it lists are on disk
+            let manifest_op = emit_output_manifest_operation(container, app_dir_only);
+            manifest_op.read_strongly_consistent().await?;
+            get_effects(manifest_op).await?.apply().await?;
+
             // Send a compilation event to indicate that the files have been written to disk
             tt_clone.send_compilation_event(Arc::new(TimingEvent::new(
                 "Finished writing to disk".to_owned(),
@@ -927,12 +945,119 @@ pub async fn project_write_all_entrypoints_to_disk(
         .await?;
 
     Ok(TurbopackResult {
-        result: NapiEntrypoints::from_entrypoints_op(&entrypoints, &project.turbopack_ctx)?,
+        result: NapiEntrypoints::from_entrypoints_op(
+            &entrypoints,
+            &project.turbopack_ctx,
+            container,
+        )?,
         issues: issues.iter().map(|i| NapiIssue::from(&**i)).collect(),
         diagnostics: diags.iter().map(|d| NapiDiagnostic::from(d)).collect(),
     })
 }
 
+/// Writes the issues of the last `project_write_all_entrypoints_to_disk` call to `path` in a
+/// machine-readable `format` (`sarif` or `json-lines`).
+#[napi]
+pub async fn project_write_issue_report(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    app_dir_only: bool,
+    #[napi(ts_arg_type = "'sarif' | 'json-lines'")] format: String,
+    path: String,
+) -> napi::Result<()> {
+    let ctx = &project.turbopack_ctx;
+    let container = project.container;
+    let format = format
+        .parse::<IssueReportFormat>()
+        .map_err(|e| napi::Error::from_reason(e.to_string()))?;
+
+    ctx.turbo_tasks()
+        .run_once(async move {
+            let EntrypointsWithIssues { issues, .. } =
+                &*get_all_written_entrypoints_with_issues_operation(container, app_dir_only)
+                    .read_strongly_consistent()
+                    .await?;
+            write_issue_report(format, issues, Some(&PathBuf::from(path)))
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))
+}
+
+#[turbo_tasks::value(transparent)]
+struct IssueRuleMatches(Vec<u32>);
+
+/// Returns how many issues of the last `project_write_all_entrypoints_to_disk` call each of the
+/// `turbopack.issueRules` matched, in the order of the rules.
+#[napi]
+pub async fn project_issue_rule_matches(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    app_dir_only: bool,
+) -> napi::Result<Vec<u32>> {
+    let ctx = &project.turbopack_ctx;
+    let container = project.container;
+    ctx.turbo_tasks()
+        .run_once(async move {
+            let matches = issue_rule_matches_operation(container, app_dir_only)
+                .read_strongly_consistent()
+                .await?;
+            Ok(matches.to_vec())
+        })
+        .or_else(|e| ctx.throw_turbopack_internal_result(&e))
+        .await
+}
+
+#[turbo_tasks::function(operation)]
+async fn issue_rule_matches_operation(
+    container: ResolvedVc<ProjectContainer>,
+    app_dir_only: bool,
+) -> Result<Vc<IssueRuleMatches>> {
+    let entrypoints_operation = EntrypointsOperation::new(all_entrypoints_write_to_disk_operation(
+        container,
+        app_dir_only,
+    ));
+    let _ = entrypoints_operation.read_strongly_consistent().await?;
+    let matches = entrypoints_operation
+        .peek_issues_with_path()
+        .await?
+        .with_rules(container.project().issue_rules().to_resolved().await?)
+        .get_plain_issues_with_rule_matches()
+        .await?
+        .matches;
+    Ok(Vc::cell(
+        matches.into_iter().map(|count| count as u32).collect(),
+    ))
+}
+
+/// Writes the module graph of the whole app to `path`, in the Graphviz DOT format when the path
+/// ends in `.dot` and as JSON otherwise.
+#[napi]
+pub async fn project_write_module_graph_export(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    path: String,
+) -> napi::Result<()> {
+    let ctx = &project.turbopack_ctx;
+    let container = project.container;
+    ctx.turbo_tasks()
+        .run_once(async move {
+            let export = module_graph_export_operation(container)
+                .read_strongly_consistent()
+                .await?;
+            let content = if path.ends_with(".dot") {
+                export.to_dot()
+            } else {
+                export.to_json()?
+            };
+            std::fs::write(&path, content)
+                .with_context(|| format!("failed to write the module graph to {path}"))
+        })
+        .or_else(|e| ctx.throw_turbopack_internal_result(&e))
+        .await
+}
+
+#[turbo_tasks::function(operation)]
+fn module_graph_export_operation(container: ResolvedVc<ProjectContainer>) -> Vc<ModuleGraphExport> {
+    container.project().module_graph_export()
+}
+
 #[turbo_tasks::function(operation)]
 async fn get_all_written_entrypoints_with_issues_operation(
     container: ResolvedVc<ProjectContainer>,
@@ -943,7 +1068,7 @@ async fn get_all_written_entrypoints_with_issues_operation(
         app_dir_only,
     ));
     let entrypoints = entrypoints_operation.read_strongly_consistent().await?;
-    let issues = get_issues(entrypoints_operation).await?;
+    let issues = get_issues(entrypoints_operation, container.project().issue_rules()).await?;
     let diagnostics = get_diagnostics(entrypoints_operation).await?;
     let effects = Arc::new(get_effects(entrypoints_operation).await?);
     Ok(EntrypointsWithIssues {
@@ -969,6 +1094,20 @@ pub async fn all_entrypoints_write_to_disk_operation(
     Ok(project.entrypoints())
 }
 
+#[turbo_tasks::function(operation)]
+async fn emit_output_manifest_operation(
+    project: ResolvedVc<ProjectContainer>,
+    app_dir_only: bool,
+) -> Result<()> {
+    project
+        .project()
+        .emit_output_manifest(output_assets_operation(project, app_dir_only))
+        .as_side_effect()
+        .await?;
+
+    Ok(())
+}
+
 #[turbo_tasks::function(operation)]
 async fn output_assets_operation(
     container: ResolvedVc<ProjectContainer>,
@@ -1021,7 +1160,11 @@ pub fn project_entrypoints_subscribe(
             let (entrypoints, issues, diags) = ctx.value;
 
             Ok(vec![TurbopackResult {
-                result: NapiEntrypoints::from_entrypoints_op(&entrypoints, &turbopack_ctx)?,
+                result: NapiEntrypoints::from_entrypoints_op(
+                    &entrypoints,
+                    &turbopack_ctx,
+                    container,
+                )?,
                 issues: issues
                     .iter()
                     .map(|issue| NapiIssue::from(&**issue))
@@ -1048,7 +1191,7 @@ async fn hmr_update_with_issues_operation(
 ) -> Result<Vc<HmrUpdateWithIssues>> {
     let update_op = project_hmr_update_operation(project, identifier, state);
     let update = update_op.read_strongly_consistent().await?;
-    let issues = get_issues(update_op).await?;
+    let issues = get_issues(update_op, project.issue_rules()).await?;
     let diagnostics = get_diagnostics(update_op).await?;
     let effects = Arc::new(get_effects(update_op).await?);
     Ok(HmrUpdateWithIssues {
@@ -1176,7 +1319,7 @@ async fn get_hmr_identifiers_with_issues_operation(
 ) -> Result<Vc<HmrIdentifiersWithIssues>> {
     let hmr_identifiers_op = project_container_hmr_identifiers_operation(container);
     let hmr_identifiers = hmr_identifiers_op.read_strongly_consistent().await?;
-    let issues = get_issues(hmr_identifiers_op).await?;
+    let issues = get_issues(hmr_identifiers_op, container.project().issue_rules()).await?;
     let diagnostics = get_diagnostics(hmr_identifiers_op).await?;
     let effects = Arc::new(get_effects(hmr_identifiers_op).await?);
     Ok(HmrIdentifiersWithIssues {
@@ -16,7 +16,8 @@ use turbo_tasks_fs::FileContent;
 use turbopack_core::{
     diagnostics::{Diagnostic, DiagnosticContextExt, PlainDiagnostic},
     issue::{
-        IssueDescriptionExt, IssueSeverity, PlainIssue, PlainIssueSource, PlainSource, StyledString,
+        IssueDescriptionExt, IssueSeverity, PlainIssue, PlainIssueSource, PlainSource,
+        StyledString, rules::IssueRules,
     },
     source_pos::SourcePos,
 };
@@ -100,8 +101,14 @@ pub fn root_task_dispose(
     Ok(())
 }
 
-pub async fn get_issues<T: Send>(source: OperationVc<T>) -> Result<Arc<Vec<ReadRef<PlainIssue>>>> {
-    let issues = source.peek_issues_with_path().await?;
+pub async fn get_issues<T: Send>(
+    source: OperationVc<T>,
+    issue_rules: Vc<IssueRules>,
+) -> Result<Arc<Vec<ReadRef<PlainIssue>>>> {
+    let issues = source
+        .peek_issues_with_path()
+        .await?
+        .with_rules(issue_rules.to_resolved().await?);
     Ok(Arc::new(issues.get_plain_issues().await?))
 }
 
@@ -361,6 +368,7 @@ pub fn subscribe<T: 'static + Send + Sync, F: Future<Output = Result<T>> + Send,
 // propagate any actual error results.
 pub async fn strongly_consistent_catch_collectables<R: VcValueType + Send>(
     source_op: OperationVc<R>,
+    issue_rules: Vc<IssueRules>,
 ) -> Result<(
     Option<ReadRef<R>>,
     Arc<Vec<ReadRef<PlainIssue>>>,
@@ -368,7 +376,7 @@ pub async fn strongly_consistent_catch_collectables<R: VcValueType + Send>(
     Arc<Effects>,
 )> {
     let result = source_op.read_strongly_consistent().await;
-    let issues = get_issues(source_op).await?;
+    let issues = get_issues(source_op, issue_rules).await?;
     let diagnostics = get_diagnostics(source_op).await?;
     let effects = Arc::new(get_effects(source_op).await?);
 
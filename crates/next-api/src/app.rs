@@ -58,7 +58,7 @@ use turbopack_core::{
     asset::AssetContent,
     chunk::{
         ChunkGroupResult, ChunkingContext, ChunkingContextExt, EvaluatableAsset, EvaluatableAssets,
-        availability_info::AvailabilityInfo,
+        availability_info::AvailabilityInfo, budget::check_chunk_group_budgets,
     },
     file_source::FileSource,
     ident::{AssetIdent, Layer},
@@ -1315,6 +1315,28 @@ impl AppEndpoint {
         client_assets.extend(entry_client_chunks.iter().copied());
         server_assets.extend(entry_ssr_chunks.iter().copied());
 
+        if matches!(
+            this.ty,
+            AppEndpointType::Page {
+                ty: AppPageEndpointType::Html,
+                ..
+            }
+        ) {
+            // Check the size of all client chunks that are loaded by the page
+            check_chunk_group_budgets(
+                *client_chunking_context,
+                rsc_entry.ident(),
+                Vc::cell(
+                    entry_client_chunks
+                        .iter()
+                        .chain(client_shared_chunk_group.assets.await?.iter())
+                        .copied()
+                        .collect(),
+                ),
+            )
+            .await?;
+        }
+
         let manifest_path_prefix = &app_entry.original_name;
 
         if emit_manifests != EmitManifests::None {
@@ -48,7 +48,7 @@ use turbopack_core::{
     asset::AssetContent,
     chunk::{
         ChunkGroupResult, ChunkingContext, ChunkingContextExt, EvaluatableAsset, EvaluatableAssets,
-        availability_info::AvailabilityInfo,
+        availability_info::AvailabilityInfo, budget::check_chunk_group_budgets,
     },
     context::AssetContext,
     file_source::FileSource,
@@ -1372,6 +1372,12 @@ impl PageEndpoint {
             PageEndpointType::Html => {
                 let client_chunks = *self.client_chunks().await?.assets;
                 client_assets.extend(client_chunks.await?.iter().map(|asset| **asset));
+                check_chunk_group_budgets(
+                    this.pages_project.project().client_chunking_context(),
+                    AssetIdent::from_path(this.page.await?.base_path.clone()),
+                    client_chunks,
+                )
+                .await?;
                 let build_manifest = self.build_manifest(client_chunks).to_resolved().await?;
                 let page_loader = self.page_loader(client_chunks);
                 let client_build_manifest = self
@@ -5,8 +5,9 @@ use indexmap::map::Entry;
 use next_core::{
     all_assets_from_entries,
     app_structure::find_app_dir,
-    emit_assets, get_edge_chunking_context, get_edge_chunking_context_with_client_assets,
-    get_edge_compile_time_info, get_edge_resolve_options_context,
+    emit_assets, emit_manifest, get_edge_chunking_context,
+    get_edge_chunking_context_with_client_assets, get_edge_compile_time_info,
+    get_edge_resolve_options_context,
     instrumentation::instrumentation_files,
     middleware::middleware_files,
     mode::NextMode,
@@ -36,11 +37,16 @@ use turbo_tasks::{
     trace::TraceRawVcs,
 };
 use turbo_tasks_env::{EnvMap, ProcessEnv};
-use turbo_tasks_fs::{DiskFileSystem, FileSystem, FileSystemPath, VirtualFileSystem, invalidation};
+use turbo_tasks_fs::{
+    DiskFileSystem, DiskWriteOptions, File, FileContent, FileSystem, FileSystemPath,
+    VirtualFileSystem, invalidation,
+};
 use turbo_unix_path::{join_path, unix_to_sys};
 use turbopack::{
-    ModuleAssetContext, evaluate_context::node_build_environment,
-    global_module_ids::get_global_module_id_strategy, transition::TransitionOptions,
+    ModuleAssetContext,
+    evaluate_context::node_build_environment,
+    global_module_ids::{get_global_module_id_strategy, write_module_id_records},
+    transition::TransitionOptions,
 };
 use turbopack_core::{
     PROJECT_FILESYSTEM_NAME,
@@ -57,13 +63,15 @@ use turbopack_core::{
     ident::Layer,
     issue::{
         Issue, IssueDescriptionExt, IssueExt, IssueSeverity, IssueStage, OptionStyledString,
-        StyledString,
+        StyledString, rules::IssueRules,
     },
     module::Module,
     module_graph::{
         GraphEntries, ModuleGraph, SingleModuleGraph, VisitedModules,
         chunk_group_info::ChunkGroupEntry,
+        cycles::check_circular_dependencies,
         export_usage::{OptionExportUsageInfo, compute_export_usage_info},
+        graph_export::{ModuleGraphExport, export_module_graph},
     },
     output::{OutputAsset, OutputAssets},
     reference_type::{EntryReferenceSubType, ReferenceType},
@@ -73,6 +81,7 @@ use turbopack_core::{
         NotFoundVersion, OptionVersionedContent, Update, Version, VersionState, VersionedContent,
     },
 };
+use turbopack_ecmascript::unused_exports::{check_unused_exports, find_unused_exports};
 use turbopack_node::execution_context::ExecutionContext;
 use turbopack_nodejs::NodeJsChunkingContext;
 
@@ -683,8 +692,19 @@ impl Project {
     }
 
     #[turbo_tasks::function]
-    pub fn output_fs(&self) -> Vc<DiskFileSystem> {
-        DiskFileSystem::new(rcstr!("output"), self.root_path.clone())
+    pub async fn output_fs(&self) -> Result<Vc<DiskFileSystem>> {
+        // Builds write their output atomically, so that an interrupted build never leaves
+        // truncated files behind that a deployment could pick up.
+        let is_production = self.mode.await?.is_production();
+        let write_options = DiskWriteOptions {
+            atomic: is_production,
+            fsync: is_production && *self.next_config.turbo_fsync().await?,
+        };
+        Ok(DiskFileSystem::new_with_write_options(
+            rcstr!("output"),
+            self.root_path.clone(),
+            write_options,
+        ))
     }
 
     #[turbo_tasks::function]
@@ -773,6 +793,12 @@ impl Project {
         *self.next_config
     }
 
+    /// The rules that suppress issues or override their severity, from `turbopack.issueRules`.
+    #[turbo_tasks::function]
+    pub fn issue_rules(&self) -> Vc<IssueRules> {
+        self.next_config.turbopack_issue_rules()
+    }
+
     #[turbo_tasks::function]
     pub(super) fn next_mode(&self) -> Vc<NextMode> {
         *self.mode
@@ -941,7 +967,10 @@ impl Project {
         entry: ResolvedVc<Box<dyn Module>>,
     ) -> Result<Vc<ModuleGraph>> {
         Ok(if *self.per_page_module_graph().await? {
-            ModuleGraph::from_entry_module(*entry, self.next_mode().await?.is_production())
+            let graph =
+                ModuleGraph::from_entry_module(*entry, self.next_mode().await?.is_production());
+            report_circular_dependencies(self, graph).await?;
+            graph
         } else {
             *self.whole_app_module_graphs().await?.full
         })
@@ -959,10 +988,12 @@ impl Project {
                 .copied()
                 .map(ResolvedVc::upcast)
                 .collect();
-            ModuleGraph::from_modules(
+            let graph = ModuleGraph::from_modules(
                 Vc::cell(vec![ChunkGroupEntry::Entry(entries)]),
                 self.next_mode().await?.is_production(),
-            )
+            );
+            report_circular_dependencies(self, graph).await?;
+            graph
         } else {
             *self.whole_app_module_graphs().await?.full
         })
@@ -974,6 +1005,8 @@ impl Project {
             let module_graphs_op = whole_app_module_graph_operation(self);
             let module_graphs_vc = module_graphs_op.resolve_strongly_consistent().await?;
             let _ = module_graphs_op.take_issues_with_path().await?;
+            report_circular_dependencies(*self, *module_graphs_vc.await?.full).await?;
+            report_unused_exports(*self, *module_graphs_vc.await?.full).await?;
 
             // At this point all modules have been computed and we can get rid of the node.js
             // process pools
@@ -1037,9 +1070,12 @@ impl Project {
             module_id_strategy: self.module_ids(),
             export_usage: self.export_usage(),
             minify: self.next_config().turbo_minify(self.next_mode()),
+            minify_config: self.next_config().turbopack_minify_options(),
             source_maps: self.next_config().client_source_maps(self.next_mode()),
             no_mangling: self.no_mangling(),
             scope_hoisting: self.next_config().turbo_scope_hoisting(self.next_mode()),
+            chunking: self.next_config().turbopack_chunking(),
+            budgets: self.next_config().turbopack_budgets(),
         }))
     }
 
@@ -1057,6 +1093,7 @@ impl Project {
             module_id_strategy: self.module_ids(),
             export_usage: self.export_usage(),
             turbo_minify: self.next_config().turbo_minify(self.next_mode()),
+            turbo_minify_config: self.next_config().turbopack_minify_options(),
             turbo_source_maps: self.next_config().server_source_maps(),
             no_mangling: self.no_mangling(),
             scope_hoisting: self.next_config().turbo_scope_hoisting(self.next_mode()),
@@ -1086,6 +1123,7 @@ impl Project {
             module_id_strategy: self.module_ids(),
             export_usage: self.export_usage(),
             turbo_minify: self.next_config().turbo_minify(self.next_mode()),
+            turbo_minify_config: self.next_config().turbopack_minify_options(),
             turbo_source_maps: self.next_config().server_source_maps(),
             no_mangling: self.no_mangling(),
             scope_hoisting: self.next_config().turbo_scope_hoisting(self.next_mode()),
@@ -1656,6 +1694,8 @@ impl Project {
                 )
                 .as_side_effect()
                 .await?;
+                self.emit_module_id_records().as_side_effect().await?;
+                self.emit_unused_exports_report().as_side_effect().await?;
 
                 Ok(())
             }
@@ -1664,6 +1704,84 @@ impl Project {
         .await
     }
 
+    /// Writes the `deterministic` module ids of the whole app to the records file configured with
+    /// `turbopack.moduleIdsRecordsPath`, so that the next build reuses them.
+    #[turbo_tasks::function]
+    pub async fn emit_module_id_records(self: Vc<Self>) -> Result<()> {
+        let module_id_strategy = *self.next_config().module_ids(self.next_mode()).await?;
+        if !matches!(module_id_strategy, ModuleIdStrategyConfig::Deterministic) {
+            return Ok(());
+        }
+        let Some(records_path) = &*self.next_config().module_ids_records_path().await? else {
+            return Ok(());
+        };
+        let module_graphs = self.whole_app_module_graphs().await?;
+        write_module_id_records(
+            *module_graphs.full,
+            self.project_path().await?.join(records_path)?,
+        )
+        .as_side_effect()
+        .await?;
+        Ok(())
+    }
+
+    /// Writes the exports of the app's own modules that are never imported to
+    /// `unused-exports.json` in the dist directory when `turbopack.unusedExportsReport` is set.
+    #[turbo_tasks::function]
+    pub async fn emit_unused_exports_report(self: Vc<Self>) -> Result<()> {
+        if !self.next_mode().await?.is_production()
+            || !*self.next_config().turbopack_unused_exports_report().await?
+        {
+            return Ok(());
+        }
+        let module_graphs = self.whole_app_module_graphs().await?;
+        let unused_exports =
+            find_unused_exports(*module_graphs.full, self.project_path().owned().await?).await?;
+        self.node_root()
+            .await?
+            .join("unused-exports.json")?
+            .write(FileContent::Content(File::from(unused_exports.to_json()?)).cell())
+            .as_side_effect()
+            .await?;
+        Ok(())
+    }
+
+    /// Exports the whole app module graph for external tooling. Module sizes are estimated with the
+    /// client chunking context. See [`export_module_graph`].
+    #[turbo_tasks::function]
+    pub async fn module_graph_export(self: Vc<Self>) -> Result<Vc<ModuleGraphExport>> {
+        Ok(export_module_graph(
+            *self.whole_app_module_graphs().await?.full,
+            Some(self.client_chunking_context()),
+        ))
+    }
+
+    /// Writes the emit manifest for the given output assets. See [`emit_manifest`].
+    ///
+    /// The effects of this function must be applied after the effects of
+    /// [`Project::emit_all_output_assets`], so that the manifest is the last file that is written.
+    #[turbo_tasks::function]
+    pub async fn emit_output_manifest(
+        self: Vc<Self>,
+        output_assets: OperationVc<OutputAssets>,
+    ) -> Result<()> {
+        let all_output_assets = all_assets_from_entries_operation(output_assets);
+
+        let client_relative_path = self.client_relative_path().owned().await?;
+        let node_root = self.node_root().owned().await?;
+
+        emit_manifest(
+            all_output_assets.connect(),
+            node_root.clone(),
+            client_relative_path,
+            node_root,
+        )
+        .as_side_effect()
+        .await?;
+
+        Ok(())
+    }
+
     #[turbo_tasks::function]
     async fn hmr_content(self: Vc<Self>, identifier: RcStr) -> Result<Vc<OptionVersionedContent>> {
         if let Some(map) = self.await?.versioned_content_map {
@@ -1780,8 +1898,13 @@ impl Project {
             ModuleIdStrategyConfig::Named => Ok(Vc::upcast(DevModuleIdStrategy::new())),
             ModuleIdStrategyConfig::Deterministic => {
                 let module_graphs = self.whole_app_module_graphs().await?;
+                let records_path = match &*self.next_config().module_ids_records_path().await? {
+                    Some(records_path) => Some(self.project_path().await?.join(records_path)?),
+                    None => None,
+                };
                 Ok(Vc::upcast(get_global_module_id_strategy(
                     *module_graphs.full,
+                    records_path,
                 )))
             }
         }
@@ -1838,6 +1961,33 @@ async fn whole_app_module_graph_operation(
     .cell())
 }
 
+/// Emits issues for the import cycles of `graph` when `turbopack.circularDependencies` is
+/// configured.
+async fn report_circular_dependencies(project: Vc<Project>, graph: Vc<ModuleGraph>) -> Result<()> {
+    if let Some(options) = &*project
+        .next_config()
+        .turbopack_circular_dependencies()
+        .await?
+    {
+        check_circular_dependencies(graph, options.clone()).await?;
+    }
+    Ok(())
+}
+
+/// Reports the exports of the app's own modules that are never imported by the server or client
+/// code of the whole app.
+async fn report_unused_exports(project: Vc<Project>, graph: Vc<ModuleGraph>) -> Result<()> {
+    if project.next_mode().await?.is_production()
+        && *project
+            .next_config()
+            .turbopack_unused_exports_report()
+            .await?
+    {
+        check_unused_exports(graph, project.project_path().owned().await?).await?;
+    }
+    Ok(())
+}
+
 #[turbo_tasks::value(shared)]
 pub struct ModuleGraphs {
     pub base: ResolvedVc<ModuleGraph>,
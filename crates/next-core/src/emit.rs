@@ -1,15 +1,21 @@
 use anyhow::Result;
+use serde::Serialize;
 use tracing::Instrument;
+use turbo_rcstr::RcStr;
 use turbo_tasks::{
     FxIndexSet, ResolvedVc, TryFlatJoinIterExt, Vc,
     graph::{AdjacencyMap, GraphTraversal},
 };
-use turbo_tasks_fs::{FileSystemPath, rebase};
+use turbo_tasks_fs::{File, FileContent, FileSystemPath, rebase};
+use turbo_tasks_hash::{encode_hex, hash_xxh3_hash64};
 use turbopack_core::{
     asset::Asset,
     output::{OutputAsset, OutputAssets},
 };
 
+/// The file name of the manifest written by [`emit_manifest`], relative to the node root.
+pub const EMIT_MANIFEST_FILENAME: &str = "turbopack-emit-manifest.json";
+
 /// Emits all assets transitively reachable from the given chunks, that are
 /// inside the node root or the client root.
 ///
@@ -113,6 +119,83 @@ async fn emit_rebase(
     Ok(())
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EmitManifest {
+    files: Vec<EmitManifestEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EmitManifestEntry {
+    /// The path of the emitted file, relative to the node root when it is nested inside of it.
+    path: RcStr,
+    /// The size of the file in bytes.
+    size: u64,
+    /// The xxh3 hash of the bytes of the file, as a hex string.
+    hash: RcStr,
+}
+
+/// Writes a manifest listing every file emitted by [`emit_assets`] for the same arguments, along
+/// with its size and content hash.
+///
+/// The effects of this function must be applied after the effects of [`emit_assets`] have been
+/// applied. A missing manifest, or one that doesn't match the files on disk, means that the output
+/// directory is incomplete, e.g. because the build was interrupted.
+#[turbo_tasks::function]
+pub async fn emit_manifest(
+    assets: Vc<OutputAssets>,
+    node_root: FileSystemPath,
+    client_relative_path: FileSystemPath,
+    client_output_path: FileSystemPath,
+) -> Result<()> {
+    let files = assets
+        .await?
+        .iter()
+        .map(|asset| {
+            let node_root = node_root.clone();
+            let client_relative_path = client_relative_path.clone();
+            let client_output_path = client_output_path.clone();
+
+            async move {
+                let path = asset.path().owned().await?;
+                let path = if path.is_inside_ref(&node_root) {
+                    path
+                } else if path.is_inside_ref(&client_relative_path) {
+                    rebase(path, client_relative_path, client_output_path)
+                        .owned()
+                        .await?
+                } else {
+                    return Ok(None);
+                };
+                let content = asset.content().file_content().await?;
+                let FileContent::Content(file) = &*content else {
+                    return Ok(None);
+                };
+                let bytes = file.content().to_bytes();
+                let path = match node_root.get_path_to(&path) {
+                    Some(relative_path) => relative_path.into(),
+                    None => path.value_to_string().owned().await?,
+                };
+                Ok(Some(EmitManifestEntry {
+                    path,
+                    size: bytes.len() as u64,
+                    hash: encode_hex(hash_xxh3_hash64(&*bytes)).into(),
+                }))
+            }
+        })
+        .try_flat_join()
+        .await?;
+
+    let manifest = EmitManifest { files };
+    node_root
+        .join(EMIT_MANIFEST_FILENAME)?
+        .write(FileContent::Content(File::from(serde_json::to_string_pretty(&manifest)?)).cell())
+        .as_side_effect()
+        .await?;
+    Ok(())
+}
+
 /// Walks the asset graph from multiple assets and collect all referenced
 /// assets.
 #[turbo_tasks::function]
@@ -19,14 +19,14 @@ use turbopack_browser::{
 };
 use turbopack_core::{
     chunk::{
-        ChunkingConfig, ChunkingContext, MangleType, MinifyType, SourceMapsType,
-        module_id_strategies::ModuleIdStrategy,
+        ChunkingConfig, ChunkingContext, MangleType, MinifyOptions, MinifyType, SourceMapsType,
+        budget::OptionChunkBudgets, module_id_strategies::ModuleIdStrategy,
     },
     compile_time_info::{CompileTimeDefines, CompileTimeInfo, FreeVarReference, FreeVarReferences},
     environment::{BrowserEnvironment, Environment, ExecutionEnvironment},
     free_var_references,
     module_graph::export_usage::OptionExportUsageInfo,
-    resolve::{parse::Request, pattern::Pattern},
+    resolve::{parse::Request, pattern::Pattern, pnp::find_pnp_manifest},
 };
 use turbopack_ecmascript::chunk::EcmascriptChunkType;
 use turbopack_node::{
@@ -39,7 +39,7 @@ use crate::{
     mode::NextMode,
     next_build::get_postcss_package_mapping,
     next_client::runtime_entry::{RuntimeEntries, RuntimeEntry},
-    next_config::NextConfig,
+    next_config::{NextConfig, OptionTurbopackChunkingConfig, OptionTurbopackMinifyConfig},
     next_font::local::NextFontLocalResolvePlugin,
     next_import_map::{
         get_next_client_fallback_import_map, get_next_client_import_map,
@@ -151,8 +151,10 @@ pub async fn get_client_resolve_options_context(
             .to_resolved()
             .await?;
     let custom_conditions = mode.await?.custom_resolve_conditions().collect();
+    let root = project_path.root().owned().await?;
     let resolve_options_context = ResolveOptionsContext {
-        enable_node_modules: Some(project_path.root().owned().await?),
+        enable_pnp: find_pnp_manifest(root.clone()).owned().await?,
+        enable_node_modules: Some(root),
         custom_conditions,
         import_map: Some(next_client_import_map),
         fallback_import_map: Some(next_client_fallback_import_map),
@@ -396,9 +398,12 @@ pub struct ClientChunkingContextOptions {
     pub module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
     pub export_usage: Vc<OptionExportUsageInfo>,
     pub minify: Vc<bool>,
+    pub minify_config: Vc<OptionTurbopackMinifyConfig>,
     pub source_maps: Vc<bool>,
     pub no_mangling: Vc<bool>,
     pub scope_hoisting: Vc<bool>,
+    pub chunking: Vc<OptionTurbopackChunkingConfig>,
+    pub budgets: Vc<OptionChunkBudgets>,
 }
 
 #[turbo_tasks::function]
@@ -416,9 +421,12 @@ pub async fn get_client_chunking_context(
         module_id_strategy,
         export_usage,
         minify,
+        minify_config,
         source_maps,
         no_mangling,
         scope_hoisting,
+        chunking,
+        budgets,
     } = options;
 
     let next_mode = mode.await?;
@@ -437,9 +445,14 @@ pub async fn get_client_chunking_context(
     .chunk_base_path(asset_prefix.clone())
     .chunk_suffix_path(chunk_suffix_path)
     .minify_type(if *minify.await? {
-        MinifyType::Minify {
+        let mut minify_options = MinifyOptions {
             mangle: (!*no_mangling.await?).then_some(MangleType::OptimalSize),
+            ..Default::default()
+        };
+        if let Some(minify_config) = &*minify_config.await? {
+            minify_options = minify_config.apply_to(minify_options);
         }
+        MinifyType::Minify(minify_options)
     } else {
         MinifyType::NoMinify
     })
@@ -459,15 +472,19 @@ pub async fn get_client_chunking_context(
             .use_file_source_map_uris()
             .dynamic_chunk_content_loading(true);
     } else {
+        let mut chunking_config = ChunkingConfig {
+            min_chunk_size: 50_000,
+            max_chunk_count_per_group: 40,
+            max_merge_chunk_size: 200_000,
+            ..Default::default()
+        };
+        if let Some(chunking) = &*chunking.await? {
+            chunking_config = chunking.apply_to(chunking_config);
+        }
         builder = builder
             .chunking_config(
                 Vc::<EcmascriptChunkType>::default().to_resolved().await?,
-                ChunkingConfig {
-                    min_chunk_size: 50_000,
-                    max_chunk_count_per_group: 40,
-                    max_merge_chunk_size: 200_000,
-                    ..Default::default()
-                },
+                chunking_config,
             )
             .chunking_config(
                 Vc::<CssChunkType>::default().to_resolved().await?,
@@ -478,6 +495,9 @@ pub async fn get_client_chunking_context(
             )
             .use_content_hashing(ContentHashing::Direct { length: 16 })
             .module_merging(*scope_hoisting.await?);
+        if let Some(budgets) = *budgets.await? {
+            builder = builder.chunk_budgets(budgets);
+        }
     }
 
     Ok(Vc::upcast(builder.build()))
@@ -18,7 +18,15 @@ use turbopack::module_options::{
     module_options_context::{MdxTransformOptions, OptionWebpackConditions},
 };
 use turbopack_core::{
-    issue::{Issue, IssueExt, IssueStage, OptionStyledString, StyledString},
+    chunk::{
+        ChunkCacheGroup, ChunkingConfig, MinifyOptions,
+        budget::{ChunkBudgets, OptionChunkBudgets},
+    },
+    issue::{
+        Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString,
+        rules::{IssueRule, IssueRuleAction, IssueRules},
+    },
+    module_graph::cycles::{CircularDependencyOptions, OptionCircularDependencyOptions},
     resolve::ResolveAliasMap,
 };
 use turbopack_ecmascript::{OptionTreeShaking, TreeShakingMode};
@@ -563,6 +571,286 @@ pub struct TurbopackConfig {
     pub resolve_alias: Option<FxIndexMap<RcStr, JsonValue>>,
     pub resolve_extensions: Option<Vec<RcStr>>,
     pub module_ids: Option<ModuleIds>,
+    /// A path, relative to the project directory, of a file that persists the `deterministic`
+    /// module ids between builds.
+    pub module_ids_records_path: Option<RcStr>,
+    /// Overrides for the production chunking of client JavaScript.
+    pub chunking: Option<TurbopackChunkingConfig>,
+    /// Size budgets for the client JavaScript of each entry.
+    pub budgets: Option<TurbopackBudgetsConfig>,
+    /// Rules that suppress issues or override their severity.
+    pub issue_rules: Option<Vec<TurbopackIssueRule>>,
+    /// Reports import cycles in the module graph when set.
+    pub circular_dependencies: Option<TurbopackCircularDependenciesConfig>,
+    /// Reports the exports of first-party modules that are never imported when set.
+    pub unused_exports_report: Option<bool>,
+    /// Overrides for the options of the JavaScript minifier.
+    pub minify_options: Option<TurbopackMinifyConfig>,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+    OperationValue,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TurbopackChunkingConfig {
+    pub min_chunk_size: Option<usize>,
+    pub max_chunk_size: Option<usize>,
+    pub max_chunk_count_per_group: Option<usize>,
+    pub max_merge_chunk_size: Option<usize>,
+    pub cache_groups: Option<Vec<TurbopackChunkCacheGroup>>,
+}
+
+impl TurbopackChunkingConfig {
+    /// Applies the configured values on top of the given default chunking config.
+    pub fn apply_to(&self, chunking_config: ChunkingConfig) -> ChunkingConfig {
+        ChunkingConfig {
+            min_chunk_size: self
+                .min_chunk_size
+                .unwrap_or(chunking_config.min_chunk_size),
+            max_chunk_size: self
+                .max_chunk_size
+                .unwrap_or(chunking_config.max_chunk_size),
+            max_chunk_count_per_group: self
+                .max_chunk_count_per_group
+                .unwrap_or(chunking_config.max_chunk_count_per_group),
+            max_merge_chunk_size: self
+                .max_merge_chunk_size
+                .unwrap_or(chunking_config.max_merge_chunk_size),
+            cache_groups: match &self.cache_groups {
+                Some(cache_groups) => cache_groups
+                    .iter()
+                    .map(|cache_group| ChunkCacheGroup {
+                        name: cache_group.name.clone(),
+                        test: cache_group.test.clone(),
+                    })
+                    .collect(),
+                None => chunking_config.cache_groups,
+            },
+            ..chunking_config
+        }
+    }
+}
+
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue, OperationValue,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TurbopackChunkCacheGroup {
+    pub name: RcStr,
+    /// The source of a regular expression matched against module idents.
+    pub test: RcStr,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionTurbopackChunkingConfig(Option<TurbopackChunkingConfig>);
+
+/// Options of the JavaScript minifier, named like the terser options, so they aren't camel cased.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+    OperationValue,
+)]
+pub struct TurbopackMinifyConfig {
+    pub compress: Option<TurbopackMinifyCompressConfig>,
+    pub mangle: Option<TurbopackMinifyMangleConfig>,
+    /// Keeps class names, even when names are mangled.
+    pub keep_classnames: Option<bool>,
+    /// Keeps function names, even when names are mangled.
+    pub keep_fnames: Option<bool>,
+    /// The ECMAScript version the minifier may use in its output, e.g. `5` or `2020`.
+    pub ecma: Option<u32>,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+    OperationValue,
+)]
+pub struct TurbopackMinifyCompressConfig {
+    pub passes: Option<usize>,
+    pub drop_console: Option<bool>,
+    pub drop_debugger: Option<bool>,
+    /// Functions whose calls can be removed when their result is unused, e.g. `console.info`.
+    pub pure_funcs: Option<Vec<RcStr>>,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+    OperationValue,
+)]
+pub struct TurbopackMinifyMangleConfig {
+    /// Names that are never mangled.
+    pub reserved: Option<Vec<RcStr>>,
+    /// Whether names in the top level scope are mangled too.
+    pub toplevel: Option<bool>,
+}
+
+impl TurbopackMinifyConfig {
+    /// Applies the configured values on top of the given default minify options.
+    pub fn apply_to(&self, minify_options: MinifyOptions) -> MinifyOptions {
+        let compress = self.compress.as_ref();
+        let mangle = self.mangle.as_ref();
+        MinifyOptions {
+            reserved: mangle
+                .and_then(|mangle| mangle.reserved.clone())
+                .unwrap_or(minify_options.reserved),
+            top_level: mangle
+                .and_then(|mangle| mangle.toplevel)
+                .or(minify_options.top_level),
+            passes: compress
+                .and_then(|compress| compress.passes)
+                .unwrap_or(minify_options.passes),
+            drop_console: compress
+                .and_then(|compress| compress.drop_console)
+                .unwrap_or(minify_options.drop_console),
+            drop_debugger: compress
+                .and_then(|compress| compress.drop_debugger)
+                .unwrap_or(minify_options.drop_debugger),
+            pure_funcs: compress
+                .and_then(|compress| compress.pure_funcs.clone())
+                .unwrap_or(minify_options.pure_funcs),
+            keep_classnames: self
+                .keep_classnames
+                .unwrap_or(minify_options.keep_classnames),
+            keep_fnames: self.keep_fnames.unwrap_or(minify_options.keep_fnames),
+            ecma: self.ecma.or(minify_options.ecma),
+            ..minify_options
+        }
+    }
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionTurbopackMinifyConfig(Option<TurbopackMinifyConfig>);
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+    OperationValue,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TurbopackBudgetsConfig {
+    /// The maximum size of a single chunk in bytes.
+    pub max_chunk_size: Option<u64>,
+    /// The maximum gzip compressed size of a single chunk in bytes.
+    pub max_chunk_gzip_size: Option<u64>,
+    /// The maximum size of all chunks of an entry in bytes.
+    pub max_entry_size: Option<u64>,
+    /// The maximum gzip compressed size of all chunks of an entry in bytes.
+    pub max_entry_gzip_size: Option<u64>,
+    /// Defaults to `error`, which fails the build.
+    pub severity: Option<TurbopackIssueSeverity>,
+}
+
+/// The severity of the issues reported by opt-in checks like `budgets`.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+    OperationValue,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum TurbopackIssueSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+    OperationValue,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TurbopackCircularDependenciesConfig {
+    /// Globs matched against module paths. Cycles containing a matching module are not reported.
+    pub allow: Option<Vec<RcStr>>,
+    /// Cycles with more modules than this are not reported.
+    pub max_cycle_length: Option<usize>,
+    /// Defaults to `warning`.
+    pub severity: Option<TurbopackIssueSeverity>,
+}
+
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue, OperationValue,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct TurbopackIssueRule {
+    pub title: Option<RcStr>,
+    pub stage: Option<RcStr>,
+    pub path: Option<RcStr>,
+    pub package: Option<RcStr>,
+    pub action: TurbopackIssueRuleAction,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+    OperationValue,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum TurbopackIssueRuleAction {
+    Suppress,
+    Error,
+    Warning,
+    Hint,
+    Info,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
@@ -810,6 +1098,7 @@ pub struct ExperimentalConfig {
 
     turbopack_minify: Option<bool>,
     turbopack_persistent_caching: Option<bool>,
+    turbopack_fsync: Option<bool>,
     turbopack_source_maps: Option<bool>,
     turbopack_tree_shaking: Option<bool>,
     turbopack_scope_hoisting: Option<bool>,
@@ -1696,6 +1985,108 @@ impl NextConfig {
         })
     }
 
+    #[turbo_tasks::function]
+    pub fn turbopack_chunking(&self) -> Vc<OptionTurbopackChunkingConfig> {
+        Vc::cell(self.turbopack.as_ref().and_then(|t| t.chunking.clone()))
+    }
+
+    #[turbo_tasks::function]
+    pub fn turbopack_minify_options(&self) -> Vc<OptionTurbopackMinifyConfig> {
+        Vc::cell(
+            self.turbopack
+                .as_ref()
+                .and_then(|t| t.minify_options.clone()),
+        )
+    }
+
+    #[turbo_tasks::function]
+    pub fn turbopack_budgets(&self) -> Vc<OptionChunkBudgets> {
+        let Some(budgets) = self.turbopack.as_ref().and_then(|t| t.budgets.as_ref()) else {
+            return Vc::cell(None);
+        };
+        Vc::cell(Some(ChunkBudgets {
+            max_chunk_size: budgets.max_chunk_size,
+            max_chunk_gzip_size: budgets.max_chunk_gzip_size,
+            max_entry_size: budgets.max_entry_size,
+            max_entry_gzip_size: budgets.max_entry_gzip_size,
+            severity: match budgets.severity {
+                Some(TurbopackIssueSeverity::Warning) => IssueSeverity::Warning,
+                Some(TurbopackIssueSeverity::Error) | None => IssueSeverity::Error,
+            },
+        }))
+    }
+
+    #[turbo_tasks::function]
+    pub fn turbopack_issue_rules(&self) -> Vc<IssueRules> {
+        let Some(issue_rules) = self.turbopack.as_ref().and_then(|t| t.issue_rules.as_ref()) else {
+            return Vc::cell(vec![]);
+        };
+        Vc::cell(
+            issue_rules
+                .iter()
+                .map(|rule| IssueRule {
+                    title: rule.title.clone(),
+                    stage: rule.stage.clone(),
+                    path: rule.path.clone(),
+                    package: rule.package.clone(),
+                    action: match rule.action {
+                        TurbopackIssueRuleAction::Suppress => IssueRuleAction::Suppress,
+                        TurbopackIssueRuleAction::Error => {
+                            IssueRuleAction::Severity(IssueSeverity::Error)
+                        }
+                        TurbopackIssueRuleAction::Warning => {
+                            IssueRuleAction::Severity(IssueSeverity::Warning)
+                        }
+                        TurbopackIssueRuleAction::Hint => {
+                            IssueRuleAction::Severity(IssueSeverity::Hint)
+                        }
+                        TurbopackIssueRuleAction::Info => {
+                            IssueRuleAction::Severity(IssueSeverity::Info)
+                        }
+                    },
+                })
+                .collect(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    pub fn turbopack_unused_exports_report(&self) -> Vc<bool> {
+        Vc::cell(
+            self.turbopack
+                .as_ref()
+                .and_then(|t| t.unused_exports_report)
+                .unwrap_or_default(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    pub fn turbopack_circular_dependencies(&self) -> Vc<OptionCircularDependencyOptions> {
+        let Some(circular_dependencies) = self
+            .turbopack
+            .as_ref()
+            .and_then(|t| t.circular_dependencies.as_ref())
+        else {
+            return Vc::cell(None);
+        };
+        Vc::cell(Some(CircularDependencyOptions {
+            allow: circular_dependencies.allow.clone().unwrap_or_default(),
+            max_cycle_length: circular_dependencies.max_cycle_length,
+            severity: match circular_dependencies.severity {
+                Some(TurbopackIssueSeverity::Error) => IssueSeverity::Error,
+                Some(TurbopackIssueSeverity::Warning) | None => IssueSeverity::Warning,
+            },
+        }))
+    }
+
+    #[turbo_tasks::function]
+    pub fn module_ids_records_path(&self) -> Vc<Option<RcStr>> {
+        Vc::cell(
+            self.turbopack
+                .as_ref()
+                .and_then(|t| t.module_ids_records_path.clone()),
+        )
+    }
+
     #[turbo_tasks::function]
     pub async fn turbo_minify(&self, mode: Vc<NextMode>) -> Result<Vc<bool>> {
         let minify = self.experimental.turbopack_minify;
@@ -1713,6 +2104,12 @@ impl NextConfig {
         }))
     }
 
+    /// Whether the build output is `fsync`ed to disk before it is considered written.
+    #[turbo_tasks::function]
+    pub fn turbo_fsync(&self) -> Vc<bool> {
+        Vc::cell(self.experimental.turbopack_fsync.unwrap_or(false))
+    }
+
     #[turbo_tasks::function]
     pub async fn client_source_maps(&self, mode: Vc<NextMode>) -> Result<Vc<bool>> {
         let source_maps = self.experimental.turbopack_source_maps;
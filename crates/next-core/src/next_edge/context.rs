@@ -7,13 +7,14 @@ use turbopack::{css::chunk::CssChunkType, resolve_options_context::ResolveOption
 use turbopack_browser::BrowserChunkingContext;
 use turbopack_core::{
     chunk::{
-        ChunkingConfig, ChunkingContext, MangleType, MinifyType, SourceMapsType,
+        ChunkingConfig, ChunkingContext, MangleType, MinifyOptions, MinifyType, SourceMapsType,
         module_id_strategies::ModuleIdStrategy,
     },
     compile_time_info::{CompileTimeDefines, CompileTimeInfo, FreeVarReference, FreeVarReferences},
     environment::{EdgeWorkerEnvironment, Environment, ExecutionEnvironment, NodeJsVersion},
     free_var_references,
     module_graph::export_usage::OptionExportUsageInfo,
+    resolve::pnp::find_pnp_manifest,
 };
 use turbopack_ecmascript::chunk::EcmascriptChunkType;
 use turbopack_node::execution_context::ExecutionContext;
@@ -21,7 +22,7 @@ use turbopack_node::execution_context::ExecutionContext;
 use crate::{
     app_structure::CollectedRootParams,
     mode::NextMode,
-    next_config::NextConfig,
+    next_config::{NextConfig, OptionTurbopackMinifyConfig},
     next_font::local::NextFontLocalResolvePlugin,
     next_import_map::{get_next_edge_and_server_fallback_import_map, get_next_edge_import_map},
     next_server::context::ServerContextType,
@@ -154,8 +155,10 @@ pub async fn get_edge_resolve_options_context(
         custom_conditions.push(rcstr!("react-server"));
     };
 
+    let root = project_path.root().owned().await?;
     let resolve_options_context = ResolveOptionsContext {
-        enable_node_modules: Some(project_path.root().owned().await?),
+        enable_pnp: find_pnp_manifest(root.clone()).owned().await?,
+        enable_node_modules: Some(root),
         enable_edge_node_externals: true,
         custom_conditions,
         import_map: Some(next_edge_import_map),
@@ -199,6 +202,7 @@ pub struct EdgeChunkingContextOptions {
     pub module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
     pub export_usage: Vc<OptionExportUsageInfo>,
     pub turbo_minify: Vc<bool>,
+    pub turbo_minify_config: Vc<OptionTurbopackMinifyConfig>,
     pub turbo_source_maps: Vc<bool>,
     pub no_mangling: Vc<bool>,
     pub scope_hoisting: Vc<bool>,
@@ -219,6 +223,7 @@ pub async fn get_edge_chunking_context_with_client_assets(
         module_id_strategy,
         export_usage,
         turbo_minify,
+        turbo_minify_config,
         turbo_source_maps,
         no_mangling,
         scope_hoisting,
@@ -237,10 +242,15 @@ pub async fn get_edge_chunking_context_with_client_assets(
     )
     .asset_base_path(asset_prefix.owned().await?)
     .minify_type(if *turbo_minify.await? {
-        MinifyType::Minify {
+        let mut minify_options = MinifyOptions {
             // React needs deterministic function names to work correctly.
             mangle: (!*no_mangling.await?).then_some(MangleType::Deterministic),
+            ..Default::default()
+        };
+        if let Some(minify_config) = &*turbo_minify_config.await? {
+            minify_options = minify_config.apply_to(minify_options);
         }
+        MinifyType::Minify(minify_options)
     } else {
         MinifyType::NoMinify
     })
@@ -287,6 +297,7 @@ pub async fn get_edge_chunking_context(
         module_id_strategy,
         export_usage,
         turbo_minify,
+        turbo_minify_config,
         turbo_source_maps,
         no_mangling,
         scope_hoisting,
@@ -309,9 +320,14 @@ pub async fn get_edge_chunking_context(
     // asset from the output directory.
     .asset_base_path(Some(rcstr!("blob:server/edge/")))
     .minify_type(if *turbo_minify.await? {
-        MinifyType::Minify {
+        let mut minify_options = MinifyOptions {
             mangle: (!*no_mangling.await?).then_some(MangleType::OptimalSize),
+            ..Default::default()
+        };
+        if let Some(minify_config) = &*turbo_minify_config.await? {
+            minify_options = minify_config.apply_to(minify_options);
         }
+        MinifyType::Minify(minify_options)
     } else {
         MinifyType::NoMinify
     })
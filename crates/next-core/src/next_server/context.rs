@@ -16,7 +16,7 @@ use turbopack::{
 };
 use turbopack_core::{
     chunk::{
-        ChunkingConfig, MangleType, MinifyType, SourceMapsType,
+        ChunkingConfig, MangleType, MinifyOptions, MinifyType, SourceMapsType,
         module_id_strategies::ModuleIdStrategy,
     },
     compile_time_defines,
@@ -26,6 +26,7 @@ use turbopack_core::{
     },
     free_var_references,
     module_graph::export_usage::OptionExportUsageInfo,
+    resolve::pnp::find_pnp_manifest,
     target::CompileTarget,
 };
 use turbopack_ecmascript::{chunk::EcmascriptChunkType, references::esm::UrlRewriteBehavior};
@@ -47,7 +48,7 @@ use crate::{
     mode::NextMode,
     next_build::get_postcss_package_mapping,
     next_client::RuntimeEntries,
-    next_config::NextConfig,
+    next_config::{NextConfig, OptionTurbopackMinifyConfig},
     next_font::local::NextFontLocalResolvePlugin,
     next_import_map::{get_next_edge_and_server_fallback_import_map, get_next_server_import_map},
     next_server::resolve::ExternalPredicate,
@@ -317,6 +318,7 @@ pub async fn get_server_resolve_options_context(
     }
 
     let resolve_options_context = ResolveOptionsContext {
+        enable_pnp: find_pnp_manifest(root_dir.clone()).owned().await?,
         enable_node_modules: Some(root_dir.clone()),
         enable_node_externals: true,
         enable_node_native_modules: true,
@@ -1000,6 +1002,7 @@ pub struct ServerChunkingContextOptions {
     pub module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
     pub export_usage: Vc<OptionExportUsageInfo>,
     pub turbo_minify: Vc<bool>,
+    pub turbo_minify_config: Vc<OptionTurbopackMinifyConfig>,
     pub turbo_source_maps: Vc<bool>,
     pub no_mangling: Vc<bool>,
     pub scope_hoisting: Vc<bool>,
@@ -1020,6 +1023,7 @@ pub async fn get_server_chunking_context_with_client_assets(
         module_id_strategy,
         export_usage,
         turbo_minify,
+        turbo_minify_config,
         turbo_source_maps,
         no_mangling,
         scope_hoisting,
@@ -1041,10 +1045,15 @@ pub async fn get_server_chunking_context_with_client_assets(
     )
     .asset_prefix(asset_prefix)
     .minify_type(if *turbo_minify.await? {
-        MinifyType::Minify {
+        let mut minify_options = MinifyOptions {
             // React needs deterministic function names to work correctly.
             mangle: (!*no_mangling.await?).then_some(MangleType::Deterministic),
+            ..Default::default()
+        };
+        if let Some(minify_config) = &*turbo_minify_config.await? {
+            minify_options = minify_config.apply_to(minify_options);
         }
+        MinifyType::Minify(minify_options)
     } else {
         MinifyType::NoMinify
     })
@@ -1096,6 +1105,7 @@ pub async fn get_server_chunking_context(
         module_id_strategy,
         export_usage,
         turbo_minify,
+        turbo_minify_config,
         turbo_source_maps,
         no_mangling,
         scope_hoisting,
@@ -1115,9 +1125,14 @@ pub async fn get_server_chunking_context(
         next_mode.runtime_type(),
     )
     .minify_type(if *turbo_minify.await? {
-        MinifyType::Minify {
+        let mut minify_options = MinifyOptions {
             mangle: (!*no_mangling.await?).then_some(MangleType::OptimalSize),
+            ..Default::default()
+        };
+        if let Some(minify_config) = &*turbo_minify_config.await? {
+            minify_options = minify_config.apply_to(minify_options);
         }
+        MinifyType::Minify(minify_options)
     } else {
         MinifyType::NoMinify
     })
@@ -147,6 +147,12 @@ program
     '--experimental-debug-memory-usage',
     'Enables memory profiling features to debug memory consumption.'
   )
+  .addOption(
+    new Option(
+      '--experimental-turbopack-issue-report <format>',
+      'Writes Turbopack build issues to the dist directory in a machine-readable format.'
+    ).choices(['sarif', 'json-lines'])
+  )
   .option(
     '--experimental-upload-trace, <traceUrl>',
     'Reports a subset of the debugging trace to a remote HTTP URL. Includes sensitive data.'
@@ -6,6 +6,7 @@ import type { Span } from '../trace'
 import type getBaseWebpackConfig from './webpack-config'
 import type { TelemetryPluginState } from './webpack/plugins/telemetry-plugin/telemetry-plugin'
 import type { Telemetry } from '../telemetry/storage'
+import type { TurbopackIssueReportFormat } from './swc/types'
 
 // A layer for storing data that is used by plugins to communicate with each
 // other between different steps of the build process. This is only internal
@@ -95,4 +96,5 @@ export const NextBuildContext: Partial<{
   allowedRevalidateHeaderKeys?: string[]
   isCompileMode?: boolean
   debugPrerender: boolean
+  turbopackIssueReport?: TurbopackIssueReportFormat
 }> = {}
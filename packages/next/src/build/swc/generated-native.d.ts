@@ -288,6 +288,32 @@ export declare function projectWriteAllEntrypointsToDisk(
   project: { __napiType: 'Project' },
   appDirOnly: boolean
 ): Promise<TurbopackResult>
+/**
+ * Writes the issues of the last `project_write_all_entrypoints_to_disk` call to `path` in a
+ * machine-readable `format` (`sarif` or `json-lines`).
+ */
+export declare function projectWriteIssueReport(
+  project: { __napiType: 'Project' },
+  appDirOnly: boolean,
+  format: 'sarif' | 'json-lines',
+  path: string
+): Promise<void>
+/**
+ * Returns how many issues of the last `project_write_all_entrypoints_to_disk` call each of the
+ * `turbopack.issueRules` matched, in the order of the rules.
+ */
+export declare function projectIssueRuleMatches(
+  project: { __napiType: 'Project' },
+  appDirOnly: boolean
+): Promise<Array<number>>
+/**
+ * Writes the module graph of the whole app to `path`, in the Graphviz DOT format when the path
+ * ends in `.dot` and as JSON otherwise.
+ */
+export declare function projectWriteModuleGraphExport(
+  project: { __napiType: 'Project' },
+  path: string
+): Promise<void>
 export declare function projectEntrypointsSubscribe(
   project: { __napiType: 'Project' },
   func: (...args: any[]) => any
@@ -35,6 +35,7 @@ import type {
   RawEntrypoints,
   Route,
   TurboEngineOptions,
+  TurbopackIssueReportFormat,
   TurbopackResult,
   TurbopackStackFrame,
   Update,
@@ -670,6 +671,27 @@ function bindingToApi(
       return napiEntrypointsToRawEntrypoints(napiEndpoints)
     }
 
+    async writeIssueReport(
+      appDirOnly: boolean,
+      format: TurbopackIssueReportFormat,
+      path: string
+    ): Promise<void> {
+      await binding.projectWriteIssueReport(
+        this._nativeProject,
+        appDirOnly,
+        format,
+        path
+      )
+    }
+
+    async issueRuleMatches(appDirOnly: boolean): Promise<number[]> {
+      return binding.projectIssueRuleMatches(this._nativeProject, appDirOnly)
+    }
+
+    async writeModuleGraphExport(path: string): Promise<void> {
+      await binding.projectWriteModuleGraphExport(this._nativeProject, path)
+    }
+
     entrypointsSubscribe() {
       const subscription = subscribe<TurbopackResult<NapiEntrypoints>>(
         false,
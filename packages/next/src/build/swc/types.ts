@@ -132,6 +132,11 @@ export type TurbopackResult<T = {}> = T & {
   diagnostics: Diagnostics[]
 }
 
+/**
+ * A machine-readable format issues can be reported in.
+ */
+export type TurbopackIssueReportFormat = 'sarif' | 'json-lines'
+
 export interface Middleware {
   endpoint: Endpoint
 }
@@ -224,6 +229,28 @@ export interface Project {
     appDirOnly: boolean
   ): Promise<TurbopackResult<RawEntrypoints>>
 
+  /**
+   * Writes the issues of the last `writeAllEntrypointsToDisk` call to `path` in a
+   * machine-readable format.
+   */
+  writeIssueReport(
+    appDirOnly: boolean,
+    format: TurbopackIssueReportFormat,
+    path: string
+  ): Promise<void>
+
+  /**
+   * Returns how many issues of the last `writeAllEntrypointsToDisk` call each
+   * of the `turbopack.issueRules` matched, in the order of the rules.
+   */
+  issueRuleMatches(appDirOnly: boolean): Promise<number[]>
+
+  /**
+   * Writes the module graph of the whole app to `path`, in the Graphviz DOT
+   * format when the path ends in `.dot` and as JSON otherwise.
+   */
+  writeModuleGraphExport(path: string): Promise<void>
+
   entrypointsSubscribe(): AsyncIterableIterator<TurbopackResult<RawEntrypoints>>
 
   hmrEvents(identifier: string): AsyncIterableIterator<TurbopackResult<Update>>
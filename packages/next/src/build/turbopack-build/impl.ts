@@ -44,6 +44,7 @@ export async function turbopackBuild(): Promise<{
   const rewrites = NextBuildContext.rewrites!
   const appDirOnly = NextBuildContext.appDirOnly!
   const noMangling = NextBuildContext.noMangling!
+  const issueReport = NextBuildContext.turbopackIssueReport
   const currentNodeJsVersion = process.versions.node
 
   const startTime = process.hrtime()
@@ -124,6 +125,44 @@ export async function turbopackBuild(): Promise<{
       encryptionKey,
     })
 
+    // Write the report before failing on errors, so that they are included
+    if (issueReport) {
+      await project.writeIssueReport(
+        appDirOnly,
+        issueReport,
+        path.join(
+          distDir,
+          issueReport === 'sarif'
+            ? 'turbopack-issues.sarif'
+            : 'turbopack-issues.jsonl'
+        )
+      )
+    }
+
+    const moduleGraphExport = config.experimental.turbopackModuleGraphExport
+    if (moduleGraphExport) {
+      await project.writeModuleGraphExport(
+        path.join(distDir, `turbopack-module-graph.${moduleGraphExport}`)
+      )
+    }
+
+    const issueRules = config.turbopack?.issueRules
+    if (issueRules?.length) {
+      const matches = await project.issueRuleMatches(appDirOnly)
+      issueRules.forEach((rule, index) => {
+        const description = JSON.stringify(rule)
+        if (matches[index] > 0) {
+          console.log(
+            `Turbopack issue rule ${description} matched ${matches[index]} issue(s)`
+          )
+        } else {
+          console.warn(
+            `Turbopack issue rule ${description} didn't match any issue and can be removed`
+          )
+        }
+      })
+    }
+
     const topLevelErrors = []
     const topLevelWarnings = []
     for (const issue of entrypoints.issues) {
@@ -10,6 +10,7 @@ import isError from '../lib/is-error'
 import { getProjectDir } from '../lib/get-project-dir'
 import { enableMemoryDebuggingMode } from '../lib/memory/startup'
 import { disableMemoryDebuggingMode } from '../lib/memory/shutdown'
+import type { TurbopackIssueReportFormat } from '../build/swc/types'
 
 export type NextBuildOptions = {
   debug?: boolean
@@ -24,6 +25,13 @@ export type NextBuildOptions = {
   experimentalTurbo?: boolean
   experimentalBuildMode: 'default' | 'compile' | 'generate' | 'generate-env'
   experimentalUploadTrace?: string
+  experimentalTurbopackIssueReport?: TurbopackIssueReportFormat
+}
+
+function isTurbopackBuild(options: NextBuildOptions) {
+  return Boolean(
+    options.turbo || options.turbopack || process.env.IS_TURBOPACK_TEST
+  )
 }
 
 const nextBuild = (options: NextBuildOptions, directory?: string) => {
@@ -40,6 +48,7 @@ const nextBuild = (options: NextBuildOptions, directory?: string) => {
     experimentalAppOnly,
     experimentalBuildMode,
     experimentalUploadTrace,
+    experimentalTurbopackIssueReport,
   } = options
 
   let traceUploadUrl: string | undefined
@@ -71,6 +80,12 @@ const nextBuild = (options: NextBuildOptions, directory?: string) => {
     )
   }
 
+  if (experimentalTurbopackIssueReport && !isTurbopackBuild(options)) {
+    warn(
+      '--experimental-turbopack-issue-report is ignored without --turbopack.'
+    )
+  }
+
   if (experimentalDebugMemoryUsage) {
     process.env.EXPERIMENTAL_DEBUG_MEMORY_USAGE = '1'
     enableMemoryDebuggingMode()
@@ -82,9 +97,7 @@ const nextBuild = (options: NextBuildOptions, directory?: string) => {
     printAndExit(`> No such directory exists as the project root: ${dir}`)
   }
 
-  const isTurbopack = Boolean(
-    options.turbo || options.turbopack || process.env.IS_TURBOPACK_TEST
-  )
+  const isTurbopack = isTurbopackBuild(options)
   if (isTurbopack) {
     process.env.TURBOPACK = '1'
   }
@@ -99,7 +112,8 @@ const nextBuild = (options: NextBuildOptions, directory?: string) => {
     experimentalAppOnly,
     isTurbopack,
     experimentalBuildMode,
-    traceUploadUrl
+    traceUploadUrl,
+    experimentalTurbopackIssueReport
   )
     .catch((err) => {
       if (experimentalDebugMemoryUsage) {
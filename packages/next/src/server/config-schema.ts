@@ -160,6 +160,79 @@ const zTurbopackConfig: zod.ZodType<TurbopackOptions> = z.strictObject({
     .optional(),
   resolveExtensions: z.array(z.string()).optional(),
   moduleIds: z.enum(['named', 'deterministic']).optional(),
+  moduleIdsRecordsPath: z.string().optional(),
+  chunking: z
+    .strictObject({
+      minChunkSize: z.number().int().nonnegative().optional(),
+      maxChunkSize: z.number().int().nonnegative().optional(),
+      maxChunkCountPerGroup: z.number().int().nonnegative().optional(),
+      maxMergeChunkSize: z.number().int().nonnegative().optional(),
+      cacheGroups: z
+        .array(z.strictObject({ name: z.string(), test: z.string() }))
+        .optional(),
+    })
+    .optional(),
+  budgets: z
+    .strictObject({
+      maxChunkSize: z.number().int().nonnegative().optional(),
+      maxChunkGzipSize: z.number().int().nonnegative().optional(),
+      maxEntrySize: z.number().int().nonnegative().optional(),
+      maxEntryGzipSize: z.number().int().nonnegative().optional(),
+      severity: z.enum(['error', 'warning']).optional(),
+    })
+    .optional(),
+  issueRules: z
+    .array(
+      z.strictObject({
+        title: z.string().optional(),
+        stage: z.string().optional(),
+        path: z.string().optional(),
+        package: z.string().optional(),
+        action: z.enum(['suppress', 'error', 'warning', 'hint', 'info']),
+      })
+    )
+    .optional(),
+  circularDependencies: z
+    .strictObject({
+      allow: z.array(z.string()).optional(),
+      maxCycleLength: z.number().int().positive().optional(),
+      severity: z.enum(['error', 'warning']).optional(),
+    })
+    .optional(),
+  unusedExportsReport: z.boolean().optional(),
+  minifyOptions: z
+    .strictObject({
+      compress: z
+        .strictObject({
+          passes: z.number().int().positive().optional(),
+          drop_console: z.boolean().optional(),
+          drop_debugger: z.boolean().optional(),
+          pure_funcs: z.array(z.string()).optional(),
+        })
+        .optional(),
+      mangle: z
+        .strictObject({
+          reserved: z.array(z.string()).optional(),
+          toplevel: z.boolean().optional(),
+        })
+        .optional(),
+      keep_classnames: z.boolean().optional(),
+      keep_fnames: z.boolean().optional(),
+      ecma: z
+        .union([
+          z.literal(5),
+          z.literal(2015),
+          z.literal(2016),
+          z.literal(2017),
+          z.literal(2018),
+          z.literal(2019),
+          z.literal(2020),
+          z.literal(2021),
+          z.literal(2022),
+        ])
+        .optional(),
+    })
+    .optional(),
   root: z.string().optional(),
 })
 
@@ -474,6 +547,8 @@ export const configSchema: zod.ZodType<NextConfig> = z.lazy(() =>
         turbopackMemoryLimit: z.number().optional(),
         turbopackMinify: z.boolean().optional(),
         turbopackPersistentCaching: z.boolean().optional(),
+        turbopackFsync: z.boolean().optional(),
+        turbopackModuleGraphExport: z.enum(['json', 'dot']).optional(),
         turbopackSourceMaps: z.boolean().optional(),
         turbopackTreeShaking: z.boolean().optional(),
         turbopackRemoveUnusedExports: z.boolean().optional(),
@@ -289,6 +289,143 @@ export type TurbopackRuleConfigItem =
   | { [condition in TurbopackLoaderBuiltinCondition]?: TurbopackRuleConfigItem }
   | false
 
+export type TurbopackChunkingOptions = {
+  /**
+   * Try to avoid creating chunks smaller than this size (in bytes).
+   */
+  minChunkSize?: number
+  /**
+   * Split chunks bigger than this size (in bytes) into multiple chunks.
+   */
+  maxChunkSize?: number
+  /**
+   * Try to avoid loading more than this number of chunks per page.
+   */
+  maxChunkCountPerGroup?: number
+  /**
+   * Never merge chunks bigger than this size (in bytes) with other chunks.
+   */
+  maxMergeChunkSize?: number
+  /**
+   * Modules whose identifier matches `test` (a regular expression source) are
+   * put into dedicated chunks per cache group, e.g.
+   * `{ name: 'vendor-react', test: '/node_modules/react(-dom)?/' }`. Modules
+   * loaded by the same set of pages share a chunk, which has the same content
+   * on every one of these pages.
+   */
+  cacheGroups?: { name: string; test: string }[]
+}
+
+export type TurbopackBudgetsOptions = {
+  /**
+   * The maximum size (in bytes) of a single client chunk.
+   */
+  maxChunkSize?: number
+  /**
+   * The maximum gzip compressed size (in bytes) of a single client chunk.
+   */
+  maxChunkGzipSize?: number
+  /**
+   * The maximum size (in bytes) of all client chunks loaded by an entry.
+   */
+  maxEntrySize?: number
+  /**
+   * The maximum gzip compressed size (in bytes) of all client chunks loaded by
+   * an entry.
+   */
+  maxEntryGzipSize?: number
+  /**
+   * Whether an exceeded budget fails the build (`'error'`, the default) or
+   * only reports a warning.
+   */
+  severity?: 'error' | 'warning'
+}
+
+export type TurbopackCircularDependenciesOptions = {
+  /**
+   * Globs matched against module paths, e.g. `'node_modules/**'`. Cycles
+   * containing a matching module are not reported.
+   */
+  allow?: string[]
+  /**
+   * Cycles with more modules than this are not reported.
+   */
+  maxCycleLength?: number
+  /**
+   * Whether a cycle fails the build (`'error'`) or only reports a warning, the
+   * default.
+   */
+  severity?: 'error' | 'warning'
+}
+
+export type TurbopackMinifyOptions = {
+  compress?: {
+    /**
+     * The number of compress passes. Defaults to `2`.
+     */
+    passes?: number
+    /**
+     * Removes calls to `console.*`.
+     */
+    drop_console?: boolean
+    /**
+     * Removes `debugger` statements.
+     */
+    drop_debugger?: boolean
+    /**
+     * Functions whose calls can be removed when their result is unused, e.g.
+     * `'console.info'`.
+     */
+    pure_funcs?: string[]
+  }
+  mangle?: {
+    /**
+     * Names that are never mangled.
+     */
+    reserved?: string[]
+    /**
+     * Mangles names in the top level scope too.
+     */
+    toplevel?: boolean
+  }
+  /**
+   * Keeps class names, even when names are mangled.
+   */
+  keep_classnames?: boolean
+  /**
+   * Keeps function names, even when names are mangled.
+   */
+  keep_fnames?: boolean
+  /**
+   * The ECMAScript version the minifier may use in its output, e.g. `2020`.
+   */
+  ecma?: 5 | 2015 | 2016 | 2017 | 2018 | 2019 | 2020 | 2021 | 2022
+}
+
+export type TurbopackIssueRule = {
+  /**
+   * A substring of the issue title.
+   */
+  title?: string
+  /**
+   * The stage the issue was reported in, e.g. `'resolve'` or `'parse'`.
+   */
+  stage?: string
+  /**
+   * A glob matched against the path of the file the issue is reported for,
+   * relative to the project root, e.g. `'node_modules/**/*.js'`.
+   */
+  path?: string
+  /**
+   * The name of the package in `node_modules` the issue is reported for.
+   */
+  package?: string
+  /**
+   * Suppress the issue or report it with a different severity.
+   */
+  action: 'suppress' | 'error' | 'warning' | 'hint' | 'info'
+}
+
 export interface TurbopackOptions {
   /**
    * (`next --turbopack` only) A mapping of aliased imports to modules to load in their place.
@@ -328,6 +465,56 @@ export interface TurbopackOptions {
    */
   moduleIds?: 'named' | 'deterministic'
 
+  /**
+   * A path, relative to the project directory, of a JSON file that records the
+   * `'deterministic'` module IDs between builds. Recorded modules keep their ID
+   * and only new modules get a new one, so adding a module doesn't change the
+   * IDs of unrelated modules. The file is updated after every build.
+   */
+  moduleIdsRecordsPath?: string
+
+  /**
+   * (`next build --turbopack` only) Overrides for how client JavaScript is
+   * split into chunks.
+   */
+  chunking?: TurbopackChunkingOptions
+
+  /**
+   * (`next build --turbopack` only) Size budgets for client JavaScript. An
+   * issue listing the offending chunks and their largest modules is reported
+   * when a budget is exceeded.
+   */
+  budgets?: TurbopackBudgetsOptions
+
+  /**
+   * (`next --turbopack` only) Rules that suppress issues or override their
+   * severity. An issue is handled by the first rule matching all of the
+   * conditions set on it.
+   */
+  issueRules?: TurbopackIssueRule[]
+
+  /**
+   * (`next --turbopack` only) Reports import cycles, including the modules of
+   * each cycle and whether it crosses the boundary between server and client
+   * code or contains an async import.
+   */
+  circularDependencies?: TurbopackCircularDependenciesOptions
+
+  /**
+   * (`next build --turbopack` only) Reports exports of the app's own modules
+   * that are never imported by the server or client code, as warnings and in
+   * `unused-exports.json` in the dist directory. Requires
+   * `experimental.turbopackTreeShaking` to know which exports are imported.
+   */
+  unusedExportsReport?: boolean
+
+  /**
+   * (`next --turbopack` only) Options of the JavaScript minifier, named like
+   * the terser options, e.g. `{ keep_classnames: true }` to keep class names
+   * for error reporting.
+   */
+  minifyOptions?: TurbopackMinifyOptions
+
   /**
    * This is the repo root usually and only files above this
    * directory can be resolved by turbopack.
@@ -586,6 +773,19 @@ export interface ExperimentalConfig {
    */
   turbopackPersistentCaching?: boolean
 
+  /**
+   * `fsync` every file written by `next build` before it is considered written, so that the
+   * build output survives a crash of the machine. Defaults to false.
+   */
+  turbopackFsync?: boolean
+
+  /**
+   * Write the module graph of the whole app to `.next/turbopack-module-graph.json`
+   * or, in the Graphviz DOT format, to `.next/turbopack-module-graph.dot` after
+   * a Turbopack build, e.g. for dependency dashboards.
+   */
+  turbopackModuleGraphExport?: 'json' | 'dot'
+
   /**
    * Enable source maps. Defaults to true.
    */
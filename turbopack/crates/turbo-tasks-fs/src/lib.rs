@@ -25,6 +25,7 @@ pub mod source_context;
 pub mod util;
 pub(crate) mod virtual_fs;
 mod watcher;
+mod zip_fs;
 
 use std::{
     borrow::Cow,
@@ -35,7 +36,10 @@ use std::{
     io::{self, BufRead, BufReader, ErrorKind, Read},
     mem::take,
     path::{MAIN_SEPARATOR, Path, PathBuf},
-    sync::{Arc, LazyLock},
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicU64, Ordering as AtomicOrdering},
+    },
     time::Duration,
 };
 
@@ -76,7 +80,7 @@ use crate::{
     util::extract_disk_access,
     watcher::DiskWatcher,
 };
-pub use crate::{read_glob::ReadGlobResult, virtual_fs::VirtualFileSystem};
+pub use crate::{read_glob::ReadGlobResult, virtual_fs::VirtualFileSystem, zip_fs::ZipFileSystem};
 
 /// A (somewhat arbitrary) filename limit that we should try to keep output file names below.
 ///
@@ -195,6 +199,33 @@ fn create_semaphore() -> tokio::sync::Semaphore {
     tokio::sync::Semaphore::new(256)
 }
 
+/// Controls how a [`DiskFileSystem`] persists file writes.
+///
+/// The defaults match the historical behavior: files are truncated and written in place, and the
+/// data is left to the OS to flush.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    TaskInput,
+    TraceRawVcs,
+    NonLocalValue,
+)]
+pub struct DiskWriteOptions {
+    /// Write into a temporary file next to the destination and rename it into place afterwards.
+    /// Readers (and interrupted processes) never observe a partially written file.
+    pub atomic: bool,
+    /// Call `fsync` on every written file before it is considered written. Combined with `atomic`
+    /// the parent directory is synced too, so the rename survives a crash.
+    pub fsync: bool,
+}
+
 #[turbo_tasks::value_trait]
 pub trait FileSystem: ValueToString {
     /// Returns the path to the root of the file system.
@@ -247,6 +278,8 @@ struct DiskFileSystemInner {
 
     #[turbo_tasks(debug_ignore, trace_ignore)]
     watcher: DiskWatcher,
+
+    write_options: DiskWriteOptions,
 }
 
 impl DiskFileSystemInner {
@@ -439,6 +472,10 @@ impl DiskFileSystem {
         &self.inner.root
     }
 
+    pub fn write_options(&self) -> DiskWriteOptions {
+        self.inner.write_options
+    }
+
     pub fn invalidate(&self) {
         self.inner.invalidate();
     }
@@ -486,6 +523,59 @@ struct PathLockGuard<'a>(
     #[allow(dead_code)] mutex_map::MutexMapGuard<'a, PathBuf>,
 );
 
+/// Writes the contents of `file` to `full_path`, honoring the given [`DiskWriteOptions`].
+fn write_file_to_disk(full_path: &Path, file: &File, options: DiskWriteOptions) -> io::Result<()> {
+    if !options.atomic {
+        return write_file_contents(full_path, file, options);
+    }
+
+    let temp_path = atomic_write_temp_path(full_path);
+    if let Err(err) = write_file_contents(&temp_path, file, options)
+        .and_then(|_| std::fs::rename(&temp_path, full_path))
+    {
+        // Don't leave stale temporary files behind, the error is reported either way.
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(err);
+    }
+
+    #[cfg(target_family = "unix")]
+    if options.fsync
+        && let Some(parent) = full_path.parent()
+    {
+        // Persist the directory entry that was changed by the rename.
+        std::fs::File::open(parent)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+fn write_file_contents(path: &Path, file: &File, options: DiskWriteOptions) -> io::Result<()> {
+    use std::io::Write;
+
+    let mut f = std::fs::File::create(path)?;
+    std::io::copy(&mut file.read(), &mut f)?;
+    #[cfg(target_family = "unix")]
+    f.set_permissions(file.meta.permissions.into())?;
+    f.flush()?;
+    if options.fsync {
+        f.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Returns a unique sibling path of `full_path` to stage an atomic write in. The temporary file
+/// lives in the same directory so that the final rename never crosses a file system boundary.
+fn atomic_write_temp_path(full_path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let file_name = full_path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    let id = COUNTER.fetch_add(1, AtomicOrdering::Relaxed);
+    full_path.with_file_name(format!(".{file_name}.{}.{id}.tmp", std::process::id()))
+}
+
 fn format_absolute_fs_path(path: &Path, name: &str, root_path: &Path) -> Option<String> {
     if let Ok(rel_path) = path.strip_prefix(root_path) {
         let path = if MAIN_SEPARATOR != '/' {
@@ -509,7 +599,20 @@ impl DiskFileSystem {
     /// * `root` - Path to the given filesystem's root. Should be
     ///   [canonicalized][std::fs::canonicalize].
     #[turbo_tasks::function]
-    pub fn new(name: RcStr, root: RcStr) -> Result<Vc<Self>> {
+    pub fn new(name: RcStr, root: RcStr) -> Vc<Self> {
+        Self::new_with_write_options(name, root, DiskWriteOptions::default())
+    }
+
+    /// Create a new instance of `DiskFileSystem` that persists writes according to
+    /// `write_options`.
+    ///
+    /// See [`DiskFileSystem::new`] for the remaining arguments.
+    #[turbo_tasks::function]
+    pub fn new_with_write_options(
+        name: RcStr,
+        root: RcStr,
+        write_options: DiskWriteOptions,
+    ) -> Result<Vc<Self>> {
         mark_stateful();
 
         let instance = DiskFileSystem {
@@ -522,6 +625,7 @@ impl DiskFileSystem {
                 dir_invalidator_map: InvalidatorMap::new(),
                 semaphore: create_semaphore(),
                 watcher: DiskWatcher::new(),
+                write_options,
             }),
         };
 
@@ -769,17 +873,12 @@ impl FileSystem for DiskFileSystem {
 
                     let full_path_to_write = full_path.clone();
                     let content = content.clone();
+                    let write_options = inner.write_options;
                     retry_blocking(full_path_to_write.into_owned(), move |full_path| {
-                        use std::io::Write;
-
-                        let mut f = std::fs::File::create(full_path)?;
                         let FileContent::Content(file) = &*content else {
                             unreachable!()
                         };
-                        std::io::copy(&mut file.read(), &mut f)?;
-                        #[cfg(target_family = "unix")]
-                        f.set_permissions(file.meta.permissions.into())?;
-                        f.flush()?;
+                        write_file_to_disk(full_path, file, write_options)?;
 
                         static WRITE_VERSION: LazyLock<bool> = LazyLock::new(|| {
                             std::env::var_os("TURBO_ENGINE_WRITE_VERSION")
@@ -795,11 +894,7 @@ impl FileSystem for DiskFileSystem {
                                 format!("{hash:016x}")
                             };
                             full_path.set_extension(ext);
-                            let mut f = std::fs::File::create(&full_path)?;
-                            std::io::copy(&mut file.read(), &mut f)?;
-                            #[cfg(target_family = "unix")]
-                            f.set_permissions(file.meta.permissions.into())?;
-                            f.flush()?;
+                            write_file_to_disk(&full_path, file, write_options)?;
                         }
                         Ok::<(), io::Error>(())
                     })
@@ -2433,6 +2528,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_atomic_write_temp_path() {
+        let full_path = Path::new("/out/chunks/main.js");
+        let first = atomic_write_temp_path(full_path);
+        let second = atomic_write_temp_path(full_path);
+
+        assert_eq!(first.parent(), full_path.parent());
+        assert_ne!(first, second);
+        let name = first.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with(".main.js."));
+        assert!(name.ends_with(".tmp"));
+    }
+
+    #[test]
+    fn test_write_file_to_disk_atomic() {
+        let scratch = tempfile::tempdir().unwrap();
+        let full_path = scratch.path().join("file.txt");
+        std::fs::write(&full_path, "old content").unwrap();
+
+        let file = File::from("new content");
+        write_file_to_disk(
+            &full_path,
+            &file,
+            DiskWriteOptions {
+                atomic: true,
+                fsync: true,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&full_path).unwrap(), "new content");
+        // The temporary file has been renamed into place.
+        assert_eq!(std::fs::read_dir(scratch.path()).unwrap().count(), 1);
+    }
+
     #[tokio::test]
     async fn with_extension() {
         crate::register();
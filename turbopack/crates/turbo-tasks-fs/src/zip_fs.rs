@@ -0,0 +1,340 @@
+use std::io::Read;
+
+use anyhow::{Context, Result, bail};
+use auto_hash_map::AutoMap;
+use flate2::read::DeflateDecoder;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{NonLocalValue, ValueToString, Vc, trace::TraceRawVcs};
+
+use crate::{
+    File, FileContent, FileMeta, FileSystem, FileSystemPath, LinkContent, RawDirectoryContent,
+    RawDirectoryEntry, rope::Rope,
+};
+
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+const CENTRAL_DIRECTORY_HEADER_SIGNATURE: u32 = 0x02014b50;
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x04034b50;
+
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+const CENTRAL_DIRECTORY_HEADER_SIZE: usize = 46;
+const LOCAL_FILE_HEADER_SIZE: usize = 30;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+
+/// A read-only [FileSystem] for the contents of a zip archive, e.g. a package in the Yarn cache.
+///
+/// The archive is read through its own [FileSystemPath], so changes to the archive invalidate all
+/// reads from this file system. Only stored and deflated entries are supported, zip64 archives are
+/// not.
+#[turbo_tasks::value]
+pub struct ZipFileSystem {
+    name: RcStr,
+    archive: FileSystemPath,
+}
+
+#[turbo_tasks::value_impl]
+impl ZipFileSystem {
+    /// Creates a [ZipFileSystem] for the archive at `archive`. File systems with the same name and
+    /// archive are identical.
+    #[turbo_tasks::function]
+    pub fn new(name: RcStr, archive: FileSystemPath) -> Vc<Self> {
+        ZipFileSystem { name, archive }.cell()
+    }
+
+    /// The path of the zip archive.
+    #[turbo_tasks::function]
+    pub fn archive(&self) -> Vc<FileSystemPath> {
+        self.archive.clone().cell()
+    }
+
+    /// The name this file system was created with.
+    #[turbo_tasks::function]
+    pub fn name(&self) -> Vc<RcStr> {
+        Vc::cell(self.name.clone())
+    }
+
+    /// Reads the archive once and parses its central directory, so that reads of entries can
+    /// slice the compressed data out of the same bytes.
+    #[turbo_tasks::function]
+    async fn archive_content(&self) -> Result<Vc<ZipArchive>> {
+        let FileContent::Content(file) = &*self.archive.read().await? else {
+            bail!(
+                "zip archive {} doesn't exist",
+                self.archive.value_to_string().await?
+            );
+        };
+        let content = Rope::from(file.content().clone().into_bytes());
+        let index = ZipIndex::parse(&content.to_bytes())
+            .with_context(|| format!("failed to read zip archive {}", self.archive.path))?;
+        Ok(ZipArchive { content, index }.cell())
+    }
+}
+
+/// The contents of a zip archive together with the index of its entries.
+#[turbo_tasks::value(serialization = "none", eq = "manual", cell = "new")]
+struct ZipArchive {
+    /// The bytes of the archive, in a single contiguous buffer.
+    content: Rope,
+    index: ZipIndex,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+struct ZipEntry {
+    /// The offset of the local file header of the entry.
+    offset: usize,
+    compressed_size: usize,
+    method: u16,
+}
+
+#[derive(Debug, Default, TraceRawVcs, NonLocalValue)]
+struct ZipIndex {
+    files: FxHashMap<RcStr, ZipEntry>,
+    directories: FxHashMap<RcStr, AutoMap<RcStr, RawDirectoryEntry>>,
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16> {
+    let bytes = bytes
+        .get(offset..offset + 2)
+        .context("unexpected end of zip archive")?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    let bytes = bytes
+        .get(offset..offset + 4)
+        .context("unexpected end of zip archive")?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+impl ZipIndex {
+    fn parse(bytes: &[u8]) -> Result<Self> {
+        // The end of central directory record is followed by a comment of up to 64 KiB
+        let search_start = bytes
+            .len()
+            .saturating_sub(END_OF_CENTRAL_DIRECTORY_SIZE + u16::MAX as usize);
+        let end_of_central_directory = (search_start
+            ..=bytes.len().saturating_sub(END_OF_CENTRAL_DIRECTORY_SIZE))
+            .rev()
+            .find(|&offset| {
+                read_u32(bytes, offset).ok() == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE)
+            })
+            .context("end of central directory record not found")?;
+
+        let entry_count = read_u16(bytes, end_of_central_directory + 10)?;
+        let central_directory_offset = read_u32(bytes, end_of_central_directory + 16)?;
+        if entry_count == u16::MAX || central_directory_offset == u32::MAX {
+            bail!("zip64 archives are not supported");
+        }
+
+        let mut index = ZipIndex::default();
+        index.directories.insert(RcStr::default(), AutoMap::new());
+        let mut offset = central_directory_offset as usize;
+        for _ in 0..entry_count {
+            if read_u32(bytes, offset)? != CENTRAL_DIRECTORY_HEADER_SIGNATURE {
+                bail!("invalid central directory header at offset {offset}");
+            }
+            let method = read_u16(bytes, offset + 10)?;
+            let compressed_size = read_u32(bytes, offset + 20)? as usize;
+            let name_length = read_u16(bytes, offset + 28)? as usize;
+            let extra_length = read_u16(bytes, offset + 30)? as usize;
+            let comment_length = read_u16(bytes, offset + 32)? as usize;
+            let local_header_offset = read_u32(bytes, offset + 42)? as usize;
+            let name_start = offset + CENTRAL_DIRECTORY_HEADER_SIZE;
+            let name = bytes
+                .get(name_start..name_start + name_length)
+                .context("unexpected end of zip archive")?;
+            let name = std::str::from_utf8(name).context("invalid entry name")?;
+            offset = name_start + name_length + extra_length + comment_length;
+
+            if let Some(directory) = name.strip_suffix('/') {
+                index.add_entry(directory, RawDirectoryEntry::Directory);
+            } else {
+                index.add_entry(name, RawDirectoryEntry::File);
+                index.files.insert(
+                    name.into(),
+                    ZipEntry {
+                        offset: local_header_offset,
+                        compressed_size,
+                        method,
+                    },
+                );
+            }
+        }
+        Ok(index)
+    }
+
+    /// Adds an entry and all of its parent directories to the directory listings.
+    fn add_entry(&mut self, path: &str, entry: RawDirectoryEntry) {
+        if path.is_empty() {
+            return;
+        }
+        let (parent, name) = path.rsplit_once('/').unwrap_or(("", path));
+        let is_directory = entry == RawDirectoryEntry::Directory;
+        let is_parent_known = self.directories.contains_key(parent);
+        self.directories
+            .entry(parent.into())
+            .or_default()
+            .insert(name.into(), entry);
+        if is_directory {
+            self.directories.entry(path.into()).or_default();
+        }
+        if !is_parent_known {
+            self.add_entry(parent, RawDirectoryEntry::Directory);
+        }
+    }
+}
+
+impl ZipEntry {
+    fn read(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        if read_u32(bytes, self.offset)? != LOCAL_FILE_HEADER_SIGNATURE {
+            bail!("invalid local file header at offset {}", self.offset);
+        }
+        let name_length = read_u16(bytes, self.offset + 26)? as usize;
+        let extra_length = read_u16(bytes, self.offset + 28)? as usize;
+        let data_start = self.offset + LOCAL_FILE_HEADER_SIZE + name_length + extra_length;
+        let data = bytes
+            .get(data_start..data_start + self.compressed_size)
+            .context("unexpected end of zip archive")?;
+        match self.method {
+            METHOD_STORED => Ok(data.to_vec()),
+            METHOD_DEFLATED => {
+                let mut content = Vec::new();
+                DeflateDecoder::new(data).read_to_end(&mut content)?;
+                Ok(content)
+            }
+            method => bail!("unsupported zip compression method {method}"),
+        }
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl FileSystem for ZipFileSystem {
+    #[turbo_tasks::function]
+    async fn read(self: Vc<Self>, fs_path: FileSystemPath) -> Result<Vc<FileContent>> {
+        let archive = self.archive_content().await?;
+        let Some(entry) = archive.index.files.get(&fs_path.path) else {
+            return Ok(FileContent::NotFound.cell());
+        };
+        let content = entry.read(&archive.content.to_bytes())?;
+        Ok(FileContent::Content(File::new(FileMeta::default(), content)).cell())
+    }
+
+    #[turbo_tasks::function]
+    fn read_link(&self, _fs_path: FileSystemPath) -> Vc<LinkContent> {
+        LinkContent::NotFound.cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn raw_read_dir(
+        self: Vc<Self>,
+        fs_path: FileSystemPath,
+    ) -> Result<Vc<RawDirectoryContent>> {
+        let archive = self.archive_content().await?;
+        Ok(match archive.index.directories.get(&fs_path.path) {
+            Some(entries) => RawDirectoryContent::new(entries.clone()),
+            None => RawDirectoryContent::not_found(),
+        })
+    }
+
+    #[turbo_tasks::function]
+    fn write(&self, _fs_path: FileSystemPath, _content: Vc<FileContent>) -> Result<Vc<()>> {
+        bail!("Writing is not possible on a zip file system")
+    }
+
+    #[turbo_tasks::function]
+    fn write_link(&self, _fs_path: FileSystemPath, _target: Vc<LinkContent>) -> Result<Vc<()>> {
+        bail!("Writing is not possible on a zip file system")
+    }
+
+    #[turbo_tasks::function]
+    async fn metadata(self: Vc<Self>, fs_path: FileSystemPath) -> Result<Vc<FileMeta>> {
+        let archive = self.archive_content().await?;
+        if !archive.index.files.contains_key(&fs_path.path)
+            && !archive.index.directories.contains_key(&fs_path.path)
+        {
+            bail!("{} doesn't exist in the zip archive", fs_path.path);
+        }
+        Ok(FileMeta::default().cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for ZipFileSystem {
+    #[turbo_tasks::function]
+    fn to_string(&self) -> Vc<RcStr> {
+        Vc::cell(self.name.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a zip archive with stored entries.
+    fn build_archive(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        let mut central_directory = Vec::new();
+        for (name, content) in entries {
+            let offset = archive.len() as u32;
+            archive.extend_from_slice(&LOCAL_FILE_HEADER_SIGNATURE.to_le_bytes());
+            archive.extend_from_slice(&[0; 4]);
+            archive.extend_from_slice(&METHOD_STORED.to_le_bytes());
+            archive.extend_from_slice(&[0; 8]);
+            archive.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            archive.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            archive.extend_from_slice(&0u16.to_le_bytes());
+            archive.extend_from_slice(name.as_bytes());
+            archive.extend_from_slice(content);
+
+            central_directory.extend_from_slice(&CENTRAL_DIRECTORY_HEADER_SIGNATURE.to_le_bytes());
+            central_directory.extend_from_slice(&[0; 6]);
+            central_directory.extend_from_slice(&METHOD_STORED.to_le_bytes());
+            central_directory.extend_from_slice(&[0; 8]);
+            central_directory.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            central_directory.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            central_directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            central_directory.extend_from_slice(&[0; 12]);
+            central_directory.extend_from_slice(&offset.to_le_bytes());
+            central_directory.extend_from_slice(name.as_bytes());
+        }
+        let central_directory_offset = archive.len() as u32;
+        archive.extend_from_slice(&central_directory);
+        archive.extend_from_slice(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+        archive.extend_from_slice(&[0; 6]);
+        archive.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&central_directory_offset.to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes());
+        archive
+    }
+
+    #[test]
+    fn test_parse_zip_index() {
+        let archive = build_archive(&[
+            ("node_modules/foo/", b""),
+            ("node_modules/foo/package.json", b"{}"),
+            ("node_modules/foo/lib/index.js", b"module.exports = 1"),
+        ]);
+        let index = ZipIndex::parse(&archive).unwrap();
+
+        assert_eq!(
+            index.directories[""].get("node_modules"),
+            Some(&RawDirectoryEntry::Directory)
+        );
+        assert_eq!(
+            index.directories["node_modules/foo"].get("lib"),
+            Some(&RawDirectoryEntry::Directory)
+        );
+        assert_eq!(
+            index.directories["node_modules/foo"].get("package.json"),
+            Some(&RawDirectoryEntry::File)
+        );
+
+        let entry = &index.files["node_modules/foo/lib/index.js"];
+        assert_eq!(entry.read(&archive).unwrap(), b"module.exports = 1");
+    }
+}
@@ -15,6 +15,7 @@ use turbopack_core::{
         ChunkingConfigs, ChunkingContext, EntryChunkGroupResult, EvaluatableAsset,
         EvaluatableAssets, MinifyType, ModuleId, SourceMapsType,
         availability_info::AvailabilityInfo,
+        budget::{ChunkBudgets, OptionChunkBudgets},
         chunk_group::{MakeChunkGroupResult, make_chunk_group},
         module_id_strategies::{DevModuleIdStrategy, ModuleIdStrategy},
     },
@@ -115,6 +116,11 @@ impl BrowserChunkingContextBuilder {
         self
     }
 
+    pub fn debug_ids(mut self, enable_debug_ids: bool) -> Self {
+        self.chunking_context.enable_debug_ids = enable_debug_ids;
+        self
+    }
+
     pub fn asset_base_path(mut self, asset_base_path: Option<RcStr>) -> Self {
         self.chunking_context.asset_base_path = asset_base_path;
         self
@@ -178,6 +184,11 @@ impl BrowserChunkingContextBuilder {
         self
     }
 
+    pub fn chunk_budgets(mut self, chunk_budgets: ChunkBudgets) -> Self {
+        self.chunking_context.chunk_budgets = Some(chunk_budgets);
+        self
+    }
+
     pub fn use_content_hashing(mut self, content_hashing: ContentHashing) -> Self {
         self.chunking_context.content_hashing = Some(content_hashing);
         self
@@ -229,6 +240,8 @@ pub struct BrowserChunkingContext {
     enable_module_merging: bool,
     /// Enable dynamic chunk content loading.
     enable_dynamic_chunk_content_loading: bool,
+    /// Enable debug IDs for chunks and their source maps
+    enable_debug_ids: bool,
     /// The environment chunks will be evaluated in.
     environment: ResolvedVc<Environment>,
     /// The kind of runtime to include in the output.
@@ -249,6 +262,8 @@ pub struct BrowserChunkingContext {
     export_usage: Option<ResolvedVc<ExportUsageInfo>>,
     /// The chunking configs
     chunking_configs: Vec<(ResolvedVc<Box<dyn ChunkType>>, ChunkingConfig)>,
+    /// The size budgets chunk groups are checked against
+    chunk_budgets: Option<ChunkBudgets>,
 }
 
 impl BrowserChunkingContext {
@@ -279,6 +294,7 @@ impl BrowserChunkingContext {
                 enable_tracing: false,
                 enable_module_merging: false,
                 enable_dynamic_chunk_content_loading: false,
+                enable_debug_ids: false,
                 environment,
                 runtime_type,
                 minify_type: MinifyType::NoMinify,
@@ -289,6 +305,7 @@ impl BrowserChunkingContext {
                 module_id_strategy: ResolvedVc::upcast(DevModuleIdStrategy::new_resolved()),
                 export_usage: None,
                 chunking_configs: Default::default(),
+                chunk_budgets: None,
             },
         }
     }
@@ -386,7 +403,7 @@ impl BrowserChunkingContext {
     /// Returns the minify type.
     #[turbo_tasks::function]
     pub fn minify_type(&self) -> Vc<MinifyType> {
-        self.minify_type.cell()
+        self.minify_type.clone().cell()
     }
 }
 
@@ -492,18 +509,12 @@ impl ChunkingContext for BrowserChunkingContext {
 
     #[turbo_tasks::function]
     fn reference_chunk_source_maps(&self, _chunk: Vc<Box<dyn OutputAsset>>) -> Vc<bool> {
-        Vc::cell(match self.source_maps_type {
-            SourceMapsType::Full => true,
-            SourceMapsType::None => false,
-        })
+        Vc::cell(self.source_maps_type.is_enabled())
     }
 
     #[turbo_tasks::function]
     fn reference_module_source_maps(&self, _module: Vc<Box<dyn Module>>) -> Vc<bool> {
-        Vc::cell(match self.source_maps_type {
-            SourceMapsType::Full => true,
-            SourceMapsType::None => false,
-        })
+        Vc::cell(self.source_maps_type.is_enabled())
     }
 
     #[turbo_tasks::function]
@@ -538,6 +549,11 @@ impl ChunkingContext for BrowserChunkingContext {
         Ok(Vc::cell(self.chunking_configs.iter().cloned().collect()))
     }
 
+    #[turbo_tasks::function]
+    fn chunk_budgets(&self) -> Vc<OptionChunkBudgets> {
+        Vc::cell(self.chunk_budgets)
+    }
+
     #[turbo_tasks::function]
     fn should_use_file_source_map_uris(&self) -> Vc<bool> {
         Vc::cell(self.should_use_file_source_map_uris)
@@ -558,9 +574,19 @@ impl ChunkingContext for BrowserChunkingContext {
         Vc::cell(self.enable_dynamic_chunk_content_loading)
     }
 
+    #[turbo_tasks::function]
+    fn is_debug_ids_enabled(&self) -> Vc<bool> {
+        Vc::cell(self.enable_debug_ids)
+    }
+
+    #[turbo_tasks::function]
+    fn source_maps_type(&self) -> Vc<SourceMapsType> {
+        self.source_maps_type.cell()
+    }
+
     #[turbo_tasks::function]
     pub fn minify_type(&self) -> Vc<MinifyType> {
-        self.minify_type.cell()
+        self.minify_type.clone().cell()
     }
 
     #[turbo_tasks::function]
@@ -4,7 +4,7 @@ use turbo_tasks::{FxIndexSet, ResolvedVc, ValueToString, Vc};
 use turbo_tasks_fs::FileSystemPath;
 use turbopack_core::{
     asset::{Asset, AssetContent},
-    chunk::{Chunk, ChunkingContext, OutputChunk, OutputChunkRuntimeInfo},
+    chunk::{Chunk, ChunkItems, ChunkingContext, OutputChunk, OutputChunkRuntimeInfo},
     ident::AssetIdent,
     introspect::{Introspectable, IntrospectableChildren},
     output::{OutputAsset, OutputAssets},
@@ -74,6 +74,11 @@ impl OutputChunk for EcmascriptBrowserChunk {
         }
         .cell())
     }
+
+    #[turbo_tasks::function]
+    fn contained_chunk_items(&self) -> Vc<ChunkItems> {
+        self.chunk.chunk_items()
+    }
 }
 
 #[turbo_tasks::value_impl]
@@ -101,9 +106,10 @@ impl OutputAsset for EcmascriptBrowserChunk {
     async fn path(self: Vc<Self>) -> Result<Vc<FileSystemPath>> {
         let this = self.await?;
         let ident = this.ident_for_path();
+        let name = this.chunk.name().owned().await?;
         Ok(this
             .chunking_context
-            .chunk_path(Some(Vc::upcast(self)), ident, None, rcstr!(".js")))
+            .chunk_path(Some(Vc::upcast(self)), ident, name, rcstr!(".js")))
     }
 
     #[turbo_tasks::function]
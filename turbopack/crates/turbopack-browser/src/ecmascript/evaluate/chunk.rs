@@ -6,14 +6,14 @@ use indoc::writedoc;
 use serde::Serialize;
 use turbo_rcstr::{RcStr, rcstr};
 use turbo_tasks::{ReadRef, ResolvedVc, TryJoinIterExt, ValueToString, Vc};
-use turbo_tasks_fs::{File, FileSystemPath, rope::RopeBuilder};
+use turbo_tasks_fs::FileSystemPath;
 use turbopack_core::{
     asset::{Asset, AssetContent},
     chunk::{
         ChunkData, ChunkingContext, ChunksData, EvaluatableAssets, MinifyType,
         ModuleChunkItemIdExt, ModuleId,
     },
-    code_builder::{Code, CodeBuilder},
+    code_builder::{Code, CodeBuilder, FooterCommentStyle},
     ident::AssetIdent,
     module::Module,
     module_graph::ModuleGraph,
@@ -190,8 +190,8 @@ impl EcmascriptBrowserEvaluateChunk {
 
         let mut code = code.build();
 
-        if let MinifyType::Minify { mangle } = *this.chunking_context.minify_type().await? {
-            code = minify(code, source_maps, mangle)?;
+        if let MinifyType::Minify(options) = &*this.chunking_context.minify_type().await? {
+            code = minify(code, source_maps, options)?;
         }
 
         Ok(code.cell())
@@ -283,31 +283,23 @@ impl OutputAsset for EcmascriptBrowserEvaluateChunk {
 impl Asset for EcmascriptBrowserEvaluateChunk {
     #[turbo_tasks::function]
     async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
-        let code = self.code().await?;
-
-        let rope = if code.has_source_map() {
-            let mut rope_builder = RopeBuilder::default();
-            rope_builder.concat(code.source_code());
-            let source_map_path = self.source_map().path().await?;
-            write!(
-                rope_builder,
-                "\n\n//# sourceMappingURL={}",
-                urlencoding::encode(source_map_path.file_name())
-            )?;
-            rope_builder.build()
-        } else {
-            code.source_code().clone()
-        };
-
-        Ok(AssetContent::file(File::from(rope).into()))
+        let this = self.await?;
+        Ok(self.code().chunk_file_content(
+            Vc::upcast(*this.chunking_context),
+            Vc::upcast(self.source_map()),
+            FooterCommentStyle::JavaScript,
+        ))
     }
 }
 
 #[turbo_tasks::value_impl]
 impl GenerateSourceMap for EcmascriptBrowserEvaluateChunk {
     #[turbo_tasks::function]
-    fn generate_source_map(self: Vc<Self>) -> Vc<OptionStringifiedSourceMap> {
-        self.code().generate_source_map()
+    async fn generate_source_map(self: Vc<Self>) -> Result<Vc<OptionStringifiedSourceMap>> {
+        let this = self.await?;
+        Ok(self
+            .code()
+            .generate_chunk_source_map(Vc::upcast(*this.chunking_context)))
     }
 }
 
@@ -13,11 +13,11 @@ use crossterm::style::{StyledContent, Stylize};
 use owo_colors::{OwoColorize as _, Style};
 use rustc_hash::{FxHashMap, FxHashSet};
 use turbo_rcstr::RcStr;
-use turbo_tasks::{RawVc, ReadRef, TransientInstance, TransientValue, Vc};
+use turbo_tasks::{RawVc, ReadRef, ResolvedVc, TransientInstance, TransientValue, Vc};
 use turbo_tasks_fs::{FileLinesContent, source_context::get_source_context};
 use turbopack_core::issue::{
     CapturedIssues, IssueReporter, IssueSeverity, PlainIssue, PlainIssueProcessingPathItem,
-    PlainIssueSource, PlainTraceItem, StyledString,
+    PlainIssueSource, PlainTraceItem, StyledString, rules::IssueRules,
 };
 
 use crate::source_context::format_source_context_lines;
@@ -600,6 +600,40 @@ impl IssueReporter for ConsoleUi {
     }
 }
 
+/// Suppresses issues or overrides their severity according to [IssueRules] before forwarding them
+/// to the `inner` reporter.
+#[turbo_tasks::value(shared)]
+pub struct IssueRulesReporter {
+    rules: ResolvedVc<IssueRules>,
+    inner: ResolvedVc<Box<dyn IssueReporter>>,
+}
+
+#[turbo_tasks::value_impl]
+impl IssueRulesReporter {
+    #[turbo_tasks::function]
+    pub fn new(
+        rules: ResolvedVc<IssueRules>,
+        inner: ResolvedVc<Box<dyn IssueReporter>>,
+    ) -> Vc<Self> {
+        IssueRulesReporter { rules, inner }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl IssueReporter for IssueRulesReporter {
+    #[turbo_tasks::function]
+    fn report_issues(
+        &self,
+        issues: TransientInstance<CapturedIssues>,
+        source: TransientValue<RawVc>,
+        min_failing_severity: IssueSeverity,
+    ) -> Vc<bool> {
+        let issues = (*issues).clone().with_rules(self.rules);
+        self.inner
+            .report_issues(TransientInstance::new(issues), source, min_failing_severity)
+    }
+}
+
 fn make_relative_to_cwd<'a>(path: &'a str, project_dir: &Path, cwd: &Path) -> Cow<'a, str> {
     if let Some(path_in_project) = path.strip_prefix("[project]/") {
         let abs_path = if std::path::MAIN_SEPARATOR != '/' {
@@ -0,0 +1,467 @@
+use std::{
+    borrow::Cow,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{Result, anyhow};
+use clap::ValueEnum;
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use turbo_tasks::{
+    NonLocalValue, RawVc, ReadRef, ResolvedVc, TransientInstance, TransientValue, Vc,
+    trace::TraceRawVcs,
+};
+use turbopack_core::issue::{
+    CapturedIssues, IssueReporter, IssueSeverity, PlainIssue, PlainIssueSource, StyledString,
+};
+
+/// A machine-readable format issues can be reported in.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    ValueEnum,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum IssueReportFormat {
+    /// A [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log, as
+    /// consumed by code scanning tools.
+    Sarif,
+    /// One JSON object per issue and line.
+    JsonLines,
+}
+
+impl FromStr for IssueReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <IssueReportFormat as ValueEnum>::from_str(s, true).map_err(|s| anyhow!("{}", s))
+    }
+}
+
+/// Returns the path of an issue file relative to the project directory, or the path unchanged if
+/// it isn't inside of the project.
+fn project_relative_path(path: &str) -> Cow<'_, str> {
+    match path.strip_prefix("[project]/") {
+        Some(path) => path.into(),
+        None => path.into(),
+    }
+}
+
+/// The 1-based line and column range of an issue source.
+fn source_range(source: &PlainIssueSource) -> Option<Value> {
+    source.range.map(|(start, end)| {
+        json!({
+            "startLine": start.line + 1,
+            "startColumn": start.column + 1,
+            "endLine": end.line + 1,
+            "endColumn": end.column + 1,
+        })
+    })
+}
+
+fn import_traces(issue: &PlainIssue) -> Value {
+    Value::Array(
+        issue
+            .import_traces
+            .iter()
+            .map(|trace| {
+                Value::Array(
+                    trace
+                        .iter()
+                        .map(|item| {
+                            json!({
+                                "fsName": item.fs_name,
+                                "path": item.path,
+                                "layer": item.layer,
+                            })
+                        })
+                        .collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn processing_path(issue: &PlainIssue) -> Value {
+    Value::Array(
+        issue
+            .processing_path
+            .iter()
+            .flatten()
+            .map(|item| {
+                json!({
+                    "filePath": item.file_path.as_deref(),
+                    "description": &*item.description,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Serializes an issue into the JSON object used by [IssueReportFormat::JsonLines]. Lines and
+/// columns are 1-based.
+pub fn issue_to_json(issue: &PlainIssue) -> Value {
+    json!({
+        "severity": issue.severity.as_str(),
+        "stage": issue.stage.to_string(),
+        "title": issue.title.to_unstyled_string(),
+        "description": issue.description.as_ref().map(StyledString::to_unstyled_string),
+        "detail": issue.detail.as_ref().map(StyledString::to_unstyled_string),
+        "documentationLink": (!issue.documentation_link.is_empty())
+            .then_some(&issue.documentation_link),
+        "filePath": (!issue.file_path.is_empty()).then(|| project_relative_path(&issue.file_path)),
+        "source": issue.source.as_ref().map(|source| json!({
+            "ident": &*source.asset.ident,
+            "range": source_range(source),
+        })),
+        "importTraces": import_traces(issue),
+        "processingPath": processing_path(issue),
+    })
+}
+
+fn sarif_level(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Bug | IssueSeverity::Fatal | IssueSeverity::Error => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Hint | IssueSeverity::Note | IssueSeverity::Suggestion => "note",
+        IssueSeverity::Info => "none",
+    }
+}
+
+/// Builds a SARIF 2.1.0 log with one run containing all given issues. Issues are grouped into
+/// rules by their stage.
+pub fn issues_to_sarif(issues: &[ReadRef<PlainIssue>]) -> Value {
+    let mut rules = Vec::new();
+    let mut rule_ids = FxHashSet::default();
+    let results = issues
+        .iter()
+        .map(|issue| {
+            let stage = issue.stage.to_string();
+            let rule_id = format!("turbopack/{}", stage.replace(' ', "-"));
+            if rule_ids.insert(rule_id.clone()) {
+                rules.push(json!({
+                    "id": rule_id,
+                    "name": stage,
+                    "shortDescription": { "text": format!("Turbopack {stage} issue") },
+                }));
+            }
+
+            let mut message = issue.title.to_unstyled_string();
+            if let Some(description) = &issue.description {
+                message.push_str("\n\n");
+                message.push_str(&description.to_unstyled_string());
+            }
+
+            let mut result = json!({
+                "ruleId": rule_id,
+                "level": sarif_level(issue.severity),
+                "message": { "text": message },
+                "properties": {
+                    "severity": issue.severity.as_str(),
+                    "documentationLink": (!issue.documentation_link.is_empty())
+                        .then_some(&issue.documentation_link),
+                    "importTraces": import_traces(issue),
+                    "processingPath": processing_path(issue),
+                },
+            });
+            // Issues that aren't tied to a file (e.g. config issues) have no location.
+            if !issue.file_path.is_empty() {
+                let mut physical_location = json!({
+                    "artifactLocation": {
+                        "uri": project_relative_path(&issue.file_path),
+                        "uriBaseId": "%SRCROOT%",
+                    },
+                });
+                if let Some(region) = issue.source.as_ref().and_then(source_range) {
+                    physical_location["region"] = region;
+                }
+                result["locations"] = json!([{ "physicalLocation": physical_location }]);
+            }
+            result
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "turbopack",
+                    "informationUri": "https://turbo.build/pack",
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+/// Formats the given issues in the given format.
+pub fn format_issue_report(
+    format: IssueReportFormat,
+    issues: &[ReadRef<PlainIssue>],
+) -> Result<String> {
+    Ok(match format {
+        IssueReportFormat::Sarif => serde_json::to_string_pretty(&issues_to_sarif(issues))?,
+        IssueReportFormat::JsonLines => {
+            let mut report = String::new();
+            for issue in issues {
+                report.push_str(&serde_json::to_string(&issue_to_json(issue))?);
+                report.push('\n');
+            }
+            report
+        }
+    })
+}
+
+/// Writes the report to the given file, or to stdout when no file is given.
+pub fn write_issue_report(
+    format: IssueReportFormat,
+    issues: &[ReadRef<PlainIssue>],
+    path: Option<&Path>,
+) -> Result<()> {
+    let report = format_issue_report(format, issues)?;
+    match path {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, report)?;
+        }
+        None => {
+            std::io::stdout().lock().write_all(report.as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone)]
+pub struct IssueReportOptions {
+    pub format: IssueReportFormat,
+    /// The file the report is written to. The report is written to stdout when this is `None`.
+    pub path: Option<PathBuf>,
+    /// Issues less severe than this are not reported.
+    pub log_level: IssueSeverity,
+}
+
+/// Writes emitted issues in a machine-readable format. The complete report is rewritten every
+/// time issues are reported.
+///
+/// Issues are additionally forwarded to an `inner` reporter, e.g. the
+/// [ConsoleUi][crate::issue::ConsoleUi], which then decides whether fatal issues were present.
+#[turbo_tasks::value(shared)]
+pub struct StructuredIssueReporter {
+    options: IssueReportOptions,
+    inner: Option<ResolvedVc<Box<dyn IssueReporter>>>,
+}
+
+#[turbo_tasks::value_impl]
+impl StructuredIssueReporter {
+    #[turbo_tasks::function]
+    pub fn new(
+        options: TransientInstance<IssueReportOptions>,
+        inner: Option<ResolvedVc<Box<dyn IssueReporter>>>,
+    ) -> Vc<Self> {
+        StructuredIssueReporter {
+            options: (*options).clone(),
+            inner,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl IssueReporter for StructuredIssueReporter {
+    #[turbo_tasks::function]
+    async fn report_issues(
+        &self,
+        issues: TransientInstance<CapturedIssues>,
+        source: TransientValue<RawVc>,
+        min_failing_severity: IssueSeverity,
+    ) -> Result<Vc<bool>> {
+        let plain_issues = issues.get_plain_issues().await?;
+
+        let mut seen = FxHashSet::default();
+        let mut reported_issues = plain_issues
+            .iter()
+            .filter(|issue| issue.severity <= self.options.log_level)
+            .filter(|issue| seen.insert(issue.internal_hash_ref(false)))
+            .cloned()
+            .collect::<Vec<_>>();
+        reported_issues.sort_by(|a, b| (a.severity, &a.file_path).cmp(&(b.severity, &b.file_path)));
+        write_issue_report(
+            self.options.format,
+            &reported_issues,
+            self.options.path.as_deref(),
+        )?;
+
+        if let Some(inner) = self.inner {
+            return Ok(inner.report_issues(issues, source, min_failing_severity));
+        }
+        Ok(Vc::cell(
+            plain_issues
+                .iter()
+                .any(|issue| issue.severity <= min_failing_severity),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use turbo_rcstr::{RcStr, rcstr};
+    use turbo_tasks_fs::FileContent;
+    use turbopack_core::{
+        issue::{IssueStage, PlainIssueProcessingPath, PlainSource, PlainTraceItem},
+        source_pos::SourcePos,
+    };
+
+    use super::*;
+
+    fn issue(file_path: RcStr, source: Option<PlainIssueSource>) -> ReadRef<PlainIssue> {
+        ReadRef::new_owned(PlainIssue {
+            severity: IssueSeverity::Error,
+            stage: IssueStage::Parse,
+            title: StyledString::Text(rcstr!("Parsing failed")),
+            file_path,
+            description: Some(StyledString::Text(rcstr!("Unexpected token"))),
+            detail: None,
+            documentation_link: rcstr!(""),
+            source,
+            processing_path: ReadRef::new_owned(PlainIssueProcessingPath(None)),
+            import_traces: vec![vec![
+                PlainTraceItem {
+                    fs_name: rcstr!("project"),
+                    root_path: rcstr!("/app"),
+                    path: rcstr!("src/index.js"),
+                    layer: None,
+                },
+                PlainTraceItem {
+                    fs_name: rcstr!("project"),
+                    root_path: rcstr!("/app"),
+                    path: rcstr!("src/broken.js"),
+                    layer: Some(rcstr!("client")),
+                },
+            ]],
+        })
+    }
+
+    fn issue_with_source() -> ReadRef<PlainIssue> {
+        issue(
+            rcstr!("[project]/src/broken.js"),
+            Some(PlainIssueSource {
+                asset: ReadRef::new_owned(PlainSource {
+                    ident: ReadRef::new_owned(rcstr!("[project]/src/broken.js")),
+                    content: ReadRef::new_owned(FileContent::NotFound),
+                }),
+                range: Some((
+                    SourcePos { line: 2, column: 4 },
+                    SourcePos { line: 2, column: 9 },
+                )),
+            }),
+        )
+    }
+
+    fn expected_import_traces() -> Value {
+        json!([[
+            { "fsName": "project", "path": "src/index.js", "layer": null },
+            { "fsName": "project", "path": "src/broken.js", "layer": "client" },
+        ]])
+    }
+
+    #[test]
+    fn test_issue_report_format_from_str() {
+        assert_eq!(
+            "sarif".parse::<IssueReportFormat>().unwrap(),
+            IssueReportFormat::Sarif
+        );
+        assert_eq!(
+            "json-lines".parse::<IssueReportFormat>().unwrap(),
+            IssueReportFormat::JsonLines
+        );
+        assert!("xml".parse::<IssueReportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_json_lines_report() {
+        let issues = [issue_with_source(), issue(rcstr!(""), None)];
+        let report = format_issue_report(IssueReportFormat::JsonLines, &issues).unwrap();
+        let lines = report
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            json!({
+                "severity": "error",
+                "stage": "parse",
+                "title": "Parsing failed",
+                "description": "Unexpected token",
+                "detail": null,
+                "documentationLink": null,
+                "filePath": "src/broken.js",
+                "source": {
+                    "ident": "[project]/src/broken.js",
+                    "range": { "startLine": 3, "startColumn": 5, "endLine": 3, "endColumn": 10 },
+                },
+                "importTraces": expected_import_traces(),
+                "processingPath": [],
+            })
+        );
+        assert_eq!(lines[1]["filePath"], Value::Null);
+        assert_eq!(lines[1]["source"], Value::Null);
+    }
+
+    #[test]
+    fn test_sarif_report() {
+        let issues = [issue_with_source(), issue(rcstr!(""), None)];
+        let report = format_issue_report(IssueReportFormat::Sarif, &issues).unwrap();
+        let report: Value = serde_json::from_str(&report).unwrap();
+        assert_eq!(report["version"], "2.1.0");
+
+        let run = &report["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            json!([{
+                "id": "turbopack/parse",
+                "name": "parse",
+                "shortDescription": { "text": "Turbopack parse issue" },
+            }])
+        );
+        assert_eq!(
+            run["results"][0],
+            json!({
+                "ruleId": "turbopack/parse",
+                "level": "error",
+                "message": { "text": "Parsing failed\n\nUnexpected token" },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "src/broken.js", "uriBaseId": "%SRCROOT%" },
+                        "region": { "startLine": 3, "startColumn": 5, "endLine": 3, "endColumn": 10 },
+                    },
+                }],
+                "properties": {
+                    "severity": "error",
+                    "documentationLink": null,
+                    "importTraces": expected_import_traces(),
+                    "processingPath": [],
+                },
+            })
+        );
+        // Issues without a file have no location
+        assert_eq!(run["results"][1].get("locations"), None);
+    }
+}
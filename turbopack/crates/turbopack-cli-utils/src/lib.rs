@@ -5,6 +5,7 @@
 #![feature(arbitrary_self_types_pointers)]
 
 pub mod issue;
+pub mod issue_report;
 pub mod runtime_entry;
 pub mod source_context;
 
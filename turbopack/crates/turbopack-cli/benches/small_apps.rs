@@ -77,13 +77,31 @@ fn bench_small_apps(c: &mut Criterion) {
                                 log_level: None,
                                 show_all: false,
                                 log_detail: false,
+                                issue_rules: None,
                                 full_stats: false,
                                 target: None,
                             },
                             no_sourcemap: false,
+                            sourcemap: None,
                             no_minify: false,
+                            debug_ids: false,
                             force_memory_cleanup: true,
                             no_scope_hoist: false,
+                            library_formats: vec![],
+                            preserve_modules: false,
+                            esm: false,
+                            min_chunk_size: None,
+                            max_chunk_size: None,
+                            max_chunk_count_per_group: None,
+                            max_merge_chunk_size: None,
+                            cache_groups: vec![],
+                            module_id_records: None,
+                            export_module_graph: None,
+                            duplicate_packages_report: None,
+                            unused_exports_report: None,
+                            asset_manifest: None,
+                            issue_report: None,
+                            issue_report_path: None,
                         })
                         .await
                     })
@@ -3,15 +3,17 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use anyhow::{Context, Result};
 use clap::{Args, Parser, ValueEnum};
 use serde::{Deserialize, Serialize};
 use turbo_tasks::{NonLocalValue, TaskInput, trace::TraceRawVcs};
-use turbopack_cli_utils::issue::IssueSeverityCliOption;
+use turbopack_cli_utils::{issue::IssueSeverityCliOption, issue_report::IssueReportFormat};
+use turbopack_core::{chunk::ChunkCacheGroup, issue::rules::IssueRule};
 
 #[derive(Debug, Parser)]
 #[clap(author, version, about, long_about = None)]
 pub enum Arguments {
-    Build(BuildArguments),
+    Build(Box<BuildArguments>),
     Dev(DevArguments),
 }
 
@@ -44,6 +46,38 @@ pub enum Target {
     Node,
 }
 
+/// The module format of the files of a library build.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    ValueEnum,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Hash,
+    TaskInput,
+    NonLocalValue,
+    TraceRawVcs,
+)]
+pub enum LibraryFormat {
+    /// ES modules, written as `.mjs` files.
+    Esm,
+    /// CommonJS modules, written as `.cjs` files.
+    Cjs,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum SourceMapMode {
+    /// Write sourcemaps and reference them from the output files.
+    Full,
+    /// Write sourcemaps, but don't reference them from the output files.
+    Hidden,
+    /// Write sourcemaps without the content of the original sources.
+    Nosources,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct CommonArguments {
     /// The entrypoints of the project. Resolved relative to the project's
@@ -74,6 +108,11 @@ pub struct CommonArguments {
     #[clap(long)]
     pub log_detail: bool,
 
+    /// A JSON file with a list of rules that suppress issues or override their severity, e.g.
+    /// `[{ "package": "some-package", "stage": "parse", "action": "suppress" }]`.
+    #[clap(long, value_parser)]
+    pub issue_rules: Option<PathBuf>,
+
     /// Whether to enable full task stats recording in Turbo Engine.
     #[clap(long)]
     pub full_stats: bool,
@@ -87,6 +126,19 @@ pub struct CommonArguments {
     pub target: Option<Target>,
 }
 
+impl CommonArguments {
+    /// Reads the rules from the `--issue-rules` file.
+    pub fn issue_rules(&self) -> Result<Vec<IssueRule>> {
+        let Some(path) = &self.issue_rules else {
+            return Ok(Vec::new());
+        };
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read issue rules from {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse issue rules in {}", path.display()))
+    }
+}
+
 #[derive(Debug, Args)]
 #[clap(author, version, about, long_about = None)]
 pub struct DevArguments {
@@ -132,17 +184,115 @@ pub struct BuildArguments {
     #[clap(long)]
     pub no_sourcemap: bool,
 
+    /// How to generate sourcemaps, e.g. `hidden` to not reference them from the output files.
+    #[clap(long, value_enum, conflicts_with = "no_sourcemap")]
+    pub sourcemap: Option<SourceMapMode>,
+
     /// Don't minify build output.
     #[clap(long)]
     pub no_minify: bool,
 
+    /// Add debug IDs to the output chunks and their sourcemaps, so that stack frames can be
+    /// matched to sourcemaps without relying on URLs.
+    #[clap(long)]
+    pub debug_ids: bool,
+
     /// Don't perform scope hoisting.
     #[clap(long)]
     pub no_scope_hoist: bool,
 
+    /// Build a library in this format instead of an application: every entry is emitted as a
+    /// single file that exports the exports of the entry module, and the `dependencies` and
+    /// `peerDependencies` of the project's `package.json` are left external. Can be repeated to
+    /// emit multiple formats. Requires the `node` target. The module graph, the asset manifest
+    /// and the package and export reports are only written for application builds.
+    #[clap(long = "library", value_enum)]
+    pub library_formats: Vec<LibraryFormat>,
+
+    /// Emit a library file for every module of the project instead of only for the entries, so
+    /// that the output has the same structure as the sources. Requires `--library`.
+    #[clap(long, requires = "library_formats")]
+    pub preserve_modules: bool,
+
+    /// Emit the chunks as ES modules (`.mjs`) instead of CommonJS modules. Requires the `node`
+    /// target. Library builds choose the format with `--library` instead.
+    #[clap(long, conflicts_with = "library_formats")]
+    pub esm: bool,
+
+    /// Try to avoid creating JavaScript chunks smaller than this size (in bytes).
+    #[clap(long)]
+    pub min_chunk_size: Option<usize>,
+
+    /// Split JavaScript chunks bigger than this size (in bytes) into multiple chunks.
+    #[clap(long)]
+    pub max_chunk_size: Option<usize>,
+
+    /// Try to avoid loading more than this number of JavaScript chunks per chunk group.
+    #[clap(long)]
+    pub max_chunk_count_per_group: Option<usize>,
+
+    /// Never merge JavaScript chunks bigger than this size (in bytes) with other chunks.
+    #[clap(long)]
+    pub max_merge_chunk_size: Option<usize>,
+
+    /// Put all modules whose ident matches a regex into dedicated chunks, given as
+    /// `<name>=<regex>`, e.g. `vendor-react=/node_modules/react(-dom)?/`. Modules loaded by the
+    /// same entries share a chunk. Modules that scope hoisting merges into another module stay in
+    /// the chunk of that module, see `--no-scope-hoist`. Can be repeated.
+    #[clap(long = "cache-group", value_parser = parse_cache_group)]
+    pub cache_groups: Vec<ChunkCacheGroup>,
+
+    /// Read the module ids assigned by previous builds from this file, relative to the project's
+    /// directory (`--dir`), and keep them stable. The ids of new modules are added to the file.
+    #[clap(long, value_parser, conflicts_with = "library_formats")]
+    pub module_id_records: Option<String>,
+
+    /// Write the module graph to this file, relative to the project's directory (`--dir`). Files
+    /// ending in `.dot` are written in the Graphviz DOT format, all others as JSON.
+    #[clap(long, value_parser, conflicts_with = "library_formats")]
+    pub export_module_graph: Option<String>,
+
+    /// Warn about packages that are included in multiple versions or from multiple locations and
+    /// write a JSON report of them to this file, relative to the project's directory (`--dir`).
+    #[clap(long, value_parser, conflicts_with = "library_formats")]
+    pub duplicate_packages_report: Option<String>,
+
+    /// Warn about exports of modules in the project that are never imported and write a JSON
+    /// report of them to this file, relative to the project's directory (`--dir`). Requires tree
+    /// shaking to know which exports are imported.
+    #[clap(long, value_parser, conflicts_with = "library_formats")]
+    pub unused_exports_report: Option<String>,
+
+    /// Write a manifest of the output files of every entry, with their content hashes, sizes,
+    /// integrity hashes and MIME types, to this file, relative to the project's directory
+    /// (`--dir`).
+    #[clap(long, value_parser, conflicts_with = "library_formats")]
+    pub asset_manifest: Option<String>,
+
+    /// Additionally report issues in a machine-readable format, e.g. for code scanning tools.
+    #[clap(long, value_enum)]
+    pub issue_report: Option<IssueReportFormat>,
+
+    /// Write the issue report (`--issue-report`) to this file instead of stdout.
+    #[clap(long, value_parser, requires = "issue_report")]
+    pub issue_report_path: Option<PathBuf>,
+
     /// Drop the `TurboTasks` object upon exit. By default we intentionally leak this memory, as
     /// we're about to exit the process anyways, but that can cause issues with valgrind or other
     /// leak detectors.
     #[clap(long, hide = true)]
     pub force_memory_cleanup: bool,
 }
+
+fn parse_cache_group(value: &str) -> Result<ChunkCacheGroup, String> {
+    let (name, test) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected `<name>=<regex>`, got `{value}`"))?;
+    if name.is_empty() {
+        return Err(format!("missing cache group name in `{value}`"));
+    }
+    Ok(ChunkCacheGroup {
+        name: name.into(),
+        test: test.into(),
+    })
+}
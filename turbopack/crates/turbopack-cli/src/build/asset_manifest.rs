@@ -0,0 +1,308 @@
+//! A machine-readable manifest of the build output, so that servers and deployment scripts don't
+//! need to guess the output files of the entries from their file names. It looks like this:
+//!
+//! ```json
+//! {
+//!   "entries": {
+//!     "src/index.js": {
+//!       "chunks": ["index.entry.js", "chunks/a1b2c3d4e5f6a7b8.js"],
+//!       "assets": ["index.entry.js", "chunks/a1b2c3d4e5f6a7b8.js", "chunks/..."]
+//!     }
+//!   },
+//!   "files": {
+//!     "chunks/a1b2c3d4e5f6a7b8.js": {
+//!       "contentHash": "0123456789abcdef",
+//!       "size": 1234,
+//!       "integrity": "sha384-...",
+//!       "mimeType": "application/javascript"
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! All paths are relative to the output directory.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use serde::Serialize;
+use sha2::{Digest, Sha384};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{ResolvedVc, TryJoinIterExt};
+use turbo_tasks_fs::{FileContent, FileSystemPath};
+use turbo_tasks_hash::{encode_hex, hash_xxh3_hash64};
+use turbopack_core::{
+    asset::{Asset, AssetContent},
+    output::{OutputAsset, OutputAssets},
+    reference::all_assets_from_entries,
+};
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetManifest {
+    /// The output files of each entry, keyed by the path of the entry module relative to the
+    /// project directory.
+    pub entries: BTreeMap<RcStr, AssetManifestEntry>,
+    /// Information about every output file.
+    pub files: BTreeMap<RcStr, AssetManifestFile>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetManifestEntry {
+    /// The files of the entry's chunk group, which have to be loaded to run the entry.
+    pub chunks: Vec<RcStr>,
+    /// All files referenced by the entry, including async chunks, source maps and static assets.
+    pub assets: Vec<RcStr>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetManifestFile {
+    /// A hex encoded hash of the content, e.g. to be used as an `ETag`.
+    pub content_hash: RcStr,
+    /// The size of the content in bytes.
+    pub size: usize,
+    /// A subresource integrity hash of the content.
+    pub integrity: RcStr,
+    pub mime_type: RcStr,
+}
+
+impl AssetManifest {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Returns the path of `path` relative to `root`, falling back to the full path.
+async fn relative_path(root: &FileSystemPath, path: &FileSystemPath) -> Result<RcStr> {
+    Ok(match root.get_path_to(path) {
+        Some(relative) => relative.into(),
+        None => path.value_to_string().owned().await?,
+    })
+}
+
+async fn output_paths(
+    output_root: &FileSystemPath,
+    assets: &[ResolvedVc<Box<dyn OutputAsset>>],
+) -> Result<Vec<RcStr>> {
+    assets
+        .iter()
+        .map(async |asset| relative_path(output_root, &*asset.path().await?).await)
+        .try_join()
+        .await
+}
+
+/// Returns the manifest entry of an output asset, or `None` if the asset isn't written as a file,
+/// e.g. because its content doesn't exist or it is a link.
+async fn manifest_file(
+    asset: ResolvedVc<Box<dyn OutputAsset>>,
+) -> Result<Option<AssetManifestFile>> {
+    let path = asset.path().await?;
+    let AssetContent::File(content) = *asset.content().await? else {
+        return Ok(None);
+    };
+    let FileContent::Content(file) = &*content.await? else {
+        return Ok(None);
+    };
+    let bytes = file.content().to_bytes();
+    Ok(Some(AssetManifestFile {
+        content_hash: encode_hex(hash_xxh3_hash64(&*bytes)).into(),
+        size: bytes.len(),
+        integrity: integrity(&bytes),
+        mime_type: match file.content_type() {
+            Some(content_type) => content_type.to_string(),
+            None => mime_type(&path.path),
+        }
+        .into(),
+    }))
+}
+
+fn integrity(bytes: &[u8]) -> RcStr {
+    let digest = Sha384::digest(bytes);
+    format!(
+        "sha384-{}",
+        base64::engine::general_purpose::STANDARD.encode(digest)
+    )
+    .into()
+}
+
+fn mime_type(path: &str) -> String {
+    // Source maps are JSON, but their extension isn't registered
+    if path.ends_with(".map") {
+        return mime_guess::mime::APPLICATION_JSON.to_string();
+    }
+    mime_guess::from_path(path)
+        .first_or_octet_stream()
+        .to_string()
+}
+
+/// Creates the manifest for the given entries, as pairs of the path of the entry module and the
+/// output assets of its chunk group.
+pub async fn asset_manifest(
+    project_path: &FileSystemPath,
+    output_root: &FileSystemPath,
+    entries: &[(FileSystemPath, ResolvedVc<OutputAssets>)],
+) -> Result<AssetManifest> {
+    let mut manifest = AssetManifest::default();
+    for (entry_path, chunk_group) in entries {
+        let name = relative_path(project_path, entry_path).await?;
+        let assets = all_assets_from_entries(**chunk_group).await?;
+        let asset_paths = output_paths(output_root, &assets).await?;
+        let files = assets
+            .iter()
+            .map(|&asset| manifest_file(asset))
+            .try_join()
+            .await
+            .with_context(|| format!("creating the asset manifest for {name}"))?;
+        let (asset_paths, files): (Vec<_>, Vec<_>) = asset_paths
+            .into_iter()
+            .zip(files)
+            .filter_map(|(path, file)| Some((path, file?)))
+            .unzip();
+        manifest
+            .files
+            .extend(asset_paths.iter().cloned().zip(files));
+        manifest.entries.insert(
+            name,
+            AssetManifestEntry {
+                chunks: output_paths(output_root, &(*chunk_group).await?).await?,
+                assets: asset_paths,
+            },
+        );
+    }
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use turbo_rcstr::rcstr;
+    use turbo_tasks::{TurboTasks, Vc};
+    use turbo_tasks_backend::{BackendOptions, TurboTasksBackend, noop_backing_storage};
+    use turbo_tasks_fs::{File, FileSystem, LinkType, VirtualFileSystem};
+    use turbopack_core::virtual_output::VirtualOutputAsset;
+
+    use super::*;
+
+    async fn output_asset(
+        path: FileSystemPath,
+        content: Vc<AssetContent>,
+        references: Vec<ResolvedVc<Box<dyn OutputAsset>>>,
+    ) -> Result<ResolvedVc<Box<dyn OutputAsset>>> {
+        Ok(ResolvedVc::upcast(
+            VirtualOutputAsset::new_with_references(path, content, Vc::cell(references))
+                .to_resolved()
+                .await?,
+        ))
+    }
+
+    fn file(content: &str) -> Vc<AssetContent> {
+        AssetContent::file(File::from(content).into())
+    }
+
+    #[tokio::test]
+    async fn test_asset_manifest() {
+        crate::register();
+        let tt = TurboTasks::new(TurboTasksBackend::new(
+            BackendOptions::default(),
+            noop_backing_storage(),
+        ));
+        tt.run_once(async {
+            let root = VirtualFileSystem::new().root().await?;
+            let project_path = root.join("project")?;
+            let output_root = project_path.join("dist")?;
+
+            let async_chunk =
+                output_asset(output_root.join("chunks/async.js")?, file("async"), vec![]).await?;
+            let source_map =
+                output_asset(output_root.join("index.js.map")?, file("{}"), vec![]).await?;
+            // Neither of these is written as a file
+            let missing = output_asset(
+                output_root.join("missing.js")?,
+                AssetContent::file(FileContent::NotFound.cell()),
+                vec![],
+            )
+            .await?;
+            let link = output_asset(
+                output_root.join("link.js")?,
+                AssetContent::Redirect {
+                    target: rcstr!("index.js"),
+                    link_type: LinkType::empty(),
+                }
+                .cell(),
+                vec![],
+            )
+            .await?;
+            let entry_chunk = output_asset(
+                output_root.join("index.js")?,
+                file("index"),
+                vec![async_chunk, source_map, missing, link],
+            )
+            .await?;
+            let runtime_chunk = output_asset(
+                output_root.join("chunks/runtime.js")?,
+                file("runtime"),
+                vec![],
+            )
+            .await?;
+            let chunk_group = ResolvedVc::cell(vec![runtime_chunk, entry_chunk]);
+
+            let manifest = asset_manifest(
+                &project_path,
+                &output_root,
+                &[(project_path.join("src/index.js")?, chunk_group)],
+            )
+            .await?;
+
+            let entry = &manifest.entries["src/index.js"];
+            assert_eq!(entry.chunks, ["chunks/runtime.js", "index.js"]);
+            let mut assets = entry.assets.clone();
+            assets.sort();
+            assert_eq!(
+                assets,
+                [
+                    "chunks/async.js",
+                    "chunks/runtime.js",
+                    "index.js",
+                    "index.js.map"
+                ]
+            );
+            assert_eq!(
+                manifest.files.keys().collect::<Vec<_>>(),
+                [
+                    "chunks/async.js",
+                    "chunks/runtime.js",
+                    "index.js",
+                    "index.js.map"
+                ]
+            );
+            let file = &manifest.files["chunks/async.js"];
+            assert_eq!(file.size, 5);
+            assert_eq!(&*file.mime_type, "application/javascript");
+            assert_eq!(file.integrity, integrity(b"async"));
+            assert_eq!(
+                &*manifest.files["index.js.map"].mime_type,
+                "application/json"
+            );
+            anyhow::Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn test_integrity() {
+        assert_eq!(
+            &*integrity(b"alert('Hello, world.');"),
+            "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO"
+        );
+    }
+
+    #[test]
+    fn test_mime_type() {
+        assert_eq!(mime_type("chunks/index.js"), "application/javascript");
+        assert_eq!(mime_type("chunks/index.css"), "text/css");
+        assert_eq!(mime_type("chunks/index.js.map"), "application/json");
+    }
+}
@@ -0,0 +1,522 @@
+//! Library builds, which emit every entry as a single file that exports the exports of the entry
+//! module, in the ES module and CommonJS formats. The packages the library depends on are left
+//! external, so that they are resolved by the consumer of the library.
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result, bail};
+use rustc_hash::{FxHashMap, FxHashSet};
+use tracing::Instrument;
+use turbo_rcstr::{RcStr, rcstr};
+use turbo_tasks::{ResolvedVc, TryFlatJoinIterExt, TryJoinIterExt, Vc};
+use turbo_tasks_fs::{FileContent, FileJsonContent, FileSystemPath, glob::Glob};
+use turbopack::{
+    ModuleAssetContext, css::chunk::CssChunkType, ecmascript::chunk::EcmascriptChunkType,
+    global_module_ids::get_global_module_id_strategy,
+};
+use turbopack_core::{
+    asset::Asset,
+    chunk::{
+        ChunkingConfig, ChunkingContext, EvaluatableAsset, EvaluatableAssets, MinifyType,
+        SourceMapsType, availability_info::AvailabilityInfo,
+    },
+    compile_time_info::CompileTimeInfo,
+    context::AssetContext,
+    environment::{Environment, ExecutionEnvironment, NodeJsEnvironment},
+    file_source::FileSource,
+    ident::Layer,
+    module::Module,
+    module_graph::{
+        ModuleGraph, chunk_group_info::ChunkGroupEntry, export_usage::compute_export_usage_info,
+    },
+    output::{OutputAsset, OutputAssets},
+    reference::all_assets_from_entries,
+    reference_type::{EntryReferenceSubType, ReferenceType},
+    resolve::{
+        ExternalTraced, ExternalType, ResolveResult, ResolveResultItem, ResolveResultOption,
+        options::{ImportMap, ImportMapping},
+        parse::Request,
+        plugin::{AfterResolvePlugin, AfterResolvePluginCondition},
+    },
+};
+use turbopack_ecmascript_runtime::RuntimeType;
+use turbopack_node::execution_context::ExecutionContext;
+use turbopack_nodejs::{NodeJsChunkFormat, NodeJsChunkingContext};
+
+use super::resolve_entries;
+use crate::{
+    arguments::LibraryFormat,
+    contexts::{
+        NodeEnv, client_resolve_options_context, get_client_import_map,
+        get_client_module_options_context,
+    },
+};
+
+/// The extensions of the modules that are emitted as library files of their own when the module
+/// structure is preserved.
+const SCRIPT_EXTENSIONS: [&str; 8] = ["js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts"];
+
+impl LibraryFormat {
+    /// The extension of the library files, without a leading `.`.
+    fn extension(self) -> &'static str {
+        match self {
+            LibraryFormat::Esm => "mjs",
+            LibraryFormat::Cjs => "cjs",
+        }
+    }
+
+    /// The extension of the type declarations of the library files, without a leading `.`.
+    fn declaration_extension(self) -> &'static str {
+        match self {
+            LibraryFormat::Esm => "d.mts",
+            LibraryFormat::Cjs => "d.cts",
+        }
+    }
+
+    fn chunk_format(self) -> NodeJsChunkFormat {
+        match self {
+            LibraryFormat::Esm => NodeJsChunkFormat::EsModule,
+            LibraryFormat::Cjs => NodeJsChunkFormat::CommonJs,
+        }
+    }
+
+    /// Whether externals are loaded with `import()`. ES modules can't `require()` them.
+    fn import_externals(self) -> bool {
+        self == LibraryFormat::Esm
+    }
+
+    fn external_type(self) -> ExternalType {
+        match self {
+            LibraryFormat::Esm => ExternalType::EcmaScriptModule,
+            LibraryFormat::Cjs => ExternalType::CommonJs,
+        }
+    }
+}
+
+#[turbo_tasks::value(transparent)]
+struct PackageExternals(Vec<RcStr>);
+
+/// The `dependencies` and `peerDependencies` of the project's `package.json`.
+#[turbo_tasks::function]
+async fn package_externals(project_path: FileSystemPath) -> Result<Vc<PackageExternals>> {
+    let package_json = project_path.join("package.json")?.read_json().await?;
+    let FileJsonContent::Content(package_json) = &*package_json else {
+        return Ok(Vc::cell(vec![]));
+    };
+    let externals: BTreeSet<RcStr> = ["dependencies", "peerDependencies"]
+        .into_iter()
+        .filter_map(|field| package_json.get(field)?.as_object())
+        .flat_map(|dependencies| dependencies.keys().map(|name| RcStr::from(name.as_str())))
+        .collect();
+    Ok(Vc::cell(externals.into_iter().collect()))
+}
+
+/// The client import map, with the packages the library depends on and their subpaths mapped to
+/// externals of the library format.
+#[turbo_tasks::function]
+async fn get_library_import_map(
+    project_path: FileSystemPath,
+    format: LibraryFormat,
+) -> Result<Vc<ImportMap>> {
+    let mut import_map = get_client_import_map(project_path.clone()).owned().await?;
+    let external = ImportMapping::External(None, format.external_type(), ExternalTraced::Untraced)
+        .resolved_cell();
+    for name in &*package_externals(project_path).await? {
+        import_map.insert_exact_alias(name.clone(), external);
+        import_map.insert_wildcard_alias(format!("{name}/"), external);
+    }
+    Ok(import_map.cell())
+}
+
+/// Leaves the modules of the project external when the module structure is preserved, so that
+/// every module imports the library files of the modules it depends on instead of containing
+/// them.
+#[turbo_tasks::value]
+struct PreserveModulesResolvePlugin {
+    project_path: FileSystemPath,
+    format: LibraryFormat,
+}
+
+#[turbo_tasks::value_impl]
+impl PreserveModulesResolvePlugin {
+    #[turbo_tasks::function]
+    fn new(project_path: FileSystemPath, format: LibraryFormat) -> Vc<Self> {
+        PreserveModulesResolvePlugin {
+            project_path,
+            format,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl AfterResolvePlugin for PreserveModulesResolvePlugin {
+    #[turbo_tasks::function]
+    fn after_resolve_condition(&self) -> Vc<AfterResolvePluginCondition> {
+        AfterResolvePluginCondition::new(
+            self.project_path.clone(),
+            Glob::new(format!("**/*.{{{}}}", SCRIPT_EXTENSIONS.join(",")).into()),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn after_resolve(
+        &self,
+        fs_path: FileSystemPath,
+        lookup_path: FileSystemPath,
+        reference_type: ReferenceType,
+        _request: Vc<Request>,
+    ) -> Result<Vc<ResolveResultOption>> {
+        if matches!(reference_type, ReferenceType::Entry(_))
+            || !is_project_module(&fs_path, &self.project_path)
+        {
+            return Ok(ResolveResultOption::none());
+        }
+        let Some(relative_path) = lookup_path.get_relative_path_to(&fs_path) else {
+            return Ok(ResolveResultOption::none());
+        };
+        let name = replace_extension(
+            &relative_path,
+            fs_path.extension_ref(),
+            self.format.extension(),
+        );
+        Ok(ResolveResultOption::some(*ResolveResult::primary(
+            ResolveResultItem::External {
+                name: name.into(),
+                ty: self.format.external_type(),
+                traced: ExternalTraced::Untraced,
+            },
+        )))
+    }
+}
+
+/// Whether the module at `path` is part of the project, as opposed to a package it depends on.
+fn is_project_module(path: &FileSystemPath, project_path: &FileSystemPath) -> bool {
+    path.is_inside_ref(project_path)
+        && !path
+            .path
+            .split('/')
+            .any(|segment| segment == "node_modules")
+        && path
+            .extension_ref()
+            .is_some_and(|extension| SCRIPT_EXTENSIONS.contains(&extension))
+}
+
+/// Replaces the `extension` of `path`, if any, with `new_extension`.
+fn replace_extension(path: &str, extension: Option<&str>, new_extension: &str) -> String {
+    let path_without_extension = extension
+        .and_then(|extension| path.strip_suffix(extension)?.strip_suffix('.'))
+        .unwrap_or(path);
+    format!("{path_without_extension}.{new_extension}")
+}
+
+#[turbo_tasks::function]
+async fn get_library_asset_context(
+    project_path: FileSystemPath,
+    execution_context: Vc<ExecutionContext>,
+    compile_time_info: Vc<CompileTimeInfo>,
+    node_env: Vc<NodeEnv>,
+    source_maps_type: SourceMapsType,
+    format: LibraryFormat,
+    preserve_modules: bool,
+) -> Result<Vc<Box<dyn AssetContext>>> {
+    let import_map = get_library_import_map(project_path.clone(), format)
+        .to_resolved()
+        .await?;
+    let after_resolve_plugins = if preserve_modules {
+        vec![ResolvedVc::upcast(
+            PreserveModulesResolvePlugin::new(project_path.clone(), format)
+                .to_resolved()
+                .await?,
+        )]
+    } else {
+        vec![]
+    };
+    let resolve_options_context = client_resolve_options_context(
+        project_path.clone(),
+        node_env,
+        import_map,
+        after_resolve_plugins,
+    )
+    .await?
+    .cell();
+    let module_options_context = get_client_module_options_context(
+        project_path,
+        execution_context,
+        compile_time_info.environment(),
+        node_env,
+        source_maps_type,
+        format.import_externals(),
+    );
+
+    Ok(Vc::upcast(ModuleAssetContext::new(
+        Default::default(),
+        compile_time_info,
+        module_options_context,
+        resolve_options_context,
+        Layer::new(rcstr!("library")),
+    )))
+}
+
+/// The paths of the modules of the project in the module graph, sorted.
+async fn project_module_paths(
+    module_graph: Vc<ModuleGraph>,
+    project_path: &FileSystemPath,
+) -> Result<Vec<FileSystemPath>> {
+    let graphs = module_graph.await?.get_graphs().await?;
+    let modules = graphs
+        .iter()
+        .flat_map(|graph| graph.iter_nodes())
+        .map(|node| node.module)
+        .collect::<FxHashSet<_>>();
+    let mut paths = modules
+        .into_iter()
+        .map(async |module| {
+            let path = module.ident().path().owned().await?;
+            Ok(is_project_module(&path, project_path).then_some(path))
+        })
+        .try_flat_join()
+        .await?;
+    paths.sort_by(|a, b| a.path.cmp(&b.path));
+    paths.dedup();
+    Ok(paths)
+}
+
+/// The options of a library build, shared by all of its formats.
+pub(super) struct LibraryBuild {
+    pub project_dir: RcStr,
+    pub root_path: FileSystemPath,
+    pub project_path: FileSystemPath,
+    pub build_output_root: FileSystemPath,
+    pub build_output_root_to_root_path: RcStr,
+    pub execution_context: Vc<ExecutionContext>,
+    pub compile_time_info: Vc<CompileTimeInfo>,
+    pub node_env: Vc<NodeEnv>,
+    pub runtime_type: RuntimeType,
+    pub source_maps_type: SourceMapsType,
+    pub minify_type: MinifyType,
+    pub debug_ids: bool,
+    pub scope_hoist: bool,
+    pub chunking_config: ChunkingConfig,
+    pub preserve_modules: bool,
+}
+
+impl LibraryBuild {
+    /// Writes the library files of the entries in the given format, together with the type
+    /// declarations next to their sources.
+    pub(super) async fn emit(
+        &self,
+        entry_requests: &[Vc<Request>],
+        format: LibraryFormat,
+    ) -> Result<()> {
+        let asset_context = get_library_asset_context(
+            self.project_path.clone(),
+            self.execution_context,
+            self.compile_time_info,
+            self.node_env,
+            self.source_maps_type,
+            format,
+            false,
+        );
+        let mut entries = resolve_entries(
+            asset_context,
+            &self.root_path,
+            entry_requests,
+            &self.project_dir,
+        )
+        .instrument(tracing::info_span!("resolve entries"))
+        .await?;
+
+        if self.preserve_modules {
+            let module_graph =
+                ModuleGraph::from_modules(Vc::cell(vec![ChunkGroupEntry::Entry(entries)]), false);
+            let asset_context = get_library_asset_context(
+                self.project_path.clone(),
+                self.execution_context,
+                self.compile_time_info,
+                self.node_env,
+                self.source_maps_type,
+                format,
+                true,
+            );
+            entries = project_module_paths(module_graph, &self.project_path)
+                .await?
+                .into_iter()
+                .map(|path| async move {
+                    asset_context
+                        .process(
+                            Vc::upcast(FileSource::new(path)),
+                            ReferenceType::Entry(EntryReferenceSubType::Undefined),
+                        )
+                        .module()
+                        .to_resolved()
+                        .await
+                })
+                .try_join()
+                .await?;
+        }
+
+        let module_graph = ModuleGraph::from_modules(
+            Vc::cell(vec![ChunkGroupEntry::Entry(entries.clone())]),
+            false,
+        );
+        let module_id_strategy = ResolvedVc::upcast(
+            get_global_module_id_strategy(module_graph, None)
+                .to_resolved()
+                .await?,
+        );
+        let export_usage = compute_export_usage_info(module_graph.to_resolved().await?)
+            .resolve_strongly_consistent()
+            .await?;
+
+        let chunking_context: Vc<Box<dyn ChunkingContext>> = Vc::upcast(
+            NodeJsChunkingContext::builder(
+                self.project_path.clone(),
+                self.build_output_root.clone(),
+                self.build_output_root_to_root_path.clone(),
+                self.build_output_root.clone(),
+                self.build_output_root.clone(),
+                self.build_output_root.clone(),
+                Environment::new(ExecutionEnvironment::NodeJsLambda(
+                    NodeJsEnvironment::default().resolved_cell(),
+                ))
+                .to_resolved()
+                .await?,
+                self.runtime_type,
+            )
+            .source_maps(self.source_maps_type)
+            .module_id_strategy(module_id_strategy)
+            .export_usage(Some(export_usage))
+            .minify_type(self.minify_type.clone())
+            .debug_ids(self.debug_ids)
+            .chunk_format(format.chunk_format())
+            .library(true)
+            .chunking_config(
+                Vc::<EcmascriptChunkType>::default().to_resolved().await?,
+                self.chunking_config.clone(),
+            )
+            .chunking_config(
+                Vc::<CssChunkType>::default().to_resolved().await?,
+                ChunkingConfig {
+                    max_merge_chunk_size: 100_000,
+                    ..Default::default()
+                },
+            )
+            .module_merging(self.scope_hoist)
+            .build(),
+        );
+
+        // Without the module structure, entries with the same file name would overwrite each other
+        let mut library_paths = FxHashMap::default();
+        for entry_module in &entries {
+            let source_path = entry_module.ident().path().owned().await?;
+            let library_path = self.library_path(&source_path, format.extension())?;
+            if let Some(other_source_path) =
+                library_paths.insert(library_path.clone(), source_path.clone())
+            {
+                bail!(
+                    "the entries {} and {} would both be written to {}, rename one of them or use \
+                     --preserve-modules to keep their directories",
+                    other_source_path.path,
+                    source_path.path,
+                    library_path.path
+                );
+            }
+        }
+
+        let library_files = entries
+            .into_iter()
+            .map(|entry_module| async move {
+                let Some(evaluatable) =
+                    ResolvedVc::try_sidecast::<Box<dyn EvaluatableAsset>>(entry_module)
+                else {
+                    bail!("Entry module is not chunkable, so it can't be emitted as a library file")
+                };
+                let source_path = entry_module.ident().path().owned().await?;
+                let asset = chunking_context
+                    .entry_chunk_group(
+                        self.library_path(&source_path, format.extension())?,
+                        EvaluatableAssets::one(*ResolvedVc::upcast(evaluatable)),
+                        module_graph,
+                        OutputAssets::empty(),
+                        AvailabilityInfo::Root,
+                    )
+                    .await?
+                    .asset;
+                Ok((source_path, asset))
+            })
+            .try_join()
+            .await?;
+
+        let mut assets: FxHashSet<ResolvedVc<Box<dyn OutputAsset>>> = FxHashSet::default();
+        for (_, asset) in &library_files {
+            assets.extend(
+                &*async move { all_assets_from_entries(Vc::cell(vec![*asset])).await }
+                    .instrument(tracing::info_span!("list chunks"))
+                    .await?,
+            );
+        }
+        assets
+            .iter()
+            .map(|asset| async move { asset.content().write(asset.path().owned().await?).await })
+            .try_join()
+            .await?;
+
+        library_files
+            .iter()
+            .map(|(source_path, _)| self.emit_declaration(source_path, format))
+            .try_join()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Copies the hand-written type declaration of the module at `source_path`, if there is one,
+    /// next to its library file.
+    async fn emit_declaration(
+        &self,
+        source_path: &FileSystemPath,
+        format: LibraryFormat,
+    ) -> Result<()> {
+        let declaration_path = source_path.parent().join(&replace_extension(
+            source_path.file_name(),
+            source_path.extension_ref(),
+            "d.ts",
+        ))?;
+        let declaration = declaration_path.read();
+        if matches!(&*declaration.await?, FileContent::NotFound) {
+            return Ok(());
+        }
+        self.library_path(source_path, format.declaration_extension())?
+            .write(declaration)
+            .as_side_effect()
+            .await
+    }
+
+    /// The path of the output file for the module at `source_path`. When the module structure is
+    /// preserved, it mirrors the path of the module in the project, otherwise it's named after
+    /// the module.
+    fn library_path(
+        &self,
+        source_path: &FileSystemPath,
+        extension: &str,
+    ) -> Result<FileSystemPath> {
+        let name = if self.preserve_modules {
+            self.project_path
+                .get_path_to(source_path)
+                .with_context(|| {
+                    format!(
+                        "module {} is not in the project directory",
+                        source_path.path
+                    )
+                })?
+        } else {
+            source_path.file_name()
+        };
+        self.build_output_root.join(&replace_extension(
+            name,
+            source_path.extension_ref(),
+            extension,
+        ))
+    }
+}
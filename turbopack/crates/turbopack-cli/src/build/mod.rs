@@ -7,35 +7,50 @@ use std::{
 
 use anyhow::{Context, Result, bail};
 use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
 use tracing::Instrument;
 use turbo_rcstr::RcStr;
 use turbo_tasks::{
-    ReadConsistency, ResolvedVc, TransientInstance, TryJoinIterExt, TurboTasks, Vc, apply_effects,
+    NonLocalValue, ReadConsistency, ResolvedVc, TaskInput, TransientInstance, TryJoinIterExt,
+    TurboTasks, Vc, apply_effects, trace::TraceRawVcs,
 };
 use turbo_tasks_backend::{
     BackendOptions, NoopBackingStorage, TurboTasksBackend, noop_backing_storage,
 };
-use turbo_tasks_fs::FileSystem;
+use turbo_tasks_fs::{File, FileContent, FileSystem, FileSystemPath};
 use turbopack::{
-    css::chunk::CssChunkType, ecmascript::chunk::EcmascriptChunkType,
-    global_module_ids::get_global_module_id_strategy,
+    css::chunk::CssChunkType,
+    ecmascript::{
+        chunk::EcmascriptChunkType,
+        unused_exports::{check_unused_exports, find_unused_exports},
+    },
+    global_module_ids::{get_global_module_id_strategy, write_module_id_records},
 };
 use turbopack_browser::{BrowserChunkingContext, ContentHashing, CurrentChunkMethod};
-use turbopack_cli_utils::issue::{ConsoleUi, LogOptions};
+use turbopack_cli_utils::{
+    issue::{ConsoleUi, IssueRulesReporter, LogOptions},
+    issue_report::{IssueReportFormat, IssueReportOptions, StructuredIssueReporter},
+};
 use turbopack_core::{
     asset::Asset,
     chunk::{
-        ChunkingConfig, ChunkingContext, EvaluatableAsset, EvaluatableAssets, MangleType,
+        ChunkingConfig, ChunkingContext, EvaluatableAsset, EvaluatableAssets, MinifyOptions,
         MinifyType, SourceMapsType, availability_info::AvailabilityInfo,
     },
+    context::AssetContext,
     environment::{BrowserEnvironment, Environment, ExecutionEnvironment, NodeJsEnvironment},
     ident::AssetIdent,
-    issue::{IssueReporter, IssueSeverity, handle_issues},
+    issue::{
+        IssueDescriptionExt, IssueReporter, IssueSeverity, handle_issues,
+        rules::{IssueRule, IssueRules},
+    },
     module::Module,
     module_graph::{
         ModuleGraph,
         chunk_group_info::{ChunkGroup, ChunkGroupEntry},
+        duplicate_packages::{check_duplicate_packages, find_duplicate_packages},
         export_usage::compute_export_usage_info,
+        graph_export::export_module_graph,
     },
     output::{OutputAsset, OutputAssets},
     reference::all_assets_from_entries,
@@ -48,16 +63,20 @@ use turbopack_core::{
 use turbopack_ecmascript_runtime::RuntimeType;
 use turbopack_env::dotenv::load_env;
 use turbopack_node::execution_context::ExecutionContext;
-use turbopack_nodejs::NodeJsChunkingContext;
+use turbopack_nodejs::{NodeJsChunkFormat, NodeJsChunkingContext};
 
+use self::asset_manifest::asset_manifest;
 use crate::{
-    arguments::{BuildArguments, Target},
+    arguments::{BuildArguments, LibraryFormat, SourceMapMode, Target},
     contexts::{NodeEnv, get_client_asset_context, get_client_compile_time_info},
     util::{
         EntryRequest, NormalizedDirs, normalize_dirs, normalize_entries, output_fs, project_fs,
     },
 };
 
+mod asset_manifest;
+pub(crate) mod library;
+
 type Backend = TurboTasksBackend<NoopBackingStorage>;
 
 pub struct TurbopackBuildBuilder {
@@ -71,8 +90,20 @@ pub struct TurbopackBuildBuilder {
     log_detail: bool,
     source_maps_type: SourceMapsType,
     minify_type: MinifyType,
+    debug_ids: bool,
     target: Target,
     scope_hoist: bool,
+    chunking_config: Option<ChunkingConfig>,
+    library_formats: Vec<LibraryFormat>,
+    preserve_modules: bool,
+    es_module_chunks: bool,
+    module_id_records_path: Option<RcStr>,
+    module_graph_export_path: Option<RcStr>,
+    duplicate_packages_report_path: Option<RcStr>,
+    unused_exports_report_path: Option<RcStr>,
+    asset_manifest_path: Option<RcStr>,
+    issue_report: Option<(IssueReportFormat, Option<PathBuf>)>,
+    issue_rules: Vec<IssueRule>,
 }
 
 impl TurbopackBuildBuilder {
@@ -89,11 +120,21 @@ impl TurbopackBuildBuilder {
             show_all: false,
             log_detail: false,
             source_maps_type: SourceMapsType::Full,
-            minify_type: MinifyType::Minify {
-                mangle: Some(MangleType::OptimalSize),
-            },
+            minify_type: MinifyType::Minify(MinifyOptions::default()),
+            debug_ids: false,
             target: Target::Node,
             scope_hoist: true,
+            chunking_config: None,
+            library_formats: vec![],
+            preserve_modules: false,
+            es_module_chunks: false,
+            module_id_records_path: None,
+            module_graph_export_path: None,
+            duplicate_packages_report_path: None,
+            unused_exports_report_path: None,
+            asset_manifest_path: None,
+            issue_report: None,
+            issue_rules: vec![],
         }
     }
 
@@ -132,6 +173,11 @@ impl TurbopackBuildBuilder {
         self
     }
 
+    pub fn debug_ids(mut self, debug_ids: bool) -> Self {
+        self.debug_ids = debug_ids;
+        self
+    }
+
     pub fn scope_hoist(mut self, scope_hoist: bool) -> Self {
         self.scope_hoist = scope_hoist;
         self
@@ -142,6 +188,84 @@ impl TurbopackBuildBuilder {
         self
     }
 
+    /// Overrides the production chunking config for JavaScript chunks. Defaults to
+    /// [`default_chunking_config`] for the target.
+    pub fn chunking_config(mut self, chunking_config: ChunkingConfig) -> Self {
+        self.chunking_config = Some(chunking_config);
+        self
+    }
+
+    /// Builds a library in each of the given formats instead of an application. Every entry is
+    /// emitted as a single file that exports the exports of the entry module, and the packages
+    /// the project depends on are left external. Only supported for the Node.js target.
+    pub fn library_formats(mut self, library_formats: Vec<LibraryFormat>) -> Self {
+        self.library_formats = library_formats;
+        self
+    }
+
+    /// Emits a library file for every module of the project, mirroring the structure of the
+    /// sources, instead of only for the entries.
+    pub fn preserve_modules(mut self, preserve_modules: bool) -> Self {
+        self.preserve_modules = preserve_modules;
+        self
+    }
+
+    /// Emits the chunks as ES modules instead of CommonJS modules. Only supported for the Node.js
+    /// target.
+    pub fn es_module_chunks(mut self, es_module_chunks: bool) -> Self {
+        self.es_module_chunks = es_module_chunks;
+        self
+    }
+
+    /// Reads the module ids of previous builds from the given path, relative to the project
+    /// directory, and keeps them stable. The ids of new modules are added to the file.
+    pub fn module_id_records_path(mut self, module_id_records_path: RcStr) -> Self {
+        self.module_id_records_path = Some(module_id_records_path);
+        self
+    }
+
+    /// Writes the module graph to the given path, relative to the project directory. Paths ending
+    /// in `.dot` are written in the Graphviz DOT format, all others as JSON.
+    pub fn module_graph_export_path(mut self, module_graph_export_path: RcStr) -> Self {
+        self.module_graph_export_path = Some(module_graph_export_path);
+        self
+    }
+
+    /// Reports packages that are included in multiple versions or from multiple locations as
+    /// issues, and writes a JSON report of them to the given path, relative to the project
+    /// directory.
+    pub fn duplicate_packages_report_path(mut self, duplicate_packages_report_path: RcStr) -> Self {
+        self.duplicate_packages_report_path = Some(duplicate_packages_report_path);
+        self
+    }
+
+    /// Reports exports of modules in the project directory that are never imported as issues, and
+    /// writes a JSON report of them to the given path, relative to the project directory.
+    pub fn unused_exports_report_path(mut self, unused_exports_report_path: RcStr) -> Self {
+        self.unused_exports_report_path = Some(unused_exports_report_path);
+        self
+    }
+
+    /// Writes a manifest of the output files of every entry with their content hashes, sizes,
+    /// integrity hashes and MIME types to the given path, relative to the project directory.
+    pub fn asset_manifest_path(mut self, asset_manifest_path: RcStr) -> Self {
+        self.asset_manifest_path = Some(asset_manifest_path);
+        self
+    }
+
+    /// Additionally reports issues in a machine-readable format, written to the given path or to
+    /// stdout when no path is given.
+    pub fn issue_report(mut self, format: IssueReportFormat, path: Option<PathBuf>) -> Self {
+        self.issue_report = Some((format, path));
+        self
+    }
+
+    /// Suppresses issues or overrides their severity before they are reported.
+    pub fn issue_rules(mut self, issue_rules: Vec<IssueRule>) -> Self {
+        self.issue_rules = issue_rules;
+        self
+    }
+
     pub async fn build(self) -> Result<()> {
         let task = self.turbo_tasks.spawn_once_task::<(), _>(async move {
             let build_result_op = build_internal(
@@ -151,8 +275,23 @@ impl TurbopackBuildBuilder {
                 self.browserslist_query,
                 self.source_maps_type,
                 self.minify_type,
+                self.debug_ids,
                 self.target,
                 self.scope_hoist,
+                self.chunking_config
+                    .unwrap_or_else(|| default_chunking_config(self.target)),
+                OutputFormat {
+                    library_formats: self.library_formats,
+                    preserve_modules: self.preserve_modules,
+                    es_module_chunks: self.es_module_chunks,
+                },
+                ReportPaths {
+                    module_id_records_path: self.module_id_records_path,
+                    module_graph_export_path: self.module_graph_export_path,
+                    duplicate_packages_report_path: self.duplicate_packages_report_path,
+                    unused_exports_report_path: self.unused_exports_report_path,
+                    asset_manifest_path: self.asset_manifest_path,
+                },
             );
 
             // Await the result to propagate any errors.
@@ -162,7 +301,7 @@ impl TurbopackBuildBuilder {
                 .instrument(tracing::info_span!("apply effects"))
                 .await?;
 
-            let issue_reporter: Vc<Box<dyn IssueReporter>> =
+            let mut issue_reporter: Vc<Box<dyn IssueReporter>> =
                 Vc::upcast(ConsoleUi::new(TransientInstance::new(LogOptions {
                     project_dir: PathBuf::from(self.project_dir),
                     current_dir: current_dir().unwrap(),
@@ -170,6 +309,38 @@ impl TurbopackBuildBuilder {
                     log_detail: self.log_detail,
                     log_level: self.log_level,
                 })));
+            if let Some((format, path)) = self.issue_report {
+                issue_reporter = Vc::upcast(StructuredIssueReporter::new(
+                    TransientInstance::new(IssueReportOptions {
+                        format,
+                        path,
+                        log_level: self.log_level,
+                    }),
+                    Some(issue_reporter),
+                ));
+            }
+            if !self.issue_rules.is_empty() {
+                let issue_rules = Vc::<IssueRules>::cell(self.issue_rules.clone())
+                    .to_resolved()
+                    .await?;
+                issue_reporter = Vc::upcast(IssueRulesReporter::new(*issue_rules, issue_reporter));
+
+                // Report how many issues each rule matched, so that stale rules can be removed
+                let matches = build_result_op
+                    .peek_issues_with_path()
+                    .await?
+                    .with_rules(issue_rules)
+                    .get_plain_issues_with_rule_matches()
+                    .await?
+                    .matches;
+                for (rule, count) in self.issue_rules.iter().zip(matches) {
+                    if count == 0 {
+                        tracing::warn!(%rule, "issue rule didn't match any issue");
+                    } else {
+                        tracing::info!(%rule, count, "issue rule matched issues");
+                    }
+                }
+            }
 
             handle_issues(
                 build_result_op,
@@ -191,6 +362,28 @@ impl TurbopackBuildBuilder {
     }
 }
 
+/// How the entries of a build are emitted.
+#[derive(
+    Clone, Debug, TaskInput, Hash, PartialEq, Eq, NonLocalValue, Serialize, Deserialize, TraceRawVcs,
+)]
+struct OutputFormat {
+    library_formats: Vec<LibraryFormat>,
+    preserve_modules: bool,
+    es_module_chunks: bool,
+}
+
+/// The paths of the files written next to the chunks of a build.
+#[derive(
+    Clone, Debug, TaskInput, Hash, PartialEq, Eq, NonLocalValue, Serialize, Deserialize, TraceRawVcs,
+)]
+struct ReportPaths {
+    module_id_records_path: Option<RcStr>,
+    module_graph_export_path: Option<RcStr>,
+    duplicate_packages_report_path: Option<RcStr>,
+    unused_exports_report_path: Option<RcStr>,
+    asset_manifest_path: Option<RcStr>,
+}
+
 #[turbo_tasks::function(operation)]
 async fn build_internal(
     project_dir: RcStr,
@@ -199,9 +392,25 @@ async fn build_internal(
     browserslist_query: RcStr,
     source_maps_type: SourceMapsType,
     minify_type: MinifyType,
+    debug_ids: bool,
     target: Target,
     scope_hoist: bool,
+    chunking_config: ChunkingConfig,
+    output_format: OutputFormat,
+    report_paths: ReportPaths,
 ) -> Result<Vc<()>> {
+    let OutputFormat {
+        library_formats,
+        preserve_modules,
+        es_module_chunks,
+    } = output_format;
+    let ReportPaths {
+        module_id_records_path,
+        module_graph_export_path,
+        duplicate_packages_report_path,
+        unused_exports_report_path,
+        asset_manifest_path,
+    } = report_paths;
     let output_fs = output_fs(project_dir.clone());
     let project_fs = project_fs(root_dir.clone(), /* watch= */ false);
     let project_relative = project_dir.strip_prefix(&*root_dir).unwrap();
@@ -255,6 +464,8 @@ async fn build_internal(
         compile_time_info,
         node_env,
         source_maps_type,
+        // ES modules can't `require()` ES module externals
+        es_module_chunks,
     );
 
     let entry_requests = (*entry_requests
@@ -279,39 +490,50 @@ async fn build_internal(
         .await?)
         .to_vec();
 
-    let origin = PlainResolveOrigin::new(asset_context, project_fs.root().await?.join("_")?);
-    let project_dir = &project_dir;
-    let entries = async move {
-        entry_requests
-            .into_iter()
-            .map(|request_vc| async move {
-                let ty = ReferenceType::Entry(EntryReferenceSubType::Undefined);
-                let request = request_vc.await?;
-                origin
-                    .resolve_asset(request_vc, origin.resolve_options(ty.clone()).await?, ty)
-                    .await?
-                    .first_module()
-                    .await?
-                    .with_context(|| {
-                        format!(
-                            "Unable to resolve entry {} from directory {}.",
-                            request.request().unwrap(),
-                            project_dir
-                        )
-                    })
-            })
-            .try_join()
-            .await
+    if es_module_chunks && target != Target::Node {
+        bail!("ES module chunks are only supported for the node target");
+    }
+
+    if !library_formats.is_empty() {
+        if target != Target::Node {
+            bail!("Library builds are only supported for the node target");
+        }
+        let library_build = library::LibraryBuild {
+            project_dir,
+            root_path,
+            project_path,
+            build_output_root,
+            build_output_root_to_root_path,
+            execution_context,
+            compile_time_info,
+            node_env,
+            runtime_type,
+            source_maps_type,
+            minify_type,
+            debug_ids,
+            scope_hoist,
+            chunking_config,
+            preserve_modules,
+        };
+        for format in library_formats {
+            library_build.emit(&entry_requests, format).await?;
+        }
+        return Ok(Default::default());
     }
-    .instrument(tracing::info_span!("resolve entries"))
-    .await?;
+
+    let entries = resolve_entries(asset_context, &root_path, &entry_requests, &project_dir)
+        .instrument(tracing::info_span!("resolve entries"))
+        .await?;
 
     let module_graph = ModuleGraph::from_modules(
         Vc::cell(vec![ChunkGroupEntry::Entry(entries.clone())]),
         false,
     );
+    let module_id_records_path = module_id_records_path
+        .map(|path| project_path.join(&path))
+        .transpose()?;
     let module_id_strategy = ResolvedVc::upcast(
-        get_global_module_id_strategy(module_graph)
+        get_global_module_id_strategy(module_graph, module_id_records_path.clone())
             .to_resolved()
             .await?,
     );
@@ -322,7 +544,7 @@ async fn build_internal(
     let chunking_context: Vc<Box<dyn ChunkingContext>> = match target {
         Target::Browser => {
             let mut builder = BrowserChunkingContext::builder(
-                project_path,
+                project_path.clone(),
                 build_output_root.clone(),
                 build_output_root_to_root_path,
                 build_output_root.clone(),
@@ -345,7 +567,8 @@ async fn build_internal(
             .module_id_strategy(module_id_strategy)
             .export_usage(Some(export_usage))
             .current_chunk_method(CurrentChunkMethod::DocumentCurrentScript)
-            .minify_type(minify_type);
+            .minify_type(minify_type)
+            .debug_ids(debug_ids);
 
             match *node_env.await? {
                 NodeEnv::Development => {}
@@ -353,12 +576,7 @@ async fn build_internal(
                     builder = builder
                         .chunking_config(
                             Vc::<EcmascriptChunkType>::default().to_resolved().await?,
-                            ChunkingConfig {
-                                min_chunk_size: 50_000,
-                                max_chunk_count_per_group: 40,
-                                max_merge_chunk_size: 200_000,
-                                ..Default::default()
-                            },
+                            chunking_config,
                         )
                         .chunking_config(
                             Vc::<CssChunkType>::default().to_resolved().await?,
@@ -376,7 +594,7 @@ async fn build_internal(
         }
         Target::Node => {
             let mut builder = NodeJsChunkingContext::builder(
-                project_path,
+                project_path.clone(),
                 build_output_root.clone(),
                 build_output_root_to_root_path,
                 build_output_root.clone(),
@@ -392,7 +610,13 @@ async fn build_internal(
             .source_maps(source_maps_type)
             .module_id_strategy(module_id_strategy)
             .export_usage(Some(export_usage))
-            .minify_type(minify_type);
+            .minify_type(minify_type)
+            .debug_ids(debug_ids)
+            .chunk_format(if es_module_chunks {
+                NodeJsChunkFormat::EsModule
+            } else {
+                NodeJsChunkFormat::CommonJs
+            });
 
             match *node_env.await? {
                 NodeEnv::Development => {}
@@ -400,12 +624,7 @@ async fn build_internal(
                     builder = builder
                         .chunking_config(
                             Vc::<EcmascriptChunkType>::default().to_resolved().await?,
-                            ChunkingConfig {
-                                min_chunk_size: 20_000,
-                                max_chunk_count_per_group: 100,
-                                max_merge_chunk_size: 100_000,
-                                ..Default::default()
-                            },
+                            chunking_config,
                         )
                         .chunking_config(
                             Vc::<CssChunkType>::default().to_resolved().await?,
@@ -422,6 +641,12 @@ async fn build_internal(
         }
     };
 
+    let entry_paths = entries
+        .iter()
+        .map(async |entry| entry.ident().path().owned().await)
+        .try_join()
+        .await?;
+
     let entry_chunk_groups = entries
         .into_iter()
         .map(|entry_module| {
@@ -492,7 +717,7 @@ async fn build_internal(
         .await?;
 
     let mut chunks: FxHashSet<ResolvedVc<Box<dyn OutputAsset>>> = FxHashSet::default();
-    for chunk_group in entry_chunk_groups {
+    for &chunk_group in &entry_chunk_groups {
         chunks.extend(
             &*async move { all_assets_from_entries(*chunk_group).await }
                 .instrument(tracing::info_span!("list chunks"))
@@ -506,9 +731,118 @@ async fn build_internal(
         .try_join()
         .await?;
 
+    if let Some(module_id_records_path) = module_id_records_path {
+        write_module_id_records(module_graph, module_id_records_path)
+            .as_side_effect()
+            .await?;
+    }
+
+    if let Some(asset_manifest_path) = asset_manifest_path {
+        let entries = entry_paths
+            .into_iter()
+            .zip(entry_chunk_groups)
+            .collect::<Vec<_>>();
+        let manifest = asset_manifest(&project_path, &build_output_root, &entries).await?;
+        output_fs
+            .root()
+            .await?
+            .join(&asset_manifest_path)?
+            .write(FileContent::Content(File::from(manifest.to_json()?)).cell())
+            .as_side_effect()
+            .await?;
+    }
+
+    if let Some(module_graph_export_path) = module_graph_export_path {
+        let export = export_module_graph(module_graph, Some(chunking_context)).await?;
+        let content = if module_graph_export_path.ends_with(".dot") {
+            export.to_dot()
+        } else {
+            export.to_json()?
+        };
+        output_fs
+            .root()
+            .await?
+            .join(&module_graph_export_path)?
+            .write(FileContent::Content(File::from(content)).cell())
+            .as_side_effect()
+            .await?;
+    }
+
+    if let Some(duplicate_packages_report_path) = duplicate_packages_report_path {
+        check_duplicate_packages(module_graph, Some(chunking_context)).await?;
+        let report = find_duplicate_packages(module_graph, Some(chunking_context)).await?;
+        output_fs
+            .root()
+            .await?
+            .join(&duplicate_packages_report_path)?
+            .write(FileContent::Content(File::from(report.to_json()?)).cell())
+            .as_side_effect()
+            .await?;
+    }
+
+    if let Some(unused_exports_report_path) = unused_exports_report_path {
+        check_unused_exports(module_graph, project_path.clone()).await?;
+        let report = find_unused_exports(module_graph, project_path).await?;
+        output_fs
+            .root()
+            .await?
+            .join(&unused_exports_report_path)?
+            .write(FileContent::Content(File::from(report.to_json()?)).cell())
+            .as_side_effect()
+            .await?;
+    }
+
     Ok(Default::default())
 }
 
+/// Resolves the entry requests in the project, relative to the root path.
+async fn resolve_entries(
+    asset_context: Vc<Box<dyn AssetContext>>,
+    root_path: &FileSystemPath,
+    entry_requests: &[Vc<Request>],
+    project_dir: &str,
+) -> Result<Vec<ResolvedVc<Box<dyn Module>>>> {
+    let origin = PlainResolveOrigin::new(asset_context, root_path.join("_")?);
+    entry_requests
+        .iter()
+        .map(|&request_vc| async move {
+            let ty = ReferenceType::Entry(EntryReferenceSubType::Undefined);
+            let request = request_vc.await?;
+            origin
+                .resolve_asset(request_vc, origin.resolve_options(ty.clone()).await?, ty)
+                .await?
+                .first_module()
+                .await?
+                .with_context(|| {
+                    format!(
+                        "Unable to resolve entry {} from directory {}.",
+                        request.request().unwrap(),
+                        project_dir
+                    )
+                })
+        })
+        .try_join()
+        .await
+}
+
+/// The production chunking config for JavaScript chunks of the given target.
+pub fn default_chunking_config(target: Target) -> ChunkingConfig {
+    match target {
+        Target::Browser => ChunkingConfig {
+            min_chunk_size: 50_000,
+            max_chunk_count_per_group: 40,
+            max_merge_chunk_size: 200_000,
+            ..Default::default()
+        },
+        Target::Node => ChunkingConfig {
+            min_chunk_size: 20_000,
+            max_chunk_count_per_group: 100,
+            max_merge_chunk_size: 100_000,
+            ..Default::default()
+        },
+    }
+}
+
 pub async fn build(args: &BuildArguments) -> Result<()> {
     let NormalizedDirs {
         project_dir,
@@ -524,6 +858,7 @@ pub async fn build(args: &BuildArguments) -> Result<()> {
         noop_backing_storage(),
     ));
 
+    let target = args.common.target.unwrap_or(Target::Node);
     let mut builder = TurbopackBuildBuilder::new(tt.clone(), project_dir, root_dir)
         .log_detail(args.common.log_detail)
         .log_level(
@@ -531,22 +866,69 @@ pub async fn build(args: &BuildArguments) -> Result<()> {
                 .log_level
                 .map_or_else(|| IssueSeverity::Warning, |l| l.0),
         )
-        .source_maps_type(if args.no_sourcemap {
-            SourceMapsType::None
-        } else {
-            SourceMapsType::Full
+        .source_maps_type(match (args.no_sourcemap, args.sourcemap) {
+            (true, _) => SourceMapsType::None,
+            (false, None | Some(SourceMapMode::Full)) => SourceMapsType::Full,
+            (false, Some(SourceMapMode::Hidden)) => SourceMapsType::Hidden,
+            (false, Some(SourceMapMode::Nosources)) => SourceMapsType::NoSources,
         })
         .minify_type(if args.no_minify {
             MinifyType::NoMinify
         } else {
-            MinifyType::Minify {
-                mangle: Some(MangleType::OptimalSize),
-            }
+            MinifyType::Minify(MinifyOptions::default())
         })
+        .debug_ids(args.debug_ids)
         .scope_hoist(!args.no_scope_hoist)
-        .target(args.common.target.unwrap_or(Target::Node))
+        .target(target)
+        .library_formats(args.library_formats.clone())
+        .preserve_modules(args.preserve_modules)
+        .es_module_chunks(args.esm)
         .show_all(args.common.show_all);
 
+    let default_chunking_config = default_chunking_config(target);
+    builder = builder.chunking_config(ChunkingConfig {
+        min_chunk_size: args
+            .min_chunk_size
+            .unwrap_or(default_chunking_config.min_chunk_size),
+        max_chunk_size: args
+            .max_chunk_size
+            .unwrap_or(default_chunking_config.max_chunk_size),
+        max_chunk_count_per_group: args
+            .max_chunk_count_per_group
+            .unwrap_or(default_chunking_config.max_chunk_count_per_group),
+        max_merge_chunk_size: args
+            .max_merge_chunk_size
+            .unwrap_or(default_chunking_config.max_merge_chunk_size),
+        cache_groups: args.cache_groups.clone(),
+        ..default_chunking_config
+    });
+
+    if let Some(module_id_records) = &args.module_id_records {
+        builder = builder.module_id_records_path(module_id_records.clone().into());
+    }
+
+    if let Some(export_module_graph) = &args.export_module_graph {
+        builder = builder.module_graph_export_path(export_module_graph.clone().into());
+    }
+
+    if let Some(duplicate_packages_report) = &args.duplicate_packages_report {
+        builder = builder.duplicate_packages_report_path(duplicate_packages_report.clone().into());
+    }
+
+    if let Some(unused_exports_report) = &args.unused_exports_report {
+        builder = builder.unused_exports_report_path(unused_exports_report.clone().into());
+    }
+
+    if let Some(asset_manifest) = &args.asset_manifest {
+        builder = builder.asset_manifest_path(asset_manifest.clone().into());
+    }
+
+    builder = builder.issue_rules(args.common.issue_rules()?);
+
+    if let Some(issue_report) = args.issue_report {
+        builder = builder.issue_report(issue_report, args.issue_report_path.clone());
+    }
+
     for entry in normalize_entries(&args.common.entries) {
         builder = builder.entry_request(EntryRequest::Relative(entry));
     }
@@ -22,7 +22,11 @@ use turbopack_core::{
     environment::{BrowserEnvironment, Environment, ExecutionEnvironment},
     free_var_references,
     ident::Layer,
-    resolve::options::{ImportMap, ImportMapping},
+    resolve::{
+        options::{ImportMap, ImportMapping},
+        plugin::AfterResolvePlugin,
+        pnp::find_pnp_manifest,
+    },
 };
 use turbopack_node::{
     execution_context::ExecutionContext, transforms::postcss::PostCssTransformOptions,
@@ -82,10 +86,28 @@ pub async fn get_client_resolve_options_context(
     let next_client_import_map = get_client_import_map(project_path.clone())
         .to_resolved()
         .await?;
+    Ok(
+        client_resolve_options_context(project_path, node_env, next_client_import_map, vec![])
+            .await?
+            .cell(),
+    )
+}
+
+/// The resolve options of the client with a custom import map and resolve plugins, which also
+/// apply to foreign code.
+pub(crate) async fn client_resolve_options_context(
+    project_path: FileSystemPath,
+    node_env: Vc<NodeEnv>,
+    import_map: ResolvedVc<ImportMap>,
+    after_resolve_plugins: Vec<ResolvedVc<Box<dyn AfterResolvePlugin>>>,
+) -> Result<ResolveOptionsContext> {
+    let root = project_path.root().owned().await?;
     let module_options_context = ResolveOptionsContext {
-        enable_node_modules: Some(project_path.root().owned().await?),
+        enable_pnp: find_pnp_manifest(root.clone()).owned().await?,
+        enable_node_modules: Some(root),
         custom_conditions: vec![node_env.await?.to_string().into(), rcstr!("browser")],
-        import_map: Some(next_client_import_map),
+        import_map: Some(import_map),
+        after_resolve_plugins,
         browser: true,
         module: true,
         ..Default::default()
@@ -98,20 +120,24 @@ pub async fn get_client_resolve_options_context(
             module_options_context.clone().resolved_cell(),
         )],
         ..module_options_context
-    }
-    .cell())
+    })
 }
 
 #[turbo_tasks::function]
-async fn get_client_module_options_context(
+pub(crate) async fn get_client_module_options_context(
     project_path: FileSystemPath,
     execution_context: ResolvedVc<ExecutionContext>,
     env: ResolvedVc<Environment>,
     node_env: Vc<NodeEnv>,
     source_maps_type: SourceMapsType,
+    import_externals: bool,
 ) -> Result<Vc<ModuleOptionsContext>> {
     let is_dev = matches!(*node_env.await?, NodeEnv::Development);
     let module_options_context = ModuleOptionsContext {
+        ecmascript: EcmascriptOptionsContext {
+            import_externals,
+            ..Default::default()
+        },
         environment: Some(env),
         execution_context: Some(execution_context),
         tree_shaking_mode: Some(TreeShakingMode::ReexportsOnly),
@@ -163,6 +189,7 @@ pub fn get_client_asset_context(
     compile_time_info: Vc<CompileTimeInfo>,
     node_env: Vc<NodeEnv>,
     source_maps_type: SourceMapsType,
+    import_externals: bool,
 ) -> Vc<Box<dyn AssetContext>> {
     let resolve_options_context =
         get_client_resolve_options_context(project_path.clone(), node_env);
@@ -172,6 +199,7 @@ pub fn get_client_asset_context(
         compile_time_info.environment(),
         node_env,
         source_maps_type,
+        import_externals,
     );
 
     let asset_context: Vc<Box<dyn AssetContext>> = Vc::upcast(ModuleAssetContext::new(
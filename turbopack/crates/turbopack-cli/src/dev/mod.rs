@@ -23,9 +23,9 @@ use turbo_tasks_backend::{
 use turbo_tasks_fs::FileSystem;
 use turbo_tasks_malloc::TurboMalloc;
 use turbopack::evaluate_context::node_build_environment;
-use turbopack_cli_utils::issue::{ConsoleUi, LogOptions};
+use turbopack_cli_utils::issue::{ConsoleUi, IssueRulesReporter, LogOptions};
 use turbopack_core::{
-    issue::{IssueReporter, IssueSeverity},
+    issue::{IssueReporter, IssueSeverity, rules::IssueRule},
     resolve::parse::Request,
     server_fs::ServerFileSystem,
 };
@@ -69,6 +69,7 @@ pub struct TurbopackDevServerBuilder {
     show_all: bool,
     log_detail: bool,
     allow_retry: bool,
+    issue_rules: Vec<IssueRule>,
 }
 
 impl TurbopackDevServerBuilder {
@@ -93,6 +94,7 @@ impl TurbopackDevServerBuilder {
             show_all: false,
             log_detail: false,
             allow_retry: false,
+            issue_rules: vec![],
         }
     }
 
@@ -149,6 +151,12 @@ impl TurbopackDevServerBuilder {
         self
     }
 
+    /// Suppresses issues or overrides their severity before they are passed to the issue reporter.
+    pub fn issue_rules(mut self, issue_rules: Vec<IssueRule>) -> TurbopackDevServerBuilder {
+        self.issue_rules = issue_rules;
+        self
+    }
+
     /// Attempts to find an open port to bind.
     fn find_port(&self, host: IpAddr, port: u16, max_attempts: u16) -> Result<DevServerBuilder> {
         // max_attempts of 1 means we loop 0 times.
@@ -216,6 +224,7 @@ impl TurbopackDevServerBuilder {
             // Initialize a ConsoleUi reporter if no custom reporter was provided
             Box::new(move || Vc::upcast(ConsoleUi::new(log_args.clone())))
         });
+        let issue_rules = self.issue_rules;
 
         #[derive(Clone, TraceRawVcs, NonLocalValue)]
         struct ServerSourceProvider {
@@ -244,7 +253,17 @@ impl TurbopackDevServerBuilder {
             browserslist_query,
         };
 
-        let issue_reporter_arc = Arc::new(move || issue_provider.get_issue_reporter());
+        let issue_reporter_arc = Arc::new(move || {
+            let issue_reporter = issue_provider.get_issue_reporter();
+            if issue_rules.is_empty() {
+                issue_reporter
+            } else {
+                Vc::upcast(IssueRulesReporter::new(
+                    Vc::cell(issue_rules.clone()),
+                    issue_reporter,
+                ))
+            }
+        });
         Ok(server.serve(tasks, source, issue_reporter_arc))
     }
 }
@@ -382,7 +401,8 @@ pub async fn start_server(args: &DevArguments) -> Result<()> {
             args.common
                 .log_level
                 .map_or_else(|| IssueSeverity::Warning, |l| l.0),
-        );
+        )
+        .issue_rules(args.common.issue_rules()?);
 
     for entry in normalize_entries(&args.common.entries) {
         server = server.entry_request(EntryRequest::Relative(entry))
@@ -0,0 +1,124 @@
+//! Builds the projects in `tests/chunking` and checks the emitted chunks.
+
+mod util;
+
+use std::{fs, path::Path, process::Command};
+
+use anyhow::{Context, Result, bail};
+use tempfile::TempDir;
+use util::copy_dir;
+
+/// Builds a copy of the fixture and returns the project directory.
+fn build(fixture: &str, args: &[&str]) -> Result<TempDir> {
+    let fixture_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/chunking")
+        .join(fixture);
+    // The output is written into the project, so build a copy of it.
+    let project_dir = tempfile::tempdir()?;
+    copy_dir(&fixture_dir, project_dir.path())
+        .with_context(|| format!("failed to copy {}", fixture_dir.display()))?;
+
+    let status = Command::new(env!("CARGO_BIN_EXE_turbopack-cli"))
+        .arg("build")
+        .arg("--dir")
+        .arg(project_dir.path())
+        .args(["--no-minify", "--no-sourcemap"])
+        .args(args)
+        .arg("src/index.js")
+        .status()
+        .context("failed to run turbopack-cli")?;
+    if !status.success() {
+        bail!("building {fixture} failed with {status}");
+    }
+    Ok(project_dir)
+}
+
+/// The paths, relative to `dist`, and contents of the emitted JavaScript files.
+fn emitted_js_files(project_dir: &TempDir) -> Result<Vec<(String, String)>> {
+    let dist = project_dir.path().join("dist");
+    let mut files = Vec::new();
+    let mut dirs = vec![dist.clone()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path
+                .extension()
+                .is_some_and(|extension| extension == "js" || extension == "mjs")
+            {
+                let content = fs::read_to_string(&path)?;
+                let path = path
+                    .strip_prefix(&dist)?
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                files.push((path, content));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap()
+}
+
+#[test]
+fn cache_group_chunks_are_named_after_the_cache_group() -> Result<()> {
+    // Scope hoisting would merge the module of the cache group into the entry module
+    let project_dir = build(
+        "cache-groups",
+        &[
+            "--no-scope-hoist",
+            "--cache-group",
+            "vendor-lib=/node_modules/lib/",
+        ],
+    )?;
+    let files = emitted_js_files(&project_dir)?;
+
+    let named = files
+        .iter()
+        .filter(|(path, _)| file_name(path).starts_with("vendor-lib-"))
+        .collect::<Vec<_>>();
+    assert_eq!(named.len(), 1, "expected one vendor-lib chunk in {files:?}");
+    let (_, content) = named[0];
+    assert!(content.contains("Hello from lib"));
+
+    // The module of the cache group is only part of its chunk
+    assert!(
+        files
+            .iter()
+            .filter(|(path, _)| !file_name(path).starts_with("vendor-lib-"))
+            .all(|(_, content)| !content.contains("Hello from lib"))
+    );
+    Ok(())
+}
+
+#[test]
+fn es_module_chunks_run_in_node() -> Result<()> {
+    let project_dir = build("esm", &["--target", "node", "--esm"])?;
+    let files = emitted_js_files(&project_dir)?;
+    assert!(
+        files.iter().all(|(path, _)| path.ends_with(".mjs")),
+        "expected only ES module chunks in {files:?}"
+    );
+
+    let output = Command::new("node")
+        .arg("dist/index.entry.mjs")
+        .current_dir(project_dir.path())
+        .output()
+        .context("failed to run node")?;
+    if !output.status.success() {
+        bail!(
+            "running the output failed with {}:\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "Hello from es-module!"
+    );
+    Ok(())
+}
@@ -0,0 +1,5 @@
+import { basename } from 'node:path'
+
+import { greet } from './greet.js'
+
+console.log(greet(basename('/chunks/es-module')))
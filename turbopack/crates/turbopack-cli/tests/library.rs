@@ -0,0 +1,84 @@
+//! Builds the projects in `tests/library` as libraries and runs their `test.mjs` with Node.js,
+//! which imports the output and checks it.
+
+mod util;
+
+use std::{
+    path::Path,
+    process::{Command, Output},
+};
+
+use anyhow::{Context, Result, bail};
+use tempfile::TempDir;
+use util::copy_dir;
+
+/// Builds a copy of the fixture as a library and returns the project directory and the output of
+/// the build.
+fn build(fixture: &str, args: &[&str]) -> Result<(TempDir, Output)> {
+    let fixture_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/library")
+        .join(fixture);
+    // The output is written into the project, so build a copy of it.
+    let project_dir = tempfile::tempdir()?;
+    copy_dir(&fixture_dir, project_dir.path())
+        .with_context(|| format!("failed to copy {}", fixture_dir.display()))?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_turbopack-cli"))
+        .arg("build")
+        .arg("--dir")
+        .arg(project_dir.path())
+        .args(["--no-minify", "--library", "esm", "--library", "cjs"])
+        .args(args)
+        .output()
+        .context("failed to run turbopack-cli")?;
+    Ok((project_dir, output))
+}
+
+fn build_and_test(fixture: &str, args: &[&str]) -> Result<()> {
+    let (project_dir, output) = build(fixture, args)?;
+    if !output.status.success() {
+        bail!(
+            "building {fixture} failed with {}:\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let status = Command::new("node")
+        .arg("test.mjs")
+        .current_dir(project_dir.path())
+        .status()
+        .context("failed to run node")?;
+    if !status.success() {
+        bail!("testing the output of {fixture} failed with {status}");
+    }
+    Ok(())
+}
+
+#[test]
+fn library_exports() -> Result<()> {
+    build_and_test("exports", &["src/index.ts"])
+}
+
+#[test]
+fn library_preserve_modules() -> Result<()> {
+    build_and_test("preserve-modules", &["--preserve-modules", "src/index.js"])
+}
+
+#[test]
+fn library_entries_with_the_same_name() -> Result<()> {
+    let (_, output) = build("name-collision", &["src/a/index.js", "src/b/index.js"])?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("would both be written to"),
+        "unexpected error: {stderr}"
+    );
+
+    let (_, output) = build(
+        "name-collision",
+        &["--preserve-modules", "src/a/index.js", "src/b/index.js"],
+    )?;
+    assert!(output.status.success());
+    Ok(())
+}
@@ -0,0 +1,5 @@
+export declare const name: string
+export declare function describe(): string
+export default describe
+export declare let count: number
+export declare function increment(): void
@@ -0,0 +1,17 @@
+import { dep } from 'dep'
+import { peer } from 'peer'
+import { double } from './math'
+
+export const name = 'exports'
+
+export function describe(): string {
+  return `${name}: ${dep}, ${peer}, ${double(2)}`
+}
+
+export default describe
+
+export let count = 0
+
+export function increment(): void {
+  count += 1
+}
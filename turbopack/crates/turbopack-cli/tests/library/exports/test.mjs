@@ -0,0 +1,29 @@
+import assert from 'node:assert/strict'
+import { readFileSync } from 'node:fs'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+
+const esm = await import('./dist/index.mjs')
+assert.equal(esm.name, 'exports')
+assert.equal(esm.describe(), 'exports: value of dep, value of peer, 4')
+assert.equal(esm.default, esm.describe)
+
+const cjs = require('./dist/index.cjs')
+assert.equal(cjs.name, 'exports')
+assert.equal(cjs.describe(), 'exports: value of dep, value of peer, 4')
+assert.equal(cjs.default, cjs.describe)
+
+// CommonJS exports are getters, so reassigned exports are live
+cjs.increment()
+assert.equal(cjs.count, 1)
+
+for (const file of ['dist/index.mjs', 'dist/index.cjs']) {
+  const code = readFileSync(file, 'utf8')
+  assert(!code.includes('value of dep'), `${file} bundles dep`)
+  assert(!code.includes('value of peer'), `${file} bundles peer`)
+}
+
+const declaration = readFileSync('src/index.d.ts', 'utf8')
+assert.equal(readFileSync('dist/index.d.mts', 'utf8'), declaration)
+assert.equal(readFileSync('dist/index.d.cts', 'utf8'), declaration)
@@ -0,0 +1,7 @@
+import { format } from './utils/format.js'
+
+export { format }
+
+export function greet(name) {
+  return format(`hello ${name}`)
+}
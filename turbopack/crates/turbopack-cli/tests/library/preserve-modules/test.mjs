@@ -0,0 +1,20 @@
+import assert from 'node:assert/strict'
+import { readFileSync } from 'node:fs'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+
+const esm = await import('./dist/src/index.mjs')
+assert.equal(esm.greet('world'), 'formatted hello world')
+assert.equal(esm.format, (await import('./dist/src/utils/format.mjs')).format)
+
+const cjs = require('./dist/src/index.cjs')
+assert.equal(cjs.greet('world'), 'formatted hello world')
+assert.equal(cjs.format, require('./dist/src/utils/format.cjs').format)
+
+// Every module is emitted on its own and imports the others by their relative paths.
+for (const extension of ['mjs', 'cjs']) {
+  const code = readFileSync(`dist/src/index.${extension}`, 'utf8')
+  assert(code.includes(`utils/format.${extension}`), `index.${extension} doesn't import format`)
+  assert(!code.includes('formatted '), `index.${extension} bundles format`)
+}
@@ -0,0 +1,141 @@
+//! Builds the projects in `tests/reports` with the CLI's reports enabled and checks the written
+//! reports and the reported issues.
+
+mod util;
+
+use std::{fs, path::Path, process::Command};
+
+use anyhow::{Context, Result, bail};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+use util::copy_dir;
+
+struct BuildResult {
+    project_dir: TempDir,
+    /// The issues of the build, as written by `--issue-report json-lines`.
+    issues: Vec<Value>,
+}
+
+impl BuildResult {
+    fn read_json(&self, path: &str) -> Result<Value> {
+        let path = self.project_dir.path().join(path);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn issue_titles(&self) -> Vec<&str> {
+        self.issues
+            .iter()
+            .filter_map(|issue| issue["title"].as_str())
+            .collect()
+    }
+}
+
+fn build(fixture: &str, args: &[&str]) -> Result<BuildResult> {
+    let fixture_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/reports")
+        .join(fixture);
+    // The output is written into the project, so build a copy of it.
+    let project_dir = tempfile::tempdir()?;
+    copy_dir(&fixture_dir, project_dir.path())
+        .with_context(|| format!("failed to copy {}", fixture_dir.display()))?;
+    let issue_report_path = project_dir.path().join("issues.jsonl");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_turbopack-cli"))
+        .arg("build")
+        .arg("--dir")
+        .arg(project_dir.path())
+        .args([
+            "--no-minify",
+            "--issue-report",
+            "json-lines",
+            "--issue-report-path",
+        ])
+        .arg(&issue_report_path)
+        .args(args)
+        .arg("src/index.js")
+        .status()
+        .context("failed to run turbopack-cli")?;
+    if !status.success() {
+        bail!("building {fixture} failed with {status}");
+    }
+
+    let issues = fs::read_to_string(&issue_report_path)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<Value>, _>>()?;
+    Ok(BuildResult {
+        project_dir,
+        issues,
+    })
+}
+
+#[test]
+fn unused_exports_report() -> Result<()> {
+    let result = build(
+        "unused-exports",
+        &["--unused-exports-report", "unused-exports.json"],
+    )?;
+
+    // `constants.js` is imported as a namespace, so all of its exports count as used, and
+    // `unused.js` isn't part of the module graph.
+    assert_eq!(
+        result.read_json("unused-exports.json")?,
+        json!({
+            "modules": [
+                {
+                    "path": "[project]/src/utils.js",
+                    "exports": ["DATE_FORMAT", "parseDate"],
+                },
+            ],
+        })
+    );
+    assert_eq!(result.issue_titles(), vec!["2 exports are never imported"]);
+    Ok(())
+}
+
+#[test]
+fn duplicate_packages_report() -> Result<()> {
+    let result = build(
+        "duplicate-packages",
+        &["--duplicate-packages-report", "duplicate-packages.json"],
+    )?;
+
+    let report = result.read_json("duplicate-packages.json")?;
+    let packages = report["packages"].as_array().context("packages")?;
+    assert_eq!(packages.len(), 1);
+    let package = &packages[0];
+    assert_eq!(package["name"], "lib");
+
+    let copies = package["copies"].as_array().context("copies")?;
+    assert_eq!(
+        copies
+            .iter()
+            .map(|copy| (copy["version"].clone(), copy["path"].clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (json!("1.0.0"), json!("[project]/node_modules/lib")),
+            (
+                json!("2.0.0"),
+                json!("[project]/node_modules/other/node_modules/lib")
+            ),
+        ]
+    );
+    // Only the largest copy is needed, every other copy is wasted.
+    let sizes = copies
+        .iter()
+        .map(|copy| copy["size"].as_u64().context("size"))
+        .collect::<Result<Vec<_>>>()?;
+    assert!(sizes.iter().all(|size| *size > 0));
+    assert_eq!(
+        package["wastedBytes"].as_u64(),
+        Some(sizes.iter().sum::<u64>() - sizes.iter().max().unwrap())
+    );
+
+    assert_eq!(
+        result.issue_titles(),
+        vec!["Package `lib` is included in 2 versions"]
+    );
+    Ok(())
+}
@@ -0,0 +1,4 @@
+import { version } from 'lib'
+import { otherVersion } from 'other'
+
+console.log(version, otherVersion)
@@ -0,0 +1,2 @@
+export const ONE = 1
+export const TWO = 2
@@ -0,0 +1,4 @@
+import { formatDate } from './utils'
+import * as constants from './constants'
+
+console.log(formatDate(new Date()), constants)
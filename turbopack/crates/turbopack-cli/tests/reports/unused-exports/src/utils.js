@@ -0,0 +1,9 @@
+export function formatDate(date) {
+  return date.toISOString()
+}
+
+export function parseDate(value) {
+  return new Date(value)
+}
+
+export const DATE_FORMAT = 'iso'
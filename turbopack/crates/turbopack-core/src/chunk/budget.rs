@@ -0,0 +1,327 @@
+use std::io::Write;
+
+use anyhow::Result;
+use flate2::{Compression, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::{RcStr, rcstr};
+use turbo_tasks::{
+    NonLocalValue, ResolvedVc, TaskInput, TryFlatJoinIterExt, TryJoinIterExt, ValueToString, Vc,
+    trace::TraceRawVcs,
+};
+use turbo_tasks_fs::{FileContent, FileSystemPath};
+
+use super::{ChunkItem, ChunkType, ChunkingContext, OutputChunk};
+use crate::{
+    asset::Asset,
+    ident::AssetIdent,
+    issue::{Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    output::{OutputAsset, OutputAssets},
+};
+
+/// The number of largest modules listed in a [ChunkBudgetIssue].
+const LARGEST_MODULES_COUNT: usize = 10;
+
+/// Size limits for the output of a chunk group. All sizes are in bytes and limits that are `None`
+/// are not enforced.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+    TaskInput,
+)]
+pub struct ChunkBudgets {
+    /// The maximum size of a single chunk.
+    pub max_chunk_size: Option<u64>,
+    /// The maximum gzip compressed size of a single chunk.
+    pub max_chunk_gzip_size: Option<u64>,
+    /// The maximum size of all chunks of a chunk group, e.g. all chunks loaded by an entry.
+    pub max_entry_size: Option<u64>,
+    /// The maximum gzip compressed size of all chunks of a chunk group.
+    pub max_entry_gzip_size: Option<u64>,
+    /// The severity of the issue emitted when a budget is exceeded.
+    pub severity: IssueSeverity,
+}
+
+impl ChunkBudgets {
+    fn is_chunk_over_budget(&self, size: &OutputAssetSize) -> bool {
+        self.max_chunk_size.is_some_and(|max| size.raw > max)
+            || self.max_chunk_gzip_size.is_some_and(|max| size.gzip > max)
+    }
+
+    fn is_entry_over_budget(&self, size: &OutputAssetSize) -> bool {
+        self.max_entry_size.is_some_and(|max| size.raw > max)
+            || self.max_entry_gzip_size.is_some_and(|max| size.gzip > max)
+    }
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionChunkBudgets(Option<ChunkBudgets>);
+
+#[turbo_tasks::value]
+#[derive(Debug, Default, Clone, Copy)]
+struct OutputAssetSize {
+    raw: u64,
+    gzip: u64,
+}
+
+#[turbo_tasks::function]
+async fn output_asset_size(asset: Vc<Box<dyn OutputAsset>>) -> Result<Vc<OutputAssetSize>> {
+    let FileContent::Content(file) = &*asset.content().file_content().await? else {
+        return Ok(OutputAssetSize::default().cell());
+    };
+    let bytes = file.content().to_bytes();
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&bytes)?;
+    let gzip = encoder.finish()?;
+    Ok(OutputAssetSize {
+        raw: bytes.len() as u64,
+        gzip: gzip.len() as u64,
+    }
+    .cell())
+}
+
+/// Checks the output assets of a chunk group, e.g. all chunks loaded by an entry, against the
+/// budgets of the chunking context and emits a [ChunkBudgetIssue] when the group or one of its
+/// chunks exceeds them.
+///
+/// This runs as a separate pass over the generated output assets, so that computing chunk groups
+/// doesn't depend on the content of their chunks. Only ECMAScript chunks count toward the budgets,
+/// other assets like stylesheets, source maps or static files are ignored.
+#[turbo_tasks::function]
+pub async fn check_chunk_group_budgets(
+    chunking_context: Vc<Box<dyn ChunkingContext>>,
+    ident: Vc<AssetIdent>,
+    assets: Vc<OutputAssets>,
+) -> Result<()> {
+    let Some(budgets) = *chunking_context.chunk_budgets().await? else {
+        return Ok(());
+    };
+
+    let chunks = assets
+        .await?
+        .iter()
+        .filter_map(|&asset| {
+            ResolvedVc::try_sidecast::<Box<dyn OutputChunk>>(asset).map(|chunk| (chunk, asset))
+        })
+        .map(async |(chunk, asset)| {
+            let is_script = matches!(
+                asset.path().await?.extension_ref(),
+                Some("js" | "mjs" | "cjs")
+            );
+            Ok(is_script.then_some((chunk, asset)))
+        })
+        .try_flat_join()
+        .await?;
+
+    let sizes = chunks
+        .iter()
+        .map(|&(_, asset)| output_asset_size(*asset))
+        .try_join()
+        .await?;
+    let entry_size = sizes
+        .iter()
+        .fold(OutputAssetSize::default(), |acc, size| OutputAssetSize {
+            raw: acc.raw + size.raw,
+            gzip: acc.gzip + size.gzip,
+        });
+    let entry_over_budget = budgets.is_entry_over_budget(&entry_size);
+
+    let mut offending_chunks = Vec::new();
+    for (&(chunk, asset), size) in chunks.iter().zip(sizes.iter()) {
+        if budgets.is_chunk_over_budget(size) {
+            offending_chunks.push((chunk, asset.path().await?.path.clone(), **size));
+        }
+    }
+
+    if !entry_over_budget && offending_chunks.is_empty() {
+        return Ok(());
+    }
+
+    // When the whole group is over budget all chunks contribute to it, otherwise only the
+    // offending chunks do.
+    let contributing_chunks = if entry_over_budget {
+        chunks.iter().map(|&(chunk, _)| chunk).collect::<Vec<_>>()
+    } else {
+        offending_chunks
+            .iter()
+            .map(|&(chunk, _, _)| chunk)
+            .collect::<Vec<_>>()
+    };
+    let mut largest_modules = contributing_chunks
+        .iter()
+        .map(|chunk| async move {
+            chunk
+                .contained_chunk_items()
+                .await?
+                .iter()
+                .map(|&chunk_item| async move {
+                    let size = *chunk_item
+                        .ty()
+                        .chunk_item_size(chunking_context, *chunk_item, None)
+                        .await?;
+                    let ident = chunk_item.asset_ident().to_string().owned().await?;
+                    Ok((ident, size))
+                })
+                .try_join()
+                .await
+        })
+        .try_join()
+        .await?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    largest_modules.sort_by(|(a_ident, a_size), (b_ident, b_size)| {
+        b_size.cmp(a_size).then_with(|| a_ident.cmp(b_ident))
+    });
+    largest_modules.truncate(LARGEST_MODULES_COUNT);
+
+    let mut lines = Vec::new();
+    if entry_over_budget {
+        lines.push(StyledString::Text(
+            format!(
+                "The chunk group has a total size of {} ({} gzip), the budget is {}.",
+                format_size(entry_size.raw),
+                format_size(entry_size.gzip),
+                format_budget(budgets.max_entry_size, budgets.max_entry_gzip_size),
+            )
+            .into(),
+        ));
+    }
+    if !offending_chunks.is_empty() {
+        lines.push(StyledString::Strong(
+            format!(
+                "Chunks exceeding the budget of {}:",
+                format_budget(budgets.max_chunk_size, budgets.max_chunk_gzip_size)
+            )
+            .into(),
+        ));
+        for (_, path, size) in &offending_chunks {
+            lines.push(StyledString::Line(vec![
+                StyledString::Code(path.clone()),
+                StyledString::Text(
+                    format!(
+                        " {} ({} gzip)",
+                        format_size(size.raw),
+                        format_size(size.gzip)
+                    )
+                    .into(),
+                ),
+            ]));
+        }
+    }
+    if !largest_modules.is_empty() {
+        lines.push(StyledString::Strong(rcstr!("Largest modules:")));
+        for (ident, size) in largest_modules {
+            lines.push(StyledString::Line(vec![
+                StyledString::Code(ident),
+                StyledString::Text(format!(" {}", format_size(size as u64)).into()),
+            ]));
+        }
+    }
+
+    ChunkBudgetIssue {
+        severity: budgets.severity,
+        path: ident.path().owned().await?,
+        chunk_group: ident.to_string().owned().await?,
+        description: StyledString::Stack(lines).resolved_cell(),
+    }
+    .resolved_cell()
+    .emit();
+
+    Ok(())
+}
+
+fn format_size(size: u64) -> String {
+    if size >= 1024 * 1024 {
+        format!("{:.2} MiB", size as f64 / (1024.0 * 1024.0))
+    } else if size >= 1024 {
+        format!("{:.2} KiB", size as f64 / 1024.0)
+    } else {
+        format!("{size} B")
+    }
+}
+
+fn format_budget(raw: Option<u64>, gzip: Option<u64>) -> String {
+    match (raw, gzip) {
+        (Some(raw), Some(gzip)) => format!("{} ({} gzip)", format_size(raw), format_size(gzip)),
+        (Some(raw), None) => format_size(raw),
+        (None, Some(gzip)) => format!("{} gzip", format_size(gzip)),
+        (None, None) => "unlimited".to_string(),
+    }
+}
+
+#[turbo_tasks::value(shared)]
+pub struct ChunkBudgetIssue {
+    pub severity: IssueSeverity,
+    pub path: FileSystemPath,
+    pub chunk_group: RcStr,
+    pub description: ResolvedVc<StyledString>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for ChunkBudgetIssue {
+    fn severity(&self) -> IssueSeverity {
+        self.severity
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Line(vec![
+            StyledString::Text(rcstr!("Chunk group ")),
+            StyledString::Code(self.chunk_group.clone()),
+            StyledString::Text(rcstr!(" exceeds its size budget")),
+        ])
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::CodeGen.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path.clone().cell()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        Vc::cell(Some(self.description))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.00 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.00 MiB");
+    }
+
+    #[test]
+    fn test_is_over_budget() {
+        let budgets = ChunkBudgets {
+            max_chunk_size: Some(100),
+            max_chunk_gzip_size: None,
+            max_entry_size: None,
+            max_entry_gzip_size: Some(50),
+            severity: IssueSeverity::Error,
+        };
+        let size = OutputAssetSize { raw: 80, gzip: 60 };
+        assert!(!budgets.is_chunk_over_budget(&size));
+        assert!(budgets.is_entry_over_budget(&size));
+        let size = OutputAssetSize { raw: 120, gzip: 40 };
+        assert!(budgets.is_chunk_over_budget(&size));
+        assert!(!budgets.is_entry_over_budget(&size));
+    }
+}
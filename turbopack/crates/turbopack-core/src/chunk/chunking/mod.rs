@@ -30,7 +30,7 @@ use crate::{
 };
 
 mod dev;
-mod production;
+pub(crate) mod production;
 mod style_production;
 
 #[turbo_tasks::value]
@@ -347,6 +347,15 @@ pub async fn make_chunks(
                     )
                     .await?;
                 } else {
+                    // Cache groups match individual chunk items, so batches need to be expanded
+                    let expanded_chunk_items;
+                    let chunk_items = if chunking_config.cache_groups.is_empty() {
+                        chunk_items
+                    } else {
+                        expanded_chunk_items =
+                            expand_batches(chunk_items, ty, chunking_context).await?;
+                        expanded_chunk_items.iter().collect()
+                    };
                     make_production_chunks(
                         chunk_items,
                         batch_groups.into_iter().collect(),
@@ -405,10 +414,22 @@ struct SplitContext<'a> {
 }
 
 /// Creates a chunk with the given `chunk_items. `key` should be unique.
+async fn make_chunk(
+    chunk_items: Vec<&ChunkItemOrBatchWithInfo>,
+    batch_groups: Vec<ResolvedVc<ChunkItemBatchGroup>>,
+    key: &mut String,
+    split_context: &mut SplitContext<'_>,
+) -> Result<()> {
+    make_named_chunk(chunk_items, batch_groups, None, key, split_context).await
+}
+
+/// Creates a chunk with the given `chunk_items`, named after a chunk cache group. `key` should be
+/// unique.
 #[tracing::instrument(level = Level::TRACE, skip_all, fields(key = display(key)))]
-async fn make_chunk<'l>(
+async fn make_named_chunk<'l>(
     chunk_items: Vec<&'l ChunkItemOrBatchWithInfo>,
     batch_groups: Vec<ResolvedVc<ChunkItemBatchGroup>>,
+    name: Option<RcStr>,
     key: &mut String,
     split_context: &mut SplitContext<'_>,
 ) -> Result<()> {
@@ -431,6 +452,7 @@ async fn make_chunk<'l>(
                 split_context.referenced_output_assets,
                 split_context.empty_referenced_output_assets,
             ),
+            name,
         ),
     );
     Ok(())
@@ -1,21 +1,31 @@
-use std::{borrow::Cow, collections::BinaryHeap, hash::BuildHasherDefault, mem::take};
+use std::{
+    borrow::Cow,
+    collections::BinaryHeap,
+    hash::{BuildHasherDefault, Hash},
+    mem::take,
+};
 
 use anyhow::{Context, Result};
 use rustc_hash::FxHasher;
 use smallvec::SmallVec;
 use tracing::{Instrument, field::Empty};
 use turbo_prehash::BuildHasherExt;
-use turbo_tasks::{FxIndexMap, FxIndexSet, ResolvedVc, TryJoinIterExt, Vc};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{FxIndexMap, FxIndexSet, ResolvedVc, TryJoinIterExt, Vc, primitives::Regex};
 
 use crate::{
     chunk::{
-        ChunkItemBatchGroup, ChunkItemWithAsyncModuleInfo, ChunkingConfig,
-        chunking::{ChunkItemOrBatchWithInfo, SplitContext, make_chunk},
+        ChunkCacheGroup, ChunkItemBatchGroup, ChunkItemWithAsyncModuleInfo, ChunkingConfig,
+        chunking::{ChunkItemOrBatchWithInfo, SplitContext, make_named_chunk},
+    },
+    module_graph::{
+        ModuleGraph,
+        chunk_group_info::{ChunkGroupInfo, RoaringBitmapWrapper},
+        merged_modules::MergedModuleInfo,
     },
-    module_graph::{ModuleGraph, chunk_group_info::RoaringBitmapWrapper},
 };
 
-pub async fn make_production_chunks(
+pub(super) async fn make_production_chunks(
     chunk_items: Vec<&ChunkItemOrBatchWithInfo>,
     batch_groups: Vec<ResolvedVc<ChunkItemBatchGroup>>,
     module_graph: Vc<ModuleGraph>,
@@ -34,6 +44,32 @@ pub async fn make_production_chunks(
         let chunk_group_info = module_graph.chunk_group_info().await?;
         let merged_modules = module_graph.merged_modules().await?;
 
+        // Chunk items of cache groups are split off first, their chunks are created last so that
+        // they never reference the output assets of the chunk group
+        let (chunk_items, cache_group_chunk_items) = if chunking_config.cache_groups.is_empty() {
+            (chunk_items, FxIndexMap::default())
+        } else {
+            let tests = chunk_cache_group_tests(chunking_config.cache_groups.clone()).await?;
+            let (chunk_group_info, merged_modules) = (&*chunk_group_info, &*merged_modules);
+            split_cache_groups(
+                chunk_items,
+                &tests,
+                |chunk_item| match chunk_item {
+                    ChunkItemOrBatchWithInfo::ChunkItem { asset_ident, .. } => {
+                        Some(asset_ident.as_str())
+                    }
+                    // Batches need to be expanded by the caller to match them against cache groups
+                    ChunkItemOrBatchWithInfo::Batch { .. } => None,
+                },
+                move |chunk_item| {
+                    let ChunkItemOrBatchWithInfo::ChunkItem { chunk_item, .. } = chunk_item else {
+                        unreachable!("only chunk items match cache groups");
+                    };
+                    module_chunk_groups(chunk_item, chunk_group_info, merged_modules)
+                },
+            )?
+        };
+
         #[derive(Default)]
         struct GroupedChunkItems<'l> {
             chunk_items: Vec<&'l ChunkItemOrBatchWithInfo>,
@@ -63,32 +99,9 @@ pub async fn make_production_chunks(
         // Put chunk items into `grouped_chunk_items` based on their chunk groups
         for (i, chunk_item) in chunk_items.into_iter().enumerate() {
             let chunk_groups = match chunk_item {
-                &ChunkItemOrBatchWithInfo::ChunkItem {
-                    chunk_item:
-                        ChunkItemWithAsyncModuleInfo {
-                            module: Some(module),
-                            ..
-                        },
-                    ..
-                } => Some(
-                    chunk_group_info
-                        .module_chunk_groups
-                        .get(&ResolvedVc::upcast(module))
-                        .or_else(|| {
-                            // Merged modules don't have a chunk group in chunk_group_info, so
-                            // lookup using the original module.
-                            merged_modules
-                                .get_original_module(ResolvedVc::upcast(module))
-                                .and_then(|module| {
-                                    chunk_group_info.module_chunk_groups.get(&module)
-                                })
-                        })
-                        .context("every module should have a chunk group")?,
-                ),
-                &ChunkItemOrBatchWithInfo::ChunkItem {
-                    chunk_item: ChunkItemWithAsyncModuleInfo { module: None, .. },
-                    ..
-                } => None,
+                ChunkItemOrBatchWithInfo::ChunkItem { chunk_item, .. } => {
+                    module_chunk_groups(chunk_item, &chunk_group_info, &merged_modules)?
+                }
                 ChunkItemOrBatchWithInfo::Batch { .. } => {
                     batch_read_refs[i].as_ref().unwrap().chunk_groups.as_ref()
                 }
@@ -111,16 +124,24 @@ pub async fn make_production_chunks(
             min_chunk_size,
             max_chunk_count_per_group,
             max_merge_chunk_size,
+            max_chunk_size,
             ..
         } = chunking_config;
 
         if min_chunk_size == 0 && max_chunk_count_per_group == 0 {
             span.record("chunks", grouped_chunk_items.len());
             for group in grouped_chunk_items.into_values() {
-                make_chunk(
+                let size = group
+                    .chunk_items
+                    .iter()
+                    .map(|chunk_item| chunk_item.size())
+                    .sum::<usize>();
+                make_chunks_with_max_size(
                     group.chunk_items,
                     group.batch_group.into_iter().collect(),
-                    &mut String::new(),
+                    size,
+                    max_chunk_size,
+                    None,
                     &mut split_context,
                 )
                 .await?;
@@ -539,10 +560,12 @@ pub async fn make_production_chunks(
             } in heap.into_iter()
             {
                 total_size += size;
-                make_chunk(
+                make_chunks_with_max_size(
                     chunk_items,
                     batch_groups.into_vec(),
-                    &mut String::new(),
+                    size,
+                    max_chunk_size,
+                    None,
                     &mut split_context,
                 )
                 .await?;
@@ -550,12 +573,167 @@ pub async fn make_production_chunks(
             span.record("total_size", total_size);
         }
 
+        for ((cache_group, _), chunk_items) in cache_group_chunk_items {
+            let size = chunk_items
+                .iter()
+                .map(|chunk_item| chunk_item.size())
+                .sum::<usize>();
+            make_chunks_with_max_size(
+                chunk_items,
+                Vec::new(),
+                size,
+                max_chunk_size,
+                Some(chunking_config.cache_groups[cache_group].name.clone()),
+                &mut split_context,
+            )
+            .await?;
+        }
+
         Ok(())
     }
     .instrument(span_outer)
     .await
 }
 
+/// The chunk groups that the module of `chunk_item` is part of, or `None` for chunk items without
+/// a module.
+fn module_chunk_groups<'a>(
+    chunk_item: &ChunkItemWithAsyncModuleInfo,
+    chunk_group_info: &'a ChunkGroupInfo,
+    merged_modules: &'a MergedModuleInfo,
+) -> Result<Option<&'a RoaringBitmapWrapper>> {
+    let Some(module) = chunk_item.module else {
+        return Ok(None);
+    };
+    chunk_group_info
+        .module_chunk_groups
+        .get(&ResolvedVc::upcast(module))
+        .or_else(|| {
+            // Merged modules don't have a chunk group in chunk_group_info, so lookup using the
+            // original module.
+            merged_modules
+                .get_original_module(ResolvedVc::upcast(module))
+                .and_then(|module| chunk_group_info.module_chunk_groups.get(&module))
+        })
+        .context("every module should have a chunk group")
+        .map(Some)
+}
+
+/// The compiled `test` regexes of chunk cache groups, in the same order.
+#[turbo_tasks::value(transparent, eq = "manual", cell = "new")]
+struct ChunkCacheGroupTests(Vec<Regex>);
+
+#[turbo_tasks::function]
+fn chunk_cache_group_tests(cache_groups: Vec<ChunkCacheGroup>) -> Result<Vc<ChunkCacheGroupTests>> {
+    let tests = cache_groups
+        .iter()
+        .map(|cache_group| {
+            let regex = regex::Regex::new(&cache_group.test).with_context(|| {
+                format!(
+                    "invalid test regex {:?} for chunk cache group {}",
+                    cache_group.test, cache_group.name
+                )
+            })?;
+            Ok(Regex(regex))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Vc::cell(tests))
+}
+
+/// The items of every cache group, by the index of the cache group and their key.
+type CacheGroupItems<K, T> = FxIndexMap<(usize, K), Vec<T>>;
+
+/// Splits off the items whose `ident` matches one of the cache group `tests`, the first matching
+/// cache group wins.
+///
+/// The items of a cache group are further grouped by their `key`, the chunk groups they are part
+/// of. A chunk group therefore gets the same cache group chunk as every other chunk group sharing
+/// these modules, which is emitted only once and can be cached across them.
+///
+/// Returns the remaining items and the items of every cache group and key, in the order of the
+/// cache groups.
+fn split_cache_groups<'l, T: Copy, K: Hash + Eq>(
+    items: Vec<T>,
+    tests: &[Regex],
+    ident: impl Fn(T) -> Option<&'l str>,
+    key: impl Fn(T) -> Result<K>,
+) -> Result<(Vec<T>, CacheGroupItems<K, T>)> {
+    let mut cache_group_items = FxIndexMap::<_, Vec<_>>::default();
+    let mut remaining_items = Vec::new();
+    for item in items {
+        let cache_group =
+            ident(item).and_then(|ident| tests.iter().position(|test| test.is_match(ident)));
+        if let Some(i) = cache_group {
+            cache_group_items
+                .entry((i, key(item)?))
+                .or_default()
+                .push(item);
+        } else {
+            remaining_items.push(item);
+        }
+    }
+    cache_group_items.sort_by(|(a, _), _, (b, _), _| a.cmp(b));
+    Ok((remaining_items, cache_group_items))
+}
+
+/// Creates a chunk with the given `chunk_items`, or multiple chunks when their total `size` exceeds
+/// `max_chunk_size`. A `max_chunk_size` of 0 disables splitting. The chunks of a cache group are
+/// named after it.
+async fn make_chunks_with_max_size(
+    chunk_items: Vec<&ChunkItemOrBatchWithInfo>,
+    batch_groups: Vec<ResolvedVc<ChunkItemBatchGroup>>,
+    size: usize,
+    max_chunk_size: usize,
+    name: Option<RcStr>,
+    split_context: &mut SplitContext<'_>,
+) -> Result<()> {
+    if max_chunk_size == 0 || size <= max_chunk_size || chunk_items.len() <= 1 {
+        return make_named_chunk(
+            chunk_items,
+            batch_groups,
+            name,
+            &mut String::new(),
+            split_context,
+        )
+        .await;
+    }
+
+    for chunk_items in
+        split_by_max_size(chunk_items, |chunk_item| chunk_item.size(), max_chunk_size)
+    {
+        make_named_chunk(
+            chunk_items,
+            batch_groups.clone(),
+            name.clone(),
+            &mut String::new(),
+            split_context,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Splits `items` into consecutive runs whose total size doesn't exceed `max_size`. An item bigger
+/// than `max_size` forms a run on its own.
+fn split_by_max_size<T>(items: Vec<T>, size: impl Fn(&T) -> usize, max_size: usize) -> Vec<Vec<T>> {
+    let mut runs = Vec::new();
+    let mut current_run = Vec::new();
+    let mut current_size = 0;
+    for item in items {
+        let item_size = size(&item);
+        if !current_run.is_empty() && current_size + item_size > max_size {
+            runs.push(take(&mut current_run));
+            current_size = 0;
+        }
+        current_size += item_size;
+        current_run.push(item);
+    }
+    if !current_run.is_empty() {
+        runs.push(current_run);
+    }
+    runs
+}
+
 struct ChunkCandidate<'l> {
     size: usize,
     chunk_items: Vec<&'l ChunkItemOrBatchWithInfo>,
@@ -643,3 +821,61 @@ fn merge_chunk_groups<'l>(
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use turbo_tasks::primitives::Regex;
+
+    use super::{split_by_max_size, split_cache_groups};
+
+    #[test]
+    fn split_by_max_size_keeps_runs_below_max_size() {
+        let runs = split_by_max_size(vec![40, 30, 20, 50, 10], |size| *size, 100);
+        assert_eq!(runs, vec![vec![40, 30, 20], vec![50, 10]]);
+    }
+
+    #[test]
+    fn split_by_max_size_puts_big_items_into_their_own_run() {
+        let runs = split_by_max_size(vec![10, 150, 20, 30], |size| *size, 100);
+        assert_eq!(runs, vec![vec![10], vec![150], vec![20, 30]]);
+    }
+
+    #[test]
+    fn split_cache_groups_by_cache_group_and_key() -> Result<()> {
+        let tests = ["/node_modules/react", "/node_modules/"]
+            .map(|test| Regex(regex::Regex::new(test).unwrap()));
+        // (ident, chunk groups)
+        let items = vec![
+            ("[project]/node_modules/lodash/index.js", 1),
+            ("[project]/src/index.js", 1),
+            ("[project]/node_modules/react/index.js", 1),
+            ("[project]/node_modules/react-dom/index.js", 2),
+            ("[project]/node_modules/react/jsx-runtime.js", 1),
+        ];
+        let (remaining, cache_groups) =
+            split_cache_groups(items, &tests, |(ident, _)| Some(ident), |(_, key)| Ok(key))?;
+
+        assert_eq!(remaining, vec![("[project]/src/index.js", 1)]);
+        // The first matching cache group wins, and modules of different chunk groups are kept
+        // apart, so that every chunk has the same content in all chunk groups
+        assert_eq!(
+            cache_groups.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    (0, 1),
+                    vec![
+                        ("[project]/node_modules/react/index.js", 1),
+                        ("[project]/node_modules/react/jsx-runtime.js", 1),
+                    ]
+                ),
+                (
+                    (0, 2),
+                    vec![("[project]/node_modules/react-dom/index.js", 2)]
+                ),
+                ((1, 1), vec![("[project]/node_modules/lodash/index.js", 1)]),
+            ]
+        );
+        Ok(())
+    }
+}
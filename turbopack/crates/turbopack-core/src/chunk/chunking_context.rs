@@ -6,7 +6,10 @@ use turbo_tasks::{NonLocalValue, ResolvedVc, TaskInput, Upcast, Vc, trace::Trace
 use turbo_tasks_fs::FileSystemPath;
 use turbo_tasks_hash::DeterministicHash;
 
-use super::{ChunkableModule, EvaluatableAssets, availability_info::AvailabilityInfo};
+use super::{
+    ChunkableModule, EvaluatableAssets, availability_info::AvailabilityInfo,
+    budget::OptionChunkBudgets,
+};
 use crate::{
     asset::Asset,
     chunk::{ChunkItem, ChunkType, ModuleId},
@@ -40,20 +43,73 @@ pub enum MangleType {
     Deterministic,
 }
 
+/// Options of the minifier, following the terser options of the same names.
+#[derive(
+    Debug,
+    TaskInput,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    DeterministicHash,
+    NonLocalValue,
+)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MinifyOptions {
+    /// How names are mangled, or `None` to keep them.
+    pub mangle: Option<MangleType>,
+    /// Names that are never mangled, in addition to globals the runtime relies on.
+    pub reserved: Vec<RcStr>,
+    /// Whether names in the top level scope are mangled too, or `None` to let the minifier decide.
+    pub top_level: Option<bool>,
+    /// The number of compress passes, a tradeoff between build performance and compression size.
+    pub passes: usize,
+    /// Whether calls to `console.*` are removed.
+    pub drop_console: bool,
+    /// Whether `debugger` statements are removed.
+    pub drop_debugger: bool,
+    /// Functions whose calls can be removed when their result is unused, e.g. `console.info` or
+    /// `assert`.
+    pub pure_funcs: Vec<RcStr>,
+    /// Whether class names are kept, even when names are mangled.
+    pub keep_classnames: bool,
+    /// Whether function names are kept, even when names are mangled.
+    pub keep_fnames: bool,
+    /// The ECMAScript version the minifier may use in its output, e.g. `5` or `2020`. Defaults
+    /// to ES5.
+    pub ecma: Option<u32>,
+}
+
+impl Default for MinifyOptions {
+    fn default() -> Self {
+        Self {
+            mangle: Some(MangleType::OptimalSize),
+            reserved: vec![],
+            top_level: None,
+            passes: 2,
+            drop_console: false,
+            drop_debugger: false,
+            pure_funcs: vec![],
+            keep_classnames: false,
+            keep_fnames: false,
+            ecma: None,
+        }
+    }
+}
+
 #[turbo_tasks::value(shared)]
-#[derive(Debug, TaskInput, Clone, Copy, Hash, DeterministicHash)]
+#[derive(Debug, TaskInput, Clone, Hash, DeterministicHash)]
 pub enum MinifyType {
-    // TODO instead of adding a new property here,
-    // refactor that to Minify(MinifyOptions) to allow defaults on MinifyOptions
-    Minify { mangle: Option<MangleType> },
+    Minify(MinifyOptions),
     NoMinify,
 }
 
 impl Default for MinifyType {
     fn default() -> Self {
-        Self::Minify {
-            mangle: Some(MangleType::OptimalSize),
-        }
+        Self::Minify(MinifyOptions::default())
     }
 }
 
@@ -63,10 +119,34 @@ pub enum SourceMapsType {
     /// Extracts source maps from input files and writes source maps for output files.
     #[default]
     Full,
+    /// Like `Full`, but output files don't reference their source maps with a `sourceMappingURL`
+    /// comment, so that source maps can e.g. be uploaded to an error tracking service without
+    /// being exposed to browsers.
+    Hidden,
+    /// Like `Full`, but source maps for output files don't contain the `sourcesContent`, so that
+    /// stack traces can be mapped without exposing the source code.
+    NoSources,
     /// Ignores the existence of source maps and does not write source maps for output files.
     None,
 }
 
+impl SourceMapsType {
+    /// Whether source maps are extracted from input files and written for output files.
+    pub fn is_enabled(self) -> bool {
+        !matches!(self, SourceMapsType::None)
+    }
+
+    /// Whether output files reference their source maps with a `sourceMappingURL` comment.
+    pub fn has_source_mapping_url(self) -> bool {
+        matches!(self, SourceMapsType::Full | SourceMapsType::NoSources)
+    }
+
+    /// Whether the source maps of output files contain the `sourcesContent`.
+    pub fn has_sources_content(self) -> bool {
+        !matches!(self, SourceMapsType::NoSources)
+    }
+}
+
 #[derive(
     Debug,
     TaskInput,
@@ -124,10 +204,34 @@ pub struct ChunkingConfig {
     /// This makes sure that code in big chunks is not duplicated in multiple chunks.
     pub max_merge_chunk_size: usize,
 
+    /// Splits chunks bigger than this size into multiple chunks. A single chunk item bigger than
+    /// this size still forms a chunk on its own. This takes precedence over
+    /// `max_chunk_count_per_group`.
+    pub max_chunk_size: usize,
+
+    /// Modules matching one of these cache groups are kept out of the regular chunks and put into
+    /// dedicated chunks per cache group. The first matching cache group wins. The modules of a
+    /// cache group are split by the set of chunk groups they are part of, so every such chunk has
+    /// the same content, and is the same output file, in all chunk groups that load it.
+    pub cache_groups: Vec<ChunkCacheGroup>,
+
     #[allow(dead_code)]
     pub placeholder_for_future_extensions: (),
 }
 
+/// A named group of modules that is always placed into a dedicated chunk, e.g. a `vendor-react`
+/// chunk for `react` and `react-dom`.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TraceRawVcs, NonLocalValue, TaskInput,
+)]
+pub struct ChunkCacheGroup {
+    /// The name of the cache group.
+    pub name: RcStr,
+    /// A regular expression that is matched against the ident of a module, e.g.
+    /// `[project]/node_modules/react/index.js [client] (ecmascript)`.
+    pub test: RcStr,
+}
+
 #[turbo_tasks::value(transparent)]
 pub struct ChunkingConfigs(FxHashMap<ResolvedVc<Box<dyn ChunkType>>, ChunkingConfig>);
 
@@ -222,6 +326,14 @@ pub trait ChunkingContext {
         Vc::cell(false)
     }
 
+    /// Whether to emit a debug ID for every chunk. It's added as a `//# debugId=` comment to the
+    /// chunk and as `debugId` to its source map, so that stack frames can be matched to the source
+    /// map without relying on URLs.
+    #[turbo_tasks::function]
+    fn is_debug_ids_enabled(self: Vc<Self>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+
     /// Whether to include information about the content of the chunk into the runtime, to allow
     /// more incremental loading of individual chunk items.
     #[turbo_tasks::function]
@@ -229,11 +341,22 @@ pub trait ChunkingContext {
         Vc::cell(false)
     }
 
+    #[turbo_tasks::function]
+    fn source_maps_type(self: Vc<Self>) -> Vc<SourceMapsType> {
+        SourceMapsType::Full.cell()
+    }
+
     #[turbo_tasks::function]
     fn minify_type(self: Vc<Self>) -> Vc<MinifyType> {
         MinifyType::NoMinify.cell()
     }
 
+    /// Size budgets the output of each chunk group is checked against.
+    #[turbo_tasks::function]
+    fn chunk_budgets(self: Vc<Self>) -> Vc<OptionChunkBudgets> {
+        Vc::cell(None)
+    }
+
     #[turbo_tasks::function]
     fn async_loader_chunk_item(
         &self,
@@ -1,5 +1,6 @@
 pub mod availability_info;
 pub mod available_modules;
+pub mod budget;
 pub mod chunk_group;
 pub(crate) mod chunk_item_batch;
 pub mod chunking;
@@ -28,8 +29,9 @@ pub use self::{
         ChunkItemOrBatchWithAsyncModuleInfo, batch_info,
     },
     chunking_context::{
-        ChunkGroupResult, ChunkGroupType, ChunkingConfig, ChunkingConfigs, ChunkingContext,
-        ChunkingContextExt, EntryChunkGroupResult, MangleType, MinifyType, SourceMapsType,
+        ChunkCacheGroup, ChunkGroupResult, ChunkGroupType, ChunkingConfig, ChunkingConfigs,
+        ChunkingContext, ChunkingContextExt, EntryChunkGroupResult, MangleType, MinifyOptions,
+        MinifyType, SourceMapsType,
     },
     data::{ChunkData, ChunkDataOption, ChunksData},
     evaluate::{EvaluatableAsset, EvaluatableAssetExt, EvaluatableAssets},
@@ -255,6 +257,13 @@ impl OutputChunkRuntimeInfo {
 pub trait OutputChunk: Asset {
     #[turbo_tasks::function]
     fn runtime_info(self: Vc<Self>) -> Vc<OutputChunkRuntimeInfo>;
+
+    /// The chunk items the content of this output chunk was generated from, e.g. to attribute its
+    /// size to modules.
+    #[turbo_tasks::function]
+    fn contained_chunk_items(self: Vc<Self>) -> Vc<ChunkItems> {
+        ChunkItems(vec![]).cell()
+    }
 }
 
 /// Specifies how a chunk interacts with other chunks when building a chunk
@@ -474,7 +483,8 @@ pub trait ChunkType: ValueToString {
     #[turbo_tasks::function]
     fn is_style(self: Vc<Self>) -> Vc<bool>;
 
-    /// Create a new chunk for the given chunk items
+    /// Create a new chunk for the given chunk items. `name` is the name of the [ChunkCacheGroup]
+    /// the chunk is created for, if any, which should be part of the chunk's ident and file name.
     #[turbo_tasks::function]
     fn chunk(
         &self,
@@ -482,6 +492,7 @@ pub trait ChunkType: ValueToString {
         chunk_items: Vec<ChunkItemOrBatchWithAsyncModuleInfo>,
         batch_groups: Vec<ResolvedVc<ChunkItemBatchGroup>>,
         referenced_output_assets: Vc<OutputAssets>,
+        name: Option<RcStr>,
     ) -> Vc<Box<dyn Chunk>>;
 
     #[turbo_tasks::function]
@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+
 use anyhow::{Result, bail};
 use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::RcStr;
 use turbo_tasks::{ResolvedVc, ValueToString, Vc};
 use turbo_tasks_hash::hash_xxh3_hash64;
 
@@ -36,6 +40,18 @@ impl ModuleIdStrategy for DevModuleIdStrategy {
     }
 }
 
+/// The content of a module id records file, which persists the numeric module ids assigned by the
+/// [GlobalModuleIdStrategy] between builds (similar to webpack's `recordsPath`).
+///
+/// Modules that are listed in the records keep their id, so adding or removing modules doesn't
+/// change the ids (and thereby the content hashes of chunks) of unrelated modules.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleIdRecords {
+    /// Maps the stringified [AssetIdent] of a module to its numeric id.
+    pub module_ids: BTreeMap<RcStr, u64>,
+}
+
 #[turbo_tasks::value(shared)]
 pub struct GlobalModuleIdStrategy {
     pub module_id_map: FxHashMap<ResolvedVc<AssetIdent>, u64>,
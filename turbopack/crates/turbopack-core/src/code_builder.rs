@@ -5,13 +5,24 @@ use std::{
 };
 
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use tracing::{Level, instrument};
-use turbo_tasks::Vc;
-use turbo_tasks_fs::rope::{Rope, RopeBuilder};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{NonLocalValue, TaskInput, Vc, trace::TraceRawVcs};
+use turbo_tasks_fs::{
+    File,
+    rope::{Rope, RopeBuilder},
+};
 use turbo_tasks_hash::hash_xxh3_hash64;
 
 use crate::{
-    source_map::{GenerateSourceMap, OptionStringifiedSourceMap, SourceMap},
+    asset::AssetContent,
+    chunk::ChunkingContext,
+    output::OutputAsset,
+    source_map::{
+        GenerateSourceMap, OptionStringifiedSourceMap, SourceMap,
+        utils::{debug_id_from_hash, finalize_chunk_source_map},
+    },
     source_pos::SourcePos,
 };
 
@@ -42,6 +53,36 @@ impl Code {
     }
 }
 
+/// The syntax of the `debugId` and `sourceMappingURL` comments at the end of a chunk file.
+#[derive(
+    Debug,
+    TaskInput,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    NonLocalValue,
+)]
+pub enum FooterCommentStyle {
+    /// `//# name=value` line comments, each after an empty line.
+    JavaScript,
+    /// `/*# name=value*/` block comments.
+    Css,
+}
+
+impl FooterCommentStyle {
+    fn write(self, rope_builder: &mut RopeBuilder, name: &str, value: &str) -> IoResult<()> {
+        match self {
+            FooterCommentStyle::JavaScript => write!(rope_builder, "\n\n//# {name}={value}"),
+            FooterCommentStyle::Css => write!(rope_builder, "/*# {name}={value}*/"),
+        }
+    }
+}
+
 /// CodeBuilder provides a mutable container to append source code.
 pub struct CodeBuilder {
     code: RopeBuilder,
@@ -200,6 +241,86 @@ impl Code {
         let hash = hash_xxh3_hash64(code.source_code());
         Vc::cell(hash)
     }
+
+    /// Returns a debug ID for this Code, which is derived from the hash of its source code.
+    #[turbo_tasks::function]
+    pub async fn debug_id(self: Vc<Self>) -> Result<Vc<RcStr>> {
+        Ok(Vc::cell(debug_id_from_hash(
+            *self.source_code_hash().await?,
+        )))
+    }
+
+    /// Generates the source map of a chunk consisting of this Code. This fills in the
+    /// `ignoreList` and, depending on the chunking context, stores the debug ID of this Code in
+    /// the source map and strips the `sourcesContent`.
+    #[turbo_tasks::function]
+    pub async fn generate_chunk_source_map(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn ChunkingContext>>,
+    ) -> Result<Vc<OptionStringifiedSourceMap>> {
+        let debug_ids = *chunking_context.is_debug_ids_enabled().await?;
+        let sources_content = chunking_context
+            .source_maps_type()
+            .await?
+            .has_sources_content();
+        let debug_id = if debug_ids {
+            Some(self.debug_id().await?)
+        } else {
+            None
+        };
+        let map = self.await?.generate_source_map_ref()?;
+        Ok(Vc::cell(Some(finalize_chunk_source_map(
+            &map,
+            debug_id.as_ref().map(|debug_id| debug_id.as_str()),
+            sources_content,
+        )?)))
+    }
+
+    /// Returns the content of a chunk file consisting of this Code. Depending on the chunking
+    /// context, it ends with comments with the debug ID of this Code and the URL of the source
+    /// map `source_map`, relative to the chunk file.
+    #[turbo_tasks::function]
+    pub async fn chunk_file_content(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn ChunkingContext>>,
+        source_map: Vc<Box<dyn OutputAsset>>,
+        comment_style: FooterCommentStyle,
+    ) -> Result<Vc<AssetContent>> {
+        let code = self.await?;
+
+        let debug_id = if *chunking_context.is_debug_ids_enabled().await? {
+            Some(self.debug_id().await?)
+        } else {
+            None
+        };
+
+        let source_mapping_url = code.has_source_map()
+            && chunking_context
+                .source_maps_type()
+                .await?
+                .has_source_mapping_url();
+
+        let rope = if source_mapping_url || debug_id.is_some() {
+            let mut rope_builder = RopeBuilder::default();
+            rope_builder.concat(code.source_code());
+            if let Some(debug_id) = debug_id {
+                comment_style.write(&mut rope_builder, "debugId", &debug_id)?;
+            }
+            if source_mapping_url {
+                let source_map_path = source_map.path().await?;
+                comment_style.write(
+                    &mut rope_builder,
+                    "sourceMappingURL",
+                    &urlencoding::encode(source_map_path.file_name()),
+                )?;
+            }
+            rope_builder.build()
+        } else {
+            code.source_code().clone()
+        };
+
+        Ok(AssetContent::file(File::from(rope).into()))
+    }
 }
 
 impl Code {
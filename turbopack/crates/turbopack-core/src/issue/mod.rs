@@ -2,6 +2,7 @@ pub mod analyze;
 pub mod code_gen;
 pub mod module;
 pub mod resolve;
+pub mod rules;
 
 use std::{
     cmp::{Ordering, min},
@@ -21,6 +22,7 @@ use turbo_tasks::{
 use turbo_tasks_fs::{FileContent, FileLine, FileLinesContent, FileSystem, FileSystemPath};
 use turbo_tasks_hash::{DeterministicHash, Xxh3Hash64Hasher};
 
+use self::rules::{AppliedIssueRules, IssueRules, apply_issue_rules};
 use crate::{
     asset::{Asset, AssetContent},
     ident::{AssetIdent, Layer},
@@ -99,6 +101,24 @@ pub enum StyledString {
     Strong(RcStr),
 }
 
+impl StyledString {
+    /// Renders the text without any styling. [StyledString::Code] is wrapped in backticks.
+    pub fn to_unstyled_string(&self) -> String {
+        match self {
+            StyledString::Line(parts) => {
+                parts.iter().map(|part| part.to_unstyled_string()).collect()
+            }
+            StyledString::Stack(parts) => parts
+                .iter()
+                .map(|part| part.to_unstyled_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            StyledString::Text(string) | StyledString::Strong(string) => string.to_string(),
+            StyledString::Code(string) => format!("`{string}`"),
+        }
+    }
+}
+
 #[turbo_tasks::value_trait]
 pub trait Issue {
     /// Severity allows the user to filter out unimportant issues, with Bug
@@ -367,12 +387,13 @@ pub struct Issues(Vec<ResolvedVc<Box<dyn Issue>>>);
 /// A list of issues captured with [`Issue::peek_issues_with_path`] and
 /// [`Issue::take_issues_with_path`].
 #[turbo_tasks::value(shared)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CapturedIssues {
     issues: AutoSet<ResolvedVc<Box<dyn Issue>>>,
     #[cfg(feature = "issue_path")]
     processing_path: ResolvedVc<ItemIssueProcessingPath>,
     tracer: ResolvedVc<DelegatingImportTracer>,
+    rules: Option<ResolvedVc<IssueRules>>,
 }
 
 #[turbo_tasks::value_impl]
@@ -400,9 +421,24 @@ impl CapturedIssues {
         self.issues.iter().copied()
     }
 
+    /// Suppresses issues or overrides their severity according to `rules` when they are turned into
+    /// `PlainIssues`.
+    pub fn with_rules(self, rules: ResolvedVc<IssueRules>) -> Self {
+        Self {
+            rules: Some(rules),
+            ..self
+        }
+    }
+
     // Returns all the issues as formatted `PlainIssues`.
     pub async fn get_plain_issues(&self) -> Result<Vec<ReadRef<PlainIssue>>> {
-        let mut list = self
+        Ok(self.get_plain_issues_with_rule_matches().await?.issues)
+    }
+
+    /// Returns all the issues as formatted `PlainIssues` together with the number of issues each
+    /// of the rules added by [CapturedIssues::with_rules] matched.
+    pub async fn get_plain_issues_with_rule_matches(&self) -> Result<AppliedIssueRules> {
+        let list = self
             .issues
             .iter()
             .map(|issue| async move {
@@ -414,8 +450,15 @@ impl CapturedIssues {
             })
             .try_join()
             .await?;
-        list.sort();
-        Ok(list)
+        let mut applied = match self.rules {
+            Some(rules) => apply_issue_rules(list, &rules.await?)?,
+            None => AppliedIssueRules {
+                issues: list,
+                matches: Vec::new(),
+            },
+        };
+        applied.issues.sort();
+        Ok(applied)
     }
 }
 
@@ -956,7 +999,7 @@ impl PlainSource {
 
 #[turbo_tasks::value(transparent, serialization = "none")]
 #[derive(Clone, Debug, DeterministicHash, PartialOrd, Ord)]
-pub struct PlainIssueProcessingPath(Option<Vec<ReadRef<PlainIssueProcessingPathItem>>>);
+pub struct PlainIssueProcessingPath(pub Option<Vec<ReadRef<PlainIssueProcessingPathItem>>>);
 
 #[turbo_tasks::value(serialization = "none")]
 #[derive(Clone, Debug, DeterministicHash, PartialOrd, Ord)]
@@ -1100,6 +1143,7 @@ where
             tracer: DelegatingImportTracer::resolved_cell(DelegatingImportTracer {
                 delegates: self.peek_collectibles(),
             }),
+            rules: None,
         })
     }
 
@@ -1114,6 +1158,7 @@ where
             tracer: DelegatingImportTracer::resolved_cell(DelegatingImportTracer {
                 delegates: self.take_collectibles(),
             }),
+            rules: None,
         })
     }
 }
@@ -1177,3 +1222,23 @@ fn find_offset(lines: &[FileLine], pos: SourcePos) -> u32 {
     let line = &lines[pos.line as usize];
     line.bytes_offset + pos.column
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_styled_string_to_text() {
+        let styled_string = StyledString::Stack(vec![
+            StyledString::Line(vec![
+                StyledString::Text("Module not found: Can't resolve ".into()),
+                StyledString::Code("./foo".into()),
+            ]),
+            StyledString::Strong("Import trace".into()),
+        ]);
+        assert_eq!(
+            styled_string.to_unstyled_string(),
+            "Module not found: Can't resolve `./foo`\nImport trace"
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use std::fmt::{Display, Formatter};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{NonLocalValue, ReadRef, TaskInput, trace::TraceRawVcs};
+use turbo_tasks_fs::glob::Glob;
+
+use super::{IssueSeverity, PlainIssue};
+
+/// What happens to an issue matched by an [IssueRule].
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    TaskInput,
+    TraceRawVcs,
+    NonLocalValue,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueRuleAction {
+    /// The issue is not reported at all.
+    Suppress,
+    /// The issue is reported with a different severity.
+    Severity(IssueSeverity),
+}
+
+/// A declarative rule that suppresses issues or overrides their severity. An issue matches the
+/// rule when it matches all conditions that are set, so a rule without any condition matches every
+/// issue.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, TaskInput, TraceRawVcs, NonLocalValue,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueRule {
+    /// A substring of the title of the issue, without any styling.
+    #[serde(default)]
+    pub title: Option<RcStr>,
+    /// The stage of the issue, e.g. `resolve` or `parse`.
+    #[serde(default)]
+    pub stage: Option<RcStr>,
+    /// A glob matched against the path of the file the issue is reported for. The path is
+    /// relative to the root of its filesystem, e.g. `node_modules/**/*.js`.
+    #[serde(default)]
+    pub path: Option<RcStr>,
+    /// The name of the package in `node_modules` the file of the issue belongs to.
+    #[serde(default)]
+    pub package: Option<RcStr>,
+    pub action: IssueRuleAction,
+}
+
+impl Display for IssueRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let conditions = [
+            ("title", &self.title),
+            ("stage", &self.stage),
+            ("path", &self.path),
+            ("package", &self.package),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.as_ref().map(|value| format!("{name} \"{value}\"")))
+        .collect::<Vec<_>>();
+        if conditions.is_empty() {
+            write!(f, "all issues")?;
+        } else {
+            write!(f, "{}", conditions.join(", "))?;
+        }
+        match self.action {
+            IssueRuleAction::Suppress => write!(f, " => suppress"),
+            IssueRuleAction::Severity(severity) => write!(f, " => {}", severity.as_str()),
+        }
+    }
+}
+
+#[turbo_tasks::value(transparent)]
+#[derive(Default)]
+pub struct IssueRules(Vec<IssueRule>);
+
+/// Strips the `[fs-name]/` prefix of a [PlainIssue::file_path].
+fn strip_filesystem_name(file_path: &str) -> &str {
+    if file_path.starts_with('[')
+        && let Some((_, path)) = file_path.split_once("]/")
+    {
+        return path;
+    }
+    file_path
+}
+
+/// Returns the name of the package in the innermost `node_modules` directory of `path`.
+fn package_name(path: &str) -> Option<&str> {
+    let (_, package_path) = path.rsplit_once("node_modules/")?;
+    let mut segments = package_path.splitn(3, '/');
+    let name = segments.next().filter(|name| !name.is_empty())?;
+    if name.starts_with('@')
+        && let Some(scoped_name) = segments.next().filter(|name| !name.is_empty())
+    {
+        return Some(&package_path[..name.len() + 1 + scoped_name.len()]);
+    }
+    Some(name)
+}
+
+struct CompiledIssueRule<'a> {
+    rule: &'a IssueRule,
+    path: Option<Glob>,
+}
+
+impl CompiledIssueRule<'_> {
+    fn matches(&self, issue: &PlainIssue) -> bool {
+        let rule = self.rule;
+        if let Some(stage) = &rule.stage
+            && issue.stage.to_string() != stage.as_str()
+        {
+            return false;
+        }
+        if let Some(title) = &rule.title
+            && !issue.title.to_unstyled_string().contains(title.as_str())
+        {
+            return false;
+        }
+        let path = strip_filesystem_name(&issue.file_path);
+        if let Some(glob) = &self.path
+            && !glob.matches(path)
+        {
+            return false;
+        }
+        if let Some(package) = &rule.package
+            && package_name(path) != Some(package.as_str())
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// The issues remaining after applying [IssueRules], see [apply_issue_rules].
+pub struct AppliedIssueRules {
+    pub issues: Vec<ReadRef<PlainIssue>>,
+    /// The number of issues matched by each rule, in the order of the rules. A rule that never
+    /// matches is likely stale and can be removed.
+    pub matches: Vec<usize>,
+}
+
+/// Suppresses issues or overrides their severity according to `rules`. Every issue is handled by
+/// the first rule it matches.
+pub fn apply_issue_rules(
+    issues: Vec<ReadRef<PlainIssue>>,
+    rules: &[IssueRule],
+) -> Result<AppliedIssueRules> {
+    let rules = rules
+        .iter()
+        .map(|rule| {
+            Ok(CompiledIssueRule {
+                rule,
+                path: rule
+                    .path
+                    .as_ref()
+                    .map(|path| {
+                        Glob::parse(path)
+                            .with_context(|| format!("invalid path glob in issue rule {rule}"))
+                    })
+                    .transpose()?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut matches = vec![0; rules.len()];
+    let issues = issues
+        .into_iter()
+        .filter_map(|issue| {
+            let Some(index) = rules.iter().position(|rule| rule.matches(&issue)) else {
+                return Some(issue);
+            };
+            matches[index] += 1;
+            match rules[index].rule.action {
+                IssueRuleAction::Suppress => None,
+                IssueRuleAction::Severity(severity) if severity == issue.severity => Some(issue),
+                IssueRuleAction::Severity(severity) => Some(ReadRef::new_owned(PlainIssue {
+                    severity,
+                    ..(*issue).clone()
+                })),
+            }
+        })
+        .collect();
+
+    Ok(AppliedIssueRules { issues, matches })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_name() {
+        assert_eq!(package_name("node_modules/foo/index.js"), Some("foo"));
+        assert_eq!(
+            package_name("node_modules/foo/node_modules/@scope/bar/lib/index.js"),
+            Some("@scope/bar")
+        );
+        assert_eq!(package_name("node_modules/@scope/bar"), Some("@scope/bar"));
+        assert_eq!(package_name("node_modules/@scope"), Some("@scope"));
+        assert_eq!(package_name("src/index.js"), None);
+    }
+
+    #[test]
+    fn test_strip_filesystem_name() {
+        assert_eq!(
+            strip_filesystem_name("[project]/node_modules/foo/index.js"),
+            "node_modules/foo/index.js"
+        );
+        assert_eq!(strip_filesystem_name("src/index.js"), "src/index.js");
+    }
+
+    #[test]
+    fn test_deserialize_rule() {
+        let rule: IssueRule = serde_json::from_str(
+            r#"{ "package": "foo", "stage": "parse", "action": { "severity": "warning" } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            rule,
+            IssueRule {
+                title: None,
+                stage: Some("parse".into()),
+                path: None,
+                package: Some("foo".into()),
+                action: IssueRuleAction::Severity(IssueSeverity::Warning),
+            }
+        );
+        assert_eq!(
+            rule.to_string(),
+            "stage \"parse\", package \"foo\" => warning"
+        );
+
+        let rule: IssueRule = serde_json::from_str(r#"{ "action": "suppress" }"#).unwrap();
+        assert_eq!(rule.action, IssueRuleAction::Suppress);
+        assert_eq!(rule.to_string(), "all issues => suppress");
+    }
+}
@@ -0,0 +1,297 @@
+//! Reports import cycles in the module graph as issues.
+//!
+//! Cycles are found as strongly connected components of the graph (see
+//! [SingleModuleGraph::traverse_cycles]). For every component, the shortest cycle through its
+//! module with the lexicographically smallest ident is reported, so that the reported path is
+//! stable between builds.
+
+use std::collections::{VecDeque, hash_map::Entry};
+
+use anyhow::{Context, Result};
+use petgraph::{graph::NodeIndex, visit::EdgeRef};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::{RcStr, rcstr};
+use turbo_tasks::{
+    NonLocalValue, ResolvedVc, TaskInput, TryJoinIterExt, ValueToString, Vc, trace::TraceRawVcs,
+};
+use turbo_tasks_fs::{FileSystemPath, glob::Glob};
+
+use crate::{
+    chunk::ChunkingType,
+    issue::{Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    module::Module,
+    module_graph::{ModuleGraph, RefData, SingleModuleGraph},
+};
+
+/// Configures which import cycles are reported by [check_circular_dependencies].
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TraceRawVcs, NonLocalValue, TaskInput,
+)]
+pub struct CircularDependencyOptions {
+    /// Globs matched against the paths of modules, relative to the root of their filesystem, e.g.
+    /// `node_modules/**`. A cycle is not reported when one of its modules matches.
+    pub allow: Vec<RcStr>,
+    /// Cycles with more modules than this are not reported.
+    pub max_cycle_length: Option<usize>,
+    /// The severity of the reported issues.
+    pub severity: IssueSeverity,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionCircularDependencyOptions(Option<CircularDependencyOptions>);
+
+/// Whether an edge is an import that can be part of a cycle. Traced references are not evaluated,
+/// so they never cause circular evaluation.
+fn is_import(ref_data: &RefData) -> bool {
+    !matches!(ref_data.chunking_type, ChunkingType::Traced)
+}
+
+/// Returns the shortest cycle starting and ending at `start` that only visits `members`, as the
+/// list of modules and the edge leading to the next module.
+pub(super) fn shortest_cycle<'a>(
+    graph: &'a SingleModuleGraph,
+    start: NodeIndex,
+    members: &FxHashSet<NodeIndex>,
+) -> Option<Vec<(NodeIndex, &'a RefData)>> {
+    let mut parents: FxHashMap<NodeIndex, (NodeIndex, &RefData)> = FxHashMap::default();
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for edge in graph.graph.edges(node) {
+            let target = edge.target();
+            if target == node || !is_import(edge.weight()) || !members.contains(&target) {
+                continue;
+            }
+            if target == start {
+                let mut cycle = vec![(node, edge.weight())];
+                let mut current = node;
+                while current != start {
+                    let (parent, ref_data) = parents[&current];
+                    cycle.push((parent, ref_data));
+                    current = parent;
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+            if let Entry::Vacant(entry) = parents.entry(target) {
+                entry.insert((node, edge.weight()));
+                queue.push_back(target);
+            }
+        }
+    }
+    None
+}
+
+/// A cycle found in a single graph, before it is filtered by [CircularDependencyOptions].
+struct FoundCycle {
+    /// The modules of the cycle in import order. The last module imports the first one.
+    modules: Vec<ResolvedVc<Box<dyn Module>>>,
+    /// The number of modules in the strongly connected component containing the cycle.
+    component_size: usize,
+    /// Whether one of the imports of the cycle is an async import, e.g. `import()`.
+    has_async_import: bool,
+}
+
+async fn find_cycles(graph: &SingleModuleGraph) -> Result<Vec<FoundCycle>> {
+    let mut components = Vec::new();
+    graph.traverse_cycles(is_import, |component| {
+        components.push(component.iter().map(|node| node.module).collect::<Vec<_>>());
+    });
+
+    components
+        .into_iter()
+        .map(async |component| {
+            let idents = component
+                .iter()
+                .map(|module| module.ident().to_string())
+                .try_join()
+                .await?;
+            let (start, _) = component
+                .iter()
+                .zip(idents.iter())
+                .min_by(|(_, a), (_, b)| a.as_str().cmp(b.as_str()))
+                .context("a cycle contains at least two modules")?;
+
+            let members = component
+                .iter()
+                .map(|&module| graph.get_module(module))
+                .collect::<Result<FxHashSet<_>>>()?;
+            let cycle = shortest_cycle(graph, graph.get_module(*start)?, &members)
+                .context("a strongly connected component contains a cycle")?;
+
+            Ok(FoundCycle {
+                modules: cycle
+                    .iter()
+                    .map(|&(node, _)| graph.graph.node_weight(node).unwrap().module())
+                    .collect(),
+                component_size: component.len(),
+                has_async_import: cycle
+                    .iter()
+                    .any(|(_, ref_data)| matches!(ref_data.chunking_type, ChunkingType::Async)),
+            })
+        })
+        .try_join()
+        .await
+}
+
+/// Emits a [CircularDependencyIssue] for every import cycle in the module graph that isn't
+/// excluded by `options`.
+///
+/// The single graphs of the module graph are searched one by one. A later graph only references
+/// modules of earlier graphs through `VisitedModule` nodes, and earlier graphs never reference
+/// later ones. As the modules of a cycle are all reachable from each other, they are all part of
+/// the first graph that visits one of them, so no cycle spans multiple graphs.
+#[turbo_tasks::function]
+pub async fn check_circular_dependencies(
+    graph: Vc<ModuleGraph>,
+    options: CircularDependencyOptions,
+) -> Result<Vc<()>> {
+    let allow = options
+        .allow
+        .iter()
+        .map(|glob| {
+            Glob::parse(glob).with_context(|| {
+                format!("invalid glob `{glob}` in the circular dependency allow list")
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for single_graph in &graph.await?.graphs {
+        let single_graph = single_graph.await?;
+        for cycle in find_cycles(&single_graph).await? {
+            if options
+                .max_cycle_length
+                .is_some_and(|max| cycle.modules.len() > max)
+            {
+                continue;
+            }
+
+            let idents = cycle
+                .modules
+                .iter()
+                .map(|module| module.ident())
+                .try_join()
+                .await?;
+            if idents
+                .iter()
+                .any(|ident| allow.iter().any(|glob| glob.matches(&ident.path.path)))
+            {
+                continue;
+            }
+
+            let mut layers: Vec<RcStr> = Vec::new();
+            for ident in &idents {
+                if let Some(layer) = &ident.layer
+                    && !layers.contains(layer.user_friendly_name())
+                {
+                    layers.push(layer.user_friendly_name().clone());
+                }
+            }
+
+            CircularDependencyIssue {
+                severity: options.severity,
+                path: idents[0].path.clone(),
+                cycle: cycle
+                    .modules
+                    .iter()
+                    .map(|module| module.ident().to_string().owned())
+                    .try_join()
+                    .await?,
+                component_size: cycle.component_size,
+                layers: if layers.len() > 1 { layers } else { Vec::new() },
+                has_async_import: cycle.has_async_import,
+            }
+            .resolved_cell()
+            .emit();
+        }
+    }
+
+    Ok(Vc::cell(()))
+}
+
+#[turbo_tasks::value(shared)]
+pub struct CircularDependencyIssue {
+    pub severity: IssueSeverity,
+    pub path: FileSystemPath,
+    /// The idents of the modules of the cycle in import order.
+    pub cycle: Vec<RcStr>,
+    pub component_size: usize,
+    /// The layers the cycle crosses, empty when all modules are in the same layer.
+    pub layers: Vec<RcStr>,
+    pub has_async_import: bool,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for CircularDependencyIssue {
+    fn severity(&self) -> IssueSeverity {
+        self.severity
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Text(
+            format!("Circular dependency between {} modules", self.cycle.len()).into(),
+        )
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Analysis.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path.clone().cell()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        let mut lines = vec![StyledString::Text(rcstr!(
+            "The following modules import each other in a cycle:"
+        ))];
+        for (index, ident) in self.cycle.iter().enumerate() {
+            lines.push(StyledString::Line(vec![
+                StyledString::Text(if index == 0 {
+                    rcstr!("  ")
+                } else {
+                    rcstr!("→ ")
+                }),
+                StyledString::Code(ident.clone()),
+            ]));
+        }
+        lines.push(StyledString::Line(vec![
+            StyledString::Text(rcstr!("→ ")),
+            StyledString::Code(self.cycle[0].clone()),
+        ]));
+        if self.component_size > self.cycle.len() {
+            lines.push(StyledString::Text(
+                format!(
+                    "The cycle is part of a group of {} modules that depend on each other.",
+                    self.component_size
+                )
+                .into(),
+            ));
+        }
+        if !self.layers.is_empty() {
+            lines.push(StyledString::Text(
+                format!(
+                    "The cycle crosses the boundaries between the {} layers, e.g. between server \
+                     and client code.",
+                    self.layers
+                        .iter()
+                        .map(|layer| format!("`{layer}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+                .into(),
+            ));
+        }
+        if self.has_async_import {
+            lines.push(StyledString::Text(rcstr!(
+                "The cycle contains an async import, so its modules are not evaluated together."
+            )));
+        }
+        Vc::cell(Some(StyledString::Stack(lines).resolved_cell()))
+    }
+}
@@ -0,0 +1,438 @@
+//! Finds packages that are included multiple times in the module graph, e.g. because different
+//! versions of a library are installed in nested `node_modules` directories.
+//!
+//! Modules are grouped by the innermost `node_modules/<name>` directory containing them and the
+//! `name` field of the `package.json` in that directory. A package is duplicated when modules from
+//! more than one of its directories are in the graph. The JSON report looks like this:
+//!
+//! ```json
+//! {
+//!   "packages": [
+//!     {
+//!       "name": "lodash",
+//!       "wastedBytes": 1234,
+//!       "copies": [
+//!         {
+//!           "version": "4.17.21",
+//!           "path": "[project]/node_modules/lodash",
+//!           "modules": 3,
+//!           "size": 4321,
+//!           "importChain": ["[project]/src/index.js [client] (ecmascript)", "..."]
+//!         }
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+
+use std::collections::{VecDeque, hash_map::Entry};
+
+use anyhow::Result;
+use petgraph::{Direction, graph::NodeIndex, visit::EdgeRef};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::{RcStr, rcstr};
+use turbo_tasks::{
+    FxIndexMap, NonLocalValue, ResolvedVc, TryJoinIterExt, ValueToString, Vc, trace::TraceRawVcs,
+};
+use turbo_tasks_fs::FileSystemPath;
+
+use crate::{
+    chunk::{ChunkItem, ChunkType, ChunkableModule, ChunkingContext, ChunkingType},
+    file_source::FileSource,
+    issue::{Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    module::Module,
+    module_graph::{ModuleGraph, SingleModuleGraph, SingleModuleGraphNode},
+    package_json::read_package_json,
+};
+
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePackages {
+    /// The duplicated packages, sorted by name.
+    pub packages: Vec<DuplicatePackage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePackage {
+    pub name: RcStr,
+    /// The sizes of all copies except the largest one, which would be saved if all copies were
+    /// deduplicated. Only available when sizes are known.
+    pub wasted_bytes: Option<u64>,
+    /// The copies of the package, sorted by version and path.
+    pub copies: Vec<DuplicatePackageCopy>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePackageCopy {
+    /// The `version` field of the `package.json`.
+    pub version: Option<RcStr>,
+    /// The directory the copy is installed in.
+    pub path: RcStr,
+    /// The number of modules of this copy in the graph.
+    pub modules: usize,
+    /// The estimated size of the modules in the output in bytes. Only available when the report is
+    /// created with a chunking context.
+    pub size: Option<u64>,
+    /// The idents of the shortest chain of modules from an entry of the graph to a module of this
+    /// copy.
+    pub import_chain: Vec<RcStr>,
+}
+
+impl DuplicatePackages {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Returns the directory of the package containing `path`, i.e. the innermost
+/// `node_modules/<name>` or `node_modules/@scope/<name>` directory.
+fn package_dir(path: &str) -> Option<&str> {
+    let index = path
+        .match_indices("node_modules/")
+        .map(|(index, _)| index)
+        .filter(|&index| index == 0 || path[..index].ends_with('/'))
+        .last()?;
+    let start = index + "node_modules/".len();
+    let mut segments = path[start..].splitn(3, '/');
+    let name = segments.next().filter(|name| !name.is_empty())?;
+    let len = if name.starts_with('@') {
+        name.len() + 1 + segments.next().filter(|name| !name.is_empty())?.len()
+    } else {
+        name.len()
+    };
+    // The path has to point into the package directory
+    segments.next()?;
+    Some(&path[..start + len])
+}
+
+/// Returns the start of the package name in a path returned by [package_dir].
+fn package_dir_name_start(dir: &str) -> usize {
+    dir.rfind("node_modules/")
+        .map_or(0, |index| index + "node_modules/".len())
+}
+
+/// Returns the shortest chain of modules from an entry of the graph to one of `targets`, starting
+/// with the entry. Traced references are not followed, as they don't include modules in the
+/// output.
+fn shortest_import_chain(
+    graph: &SingleModuleGraph,
+    targets: &[NodeIndex],
+) -> Option<Vec<NodeIndex>> {
+    // Maps every visited module to the module it imports on the way to a target.
+    let mut next: FxHashMap<NodeIndex, Option<NodeIndex>> =
+        targets.iter().map(|&target| (target, None)).collect();
+    let mut queue = targets.iter().copied().collect::<VecDeque<_>>();
+    while let Some(node) = queue.pop_front() {
+        let mut importers = graph
+            .graph
+            .edges_directed(node, Direction::Incoming)
+            .filter(|edge| !matches!(edge.weight().chunking_type, ChunkingType::Traced))
+            .map(|edge| edge.source())
+            .peekable();
+        if importers.peek().is_none() {
+            let mut chain = vec![node];
+            let mut current = node;
+            while let Some(imported) = next[&current] {
+                chain.push(imported);
+                current = imported;
+            }
+            return Some(chain);
+        }
+        for importer in importers {
+            if let Entry::Vacant(entry) = next.entry(importer) {
+                entry.insert(Some(node));
+                queue.push_back(importer);
+            }
+        }
+    }
+    None
+}
+
+/// The modules of a package directory, as (graph index, node index, module).
+type PackageModules = Vec<(usize, NodeIndex, ResolvedVc<Box<dyn Module>>)>;
+
+/// Finds all packages that are included from more than one directory. When a `chunking_context`
+/// is given, the sizes of the copies and the wasted bytes are estimated.
+#[turbo_tasks::function]
+pub async fn find_duplicate_packages(
+    module_graph: ResolvedVc<ModuleGraph>,
+    chunking_context: Option<Vc<Box<dyn ChunkingContext>>>,
+) -> Result<Vc<DuplicatePackages>> {
+    let graphs = module_graph.await?.get_graphs().await?;
+
+    let nodes = graphs
+        .iter()
+        .enumerate()
+        .flat_map(|(graph_idx, graph)| {
+            graph
+                .enumerate_nodes()
+                .filter_map(move |(node_idx, node)| match node {
+                    SingleModuleGraphNode::Module(node) => Some((graph_idx, node_idx, node.module)),
+                    SingleModuleGraphNode::VisitedModule { .. } => None,
+                })
+        })
+        .collect::<Vec<_>>();
+    let package_dirs = nodes
+        .iter()
+        .map(async |&(_, _, module)| {
+            let path = module.ident().path().owned().await?;
+            Ok(match package_dir(&path.path) {
+                Some(dir) => Some(path.root().owned().await?.join(dir)?),
+                None => None,
+            })
+        })
+        .try_join()
+        .await?;
+
+    let mut modules_by_dir: FxIndexMap<FileSystemPath, PackageModules> = FxIndexMap::default();
+    for (node, dir) in nodes.into_iter().zip(package_dirs) {
+        if let Some(dir) = dir {
+            modules_by_dir.entry(dir).or_default().push(node);
+        }
+    }
+
+    let manifests = modules_by_dir
+        .keys()
+        .map(async |dir| {
+            let package_json =
+                read_package_json(Vc::upcast(FileSource::new(dir.join("package.json")?))).await?;
+            let field = |name: &str| match &*package_json {
+                Some(package_json) => package_json[name].as_str().map(RcStr::from),
+                None => None,
+            };
+            Ok((field("name"), field("version")))
+        })
+        .try_join()
+        .await?;
+
+    let mut dirs_by_name: FxIndexMap<RcStr, Vec<(FileSystemPath, Option<RcStr>)>> =
+        FxIndexMap::default();
+    for (dir, (name, version)) in modules_by_dir.keys().zip(manifests) {
+        // Fall back to the directory name for packages without a `name`
+        let name = name.unwrap_or_else(|| dir.path[package_dir_name_start(&dir.path)..].into());
+        dirs_by_name
+            .entry(name)
+            .or_default()
+            .push((dir.clone(), version));
+    }
+
+    let mut packages = dirs_by_name
+        .into_iter()
+        .filter(|(_, dirs)| dirs.len() > 1)
+        .map(async |(name, dirs)| {
+            let mut copies = dirs
+                .into_iter()
+                .map(async |(dir, version)| {
+                    let modules = &modules_by_dir[&dir];
+                    let size = match chunking_context {
+                        Some(chunking_context) => Some(
+                            modules
+                                .iter()
+                                .filter_map(|&(_, _, module)| {
+                                    ResolvedVc::try_sidecast::<Box<dyn ChunkableModule>>(module)
+                                })
+                                .map(async |module| {
+                                    let chunk_item =
+                                        module.as_chunk_item(*module_graph, chunking_context);
+                                    Ok(*chunk_item
+                                        .ty()
+                                        .chunk_item_size(chunking_context, chunk_item, None)
+                                        .await? as u64)
+                                })
+                                .try_join()
+                                .await?
+                                .into_iter()
+                                .sum(),
+                        ),
+                        None => None,
+                    };
+
+                    let graph_idx = modules[0].0;
+                    let targets = modules
+                        .iter()
+                        .filter(|&&(idx, _, _)| idx == graph_idx)
+                        .map(|&(_, node_idx, _)| node_idx)
+                        .collect::<Vec<_>>();
+                    let graph = &graphs[graph_idx];
+                    let import_chain = shortest_import_chain(graph, &targets)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|node| {
+                            graph
+                                .graph
+                                .node_weight(node)
+                                .unwrap()
+                                .module()
+                                .ident()
+                                .to_string()
+                                .owned()
+                        })
+                        .try_join()
+                        .await?;
+
+                    Ok(DuplicatePackageCopy {
+                        version,
+                        path: dir.value_to_string().owned().await?,
+                        modules: modules.len(),
+                        size,
+                        import_chain,
+                    })
+                })
+                .try_join()
+                .await?;
+            copies.sort_by(|a, b| (&a.version, &a.path).cmp(&(&b.version, &b.path)));
+
+            let sizes = copies
+                .iter()
+                .map(|copy| copy.size)
+                .collect::<Option<Vec<_>>>();
+            let wasted_bytes =
+                sizes.map(|sizes| sizes.iter().sum::<u64>() - sizes.iter().max().unwrap());
+
+            anyhow::Ok(DuplicatePackage {
+                name,
+                wasted_bytes,
+                copies,
+            })
+        })
+        .try_join()
+        .await?;
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(DuplicatePackages { packages }.cell())
+}
+
+/// Emits a [DuplicatePackageIssue] for every package included from more than one directory.
+#[turbo_tasks::function]
+pub async fn check_duplicate_packages(
+    module_graph: ResolvedVc<ModuleGraph>,
+    chunking_context: Option<Vc<Box<dyn ChunkingContext>>>,
+) -> Result<Vc<()>> {
+    let duplicates = find_duplicate_packages(*module_graph, chunking_context).await?;
+    if duplicates.packages.is_empty() {
+        return Ok(Vc::cell(()));
+    }
+    let graphs = module_graph.await?.get_graphs().await?;
+    let Some(entry) = graphs.iter().find_map(|graph| graph.entry_modules().next()) else {
+        return Ok(Vc::cell(()));
+    };
+    let path = entry.ident().path().owned().await?;
+    for package in &duplicates.packages {
+        DuplicatePackageIssue {
+            path: path.clone(),
+            package: package.clone(),
+        }
+        .resolved_cell()
+        .emit();
+    }
+    Ok(Vc::cell(()))
+}
+
+#[turbo_tasks::value(shared)]
+pub struct DuplicatePackageIssue {
+    /// The path of an entry of the graph, as the issue isn't caused by a single file.
+    pub path: FileSystemPath,
+    pub package: DuplicatePackage,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for DuplicatePackageIssue {
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        let versions = self
+            .package
+            .copies
+            .iter()
+            .filter_map(|copy| copy.version.as_ref())
+            .collect::<FxHashSet<_>>()
+            .len();
+        StyledString::Line(vec![
+            StyledString::Text(rcstr!("Package ")),
+            StyledString::Code(self.package.name.clone()),
+            StyledString::Text(if versions > 1 {
+                format!(" is included in {versions} versions").into()
+            } else {
+                format!(" is included from {} locations", self.package.copies.len()).into()
+            }),
+        ])
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Analysis.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path.clone().cell()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        let mut lines = Vec::new();
+        for copy in &self.package.copies {
+            let mut line = vec![
+                StyledString::Strong(copy.version.clone().unwrap_or(rcstr!("unknown version"))),
+                StyledString::Text(rcstr!(" in ")),
+                StyledString::Code(copy.path.clone()),
+            ];
+            line.push(StyledString::Text(
+                match copy.size {
+                    Some(size) => format!(" ({} modules, {size} bytes)", copy.modules),
+                    None => format!(" ({} modules)", copy.modules),
+                }
+                .into(),
+            ));
+            lines.push(StyledString::Line(line));
+            if !copy.import_chain.is_empty() {
+                lines.push(StyledString::Text(
+                    format!("  imported by {}", copy.import_chain.join(" → ")).into(),
+                ));
+            }
+        }
+        if let Some(wasted_bytes) = self.package.wasted_bytes {
+            lines.push(StyledString::Text(
+                format!(
+                    "Deduplicating the package would save about {wasted_bytes} bytes. Align the \
+                     version ranges of the dependents or use the resolutions/overrides of your \
+                     package manager."
+                )
+                .into(),
+            ));
+        }
+        Vc::cell(Some(StyledString::Stack(lines).resolved_cell()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_dir() {
+        assert_eq!(
+            package_dir("node_modules/lodash/fp/map.js"),
+            Some("node_modules/lodash")
+        );
+        assert_eq!(
+            package_dir("node_modules/a/node_modules/@scope/b/index.js"),
+            Some("node_modules/a/node_modules/@scope/b")
+        );
+        assert_eq!(
+            package_dir("node_modules/.pnpm/react@18.2.0/node_modules/react/index.js"),
+            Some("node_modules/.pnpm/react@18.2.0/node_modules/react")
+        );
+        assert_eq!(package_dir("src/my_node_modules/a/index.js"), None);
+        assert_eq!(package_dir("node_modules/lodash"), None);
+        assert_eq!(package_dir("src/index.js"), None);
+    }
+}
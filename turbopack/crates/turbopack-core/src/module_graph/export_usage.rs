@@ -70,7 +70,7 @@ impl ModuleExportUsageInfo {
 }
 
 impl ModuleExportUsageInfo {
-    fn add(&mut self, usage: &ExportUsage) {
+    pub(crate) fn add(&mut self, usage: &ExportUsage) {
         match (&mut *self, usage) {
             (Self::All, _) => {}
             (_, ExportUsage::All) => {
@@ -0,0 +1,422 @@
+//! Exports the module graph in a stable, documented format for external tooling (e.g. dependency
+//! dashboards).
+//!
+//! The JSON format (version 1) looks like this:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "modules": [
+//!     {
+//!       "id": 0,
+//!       "ident": "[project]/src/index.js [client] (ecmascript)",
+//!       "path": "[project]/src/index.js",
+//!       "size": 1234,
+//!       "isAsync": false,
+//!       "isEntry": true,
+//!       "mergedInto": null,
+//!       "usedExports": "all",
+//!       "chunkGroups": [0]
+//!     }
+//!   ],
+//!   "edges": [
+//!     { "from": 0, "to": 1, "chunkingType": "parallel", "export": "*" }
+//!   ],
+//!   "chunkGroups": [
+//!     { "id": 0, "type": "entry", "entries": [0], "parent": null, "mergeTag": null }
+//!   ]
+//! }
+//! ```
+//!
+//! Module and chunk group ids are indices into the respective lists and are only stable within a
+//! single export. Modules are sorted by their ident.
+
+use std::fmt::Write;
+
+use anyhow::Result;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{
+    NonLocalValue, ResolvedVc, TryJoinIterExt, ValueToString, Vc, trace::TraceRawVcs,
+};
+
+use crate::{
+    chunk::{ChunkItem, ChunkType, ChunkableModule, ChunkingContext, ChunkingType},
+    module::Module,
+    module_graph::{
+        ModuleGraph, chunk_group_info::ChunkGroup, export_usage::ModuleExportUsageInfo,
+    },
+    resolve::ExportUsage,
+};
+
+/// The version of the export format. It's incremented on breaking changes.
+pub const MODULE_GRAPH_EXPORT_VERSION: u32 = 1;
+
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleGraphExport {
+    pub version: u32,
+    pub modules: Vec<ModuleGraphExportModule>,
+    pub edges: Vec<ModuleGraphExportEdge>,
+    pub chunk_groups: Vec<ModuleGraphExportChunkGroup>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleGraphExportModule {
+    /// The index of the module in the `modules` list.
+    pub id: usize,
+    /// The full ident of the module, including layer and modifiers.
+    pub ident: RcStr,
+    /// The path of the module source.
+    pub path: RcStr,
+    /// The estimated size of the module in the output in bytes. Only available for chunkable
+    /// modules when the graph is exported with a chunking context.
+    pub size: Option<u64>,
+    /// Whether the module is async, either by itself or by importing an async module.
+    pub is_async: bool,
+    /// Whether the module is an entry of the graph.
+    pub is_entry: bool,
+    /// The ident of the merged (scope hoisted) module this module is part of.
+    pub merged_into: Option<RcStr>,
+    /// Either `"all"`, `"evaluation"` (only side effects are used) or a list of export names.
+    pub used_exports: ModuleGraphExportUsedExports,
+    /// The ids of the chunk groups containing this module.
+    pub chunk_groups: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+#[serde(untagged)]
+pub enum ModuleGraphExportUsedExports {
+    Kind(RcStr),
+    Names(Vec<RcStr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleGraphExportEdge {
+    pub from: usize,
+    pub to: usize,
+    /// One of `parallel`, `async`, `isolated`, `shared` or `traced`.
+    pub chunking_type: RcStr,
+    /// The export that is used by this edge: an export name, `*` for the whole module or
+    /// `evaluation` for side effects only.
+    pub export: RcStr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleGraphExportChunkGroup {
+    pub id: usize,
+    /// One of `entry`, `async`, `isolated`, `isolatedMerged`, `shared` or `sharedMerged`.
+    #[serde(rename = "type")]
+    pub ty: RcStr,
+    /// The ids of the entry modules of the chunk group.
+    pub entries: Vec<usize>,
+    /// The id of the parent chunk group of merged chunk groups.
+    pub parent: Option<usize>,
+    pub merge_tag: Option<RcStr>,
+}
+
+fn chunking_type_name(chunking_type: &ChunkingType) -> &'static str {
+    match chunking_type {
+        ChunkingType::Parallel { .. } => "parallel",
+        ChunkingType::Async => "async",
+        ChunkingType::Isolated { .. } => "isolated",
+        ChunkingType::Shared { .. } => "shared",
+        ChunkingType::Traced => "traced",
+    }
+}
+
+fn export_name(export: &ExportUsage) -> RcStr {
+    match export {
+        ExportUsage::Named(name) => name.clone(),
+        ExportUsage::All => "*".into(),
+        ExportUsage::Evaluation => "evaluation".into(),
+    }
+}
+
+/// Exports the given module graph. When a `chunking_context` is given, the sizes of chunkable
+/// modules and, if module merging is enabled, the merged modules are included.
+#[turbo_tasks::function]
+pub async fn export_module_graph(
+    module_graph: ResolvedVc<ModuleGraph>,
+    chunking_context: Option<Vc<Box<dyn ChunkingContext>>>,
+) -> Result<Vc<ModuleGraphExport>> {
+    let graph = module_graph.await?;
+    let graphs = graph.get_graphs().await?;
+
+    let modules = graphs
+        .iter()
+        .flat_map(|graph| graph.iter_nodes().map(|node| node.module))
+        .collect::<Vec<_>>();
+    let entries = graphs
+        .iter()
+        .flat_map(|graph| graph.entry_modules())
+        .collect::<Vec<_>>();
+    let idents = modules
+        .iter()
+        .map(|module| async move { module.ident().to_string().owned().await })
+        .try_join()
+        .await?;
+    let mut modules_with_idents = modules.into_iter().zip(idents).collect::<Vec<_>>();
+    modules_with_idents.sort_by(|(_, a), (_, b)| a.cmp(b));
+    let module_ids = modules_with_idents
+        .iter()
+        .enumerate()
+        .map(|(id, &(module, _))| (module, id))
+        .collect::<FxHashMap<_, _>>();
+
+    let mut edges = Vec::new();
+    let mut used_exports = FxHashMap::<_, ModuleExportUsageInfo>::default();
+    graph
+        .traverse_all_edges_unordered(|(source, ref_data), target| {
+            used_exports
+                .entry(target.module)
+                .or_default()
+                .add(&ref_data.export);
+            if let (Some(&from), Some(&to)) = (
+                module_ids.get(&source.module),
+                module_ids.get(&target.module),
+            ) {
+                edges.push(ModuleGraphExportEdge {
+                    from,
+                    to,
+                    chunking_type: chunking_type_name(&ref_data.chunking_type).into(),
+                    export: export_name(&ref_data.export),
+                });
+            }
+            Ok(())
+        })
+        .await?;
+    edges.sort_by(|a, b| {
+        (a.from, a.to, &a.export, &a.chunking_type).cmp(&(
+            b.from,
+            b.to,
+            &b.export,
+            &b.chunking_type,
+        ))
+    });
+    edges.dedup();
+
+    let chunk_group_info = module_graph.chunk_group_info().await?;
+    let chunk_groups = chunk_group_info
+        .chunk_groups
+        .iter()
+        .enumerate()
+        .map(|(id, chunk_group)| {
+            let (ty, merge_tag) = match chunk_group {
+                ChunkGroup::Entry(_) => ("entry", None),
+                ChunkGroup::Async(_) => ("async", None),
+                ChunkGroup::Isolated(_) => ("isolated", None),
+                ChunkGroup::IsolatedMerged { merge_tag, .. } => {
+                    ("isolatedMerged", Some(merge_tag.clone()))
+                }
+                ChunkGroup::Shared(_) => ("shared", None),
+                ChunkGroup::SharedMerged { merge_tag, .. } => {
+                    ("sharedMerged", Some(merge_tag.clone()))
+                }
+            };
+            ModuleGraphExportChunkGroup {
+                id,
+                ty: ty.into(),
+                entries: chunk_group
+                    .entries()
+                    .filter_map(|module| module_ids.get(&module).copied())
+                    .collect(),
+                parent: chunk_group.get_merged_parent(),
+                merge_tag,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let async_module_info = module_graph.async_module_info().await?;
+    let merged_modules = match chunking_context {
+        Some(chunking_context) if *chunking_context.is_module_merging_enabled().await? => {
+            Some(module_graph.merged_modules().await?)
+        }
+        _ => None,
+    };
+
+    let modules = modules_with_idents
+        .into_iter()
+        .enumerate()
+        .map(|(id, (module, ident))| {
+            let entries = &entries;
+            let used_exports = &used_exports;
+            let chunk_group_info = &chunk_group_info;
+            let async_module_info = &async_module_info;
+            let merged_modules = &merged_modules;
+            async move {
+                let size = match chunking_context.and_then(|chunking_context| {
+                    ResolvedVc::try_sidecast::<Box<dyn ChunkableModule>>(module)
+                        .map(|module| (module, chunking_context))
+                }) {
+                    Some((module, chunking_context)) => {
+                        let chunk_item = module.as_chunk_item(*module_graph, chunking_context);
+                        let size = *chunk_item
+                            .ty()
+                            .chunk_item_size(chunking_context, chunk_item, None)
+                            .await?;
+                        Some(size as u64)
+                    }
+                    None => None,
+                };
+                let merged_into = match merged_modules
+                    .as_ref()
+                    .and_then(|merged_modules| merged_modules.should_replace_module(module))
+                {
+                    Some(replacement) => Some(replacement.ident().to_string().owned().await?),
+                    None => None,
+                };
+                let used_exports = match used_exports.get(&module) {
+                    // Modules without incoming edges are entries, which are fully used.
+                    None | Some(ModuleExportUsageInfo::All) => {
+                        ModuleGraphExportUsedExports::Kind("all".into())
+                    }
+                    Some(ModuleExportUsageInfo::Evaluation) => {
+                        ModuleGraphExportUsedExports::Kind("evaluation".into())
+                    }
+                    Some(ModuleExportUsageInfo::Exports(exports)) => {
+                        let mut names = exports.iter().cloned().collect::<Vec<_>>();
+                        names.sort();
+                        ModuleGraphExportUsedExports::Names(names)
+                    }
+                };
+                let chunk_groups = chunk_group_info
+                    .module_chunk_groups
+                    .get(&module)
+                    .map(|chunk_groups| chunk_groups.iter().map(|id| id as usize).collect())
+                    .unwrap_or_default();
+                Ok(ModuleGraphExportModule {
+                    id,
+                    path: module
+                        .ident()
+                        .path()
+                        .await?
+                        .value_to_string()
+                        .owned()
+                        .await?,
+                    ident,
+                    size,
+                    is_async: async_module_info.contains(&module),
+                    is_entry: entries.contains(&module),
+                    merged_into,
+                    used_exports,
+                    chunk_groups,
+                })
+            }
+        })
+        .try_join()
+        .await?;
+
+    Ok(ModuleGraphExport {
+        version: MODULE_GRAPH_EXPORT_VERSION,
+        modules,
+        edges,
+        chunk_groups,
+    }
+    .cell())
+}
+
+impl ModuleGraphExport {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Renders the graph in the Graphviz DOT language. Entries are drawn as boxes, async modules
+    /// with a dashed outline and async edges as dashed lines. Module sizes are shown as tooltips.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph modules {\n  node [shape=ellipse];\n");
+        for module in &self.modules {
+            let mut attributes = vec![format!("label={}", dot_string(&module.ident))];
+            if module.is_entry {
+                attributes.push("shape=box".to_string());
+            }
+            if module.is_async {
+                attributes.push("style=dashed".to_string());
+            }
+            if let Some(size) = module.size {
+                // `size` is a graph attribute in DOT, so the size is shown as a tooltip instead
+                attributes.push(format!("tooltip=\"{size} bytes\""));
+            }
+            writeln!(dot, "  m{} [{}];", module.id, attributes.join(", ")).unwrap();
+        }
+        for edge in &self.edges {
+            let style = match &*edge.chunking_type {
+                "async" => "dashed",
+                "traced" => "dotted",
+                _ => "solid",
+            };
+            writeln!(
+                dot,
+                "  m{} -> m{} [label={}, style={style}];",
+                edge.from,
+                edge.to,
+                dot_string(&edge.export)
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn dot_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_dot() {
+        let export = ModuleGraphExport {
+            version: MODULE_GRAPH_EXPORT_VERSION,
+            modules: vec![
+                ModuleGraphExportModule {
+                    id: 0,
+                    ident: "[project]/index.js".into(),
+                    path: "[project]/index.js".into(),
+                    size: Some(10),
+                    is_async: false,
+                    is_entry: true,
+                    merged_into: None,
+                    used_exports: ModuleGraphExportUsedExports::Kind("all".into()),
+                    chunk_groups: vec![0],
+                },
+                ModuleGraphExportModule {
+                    id: 1,
+                    ident: "[project]/\"lazy\".js".into(),
+                    path: "[project]/\"lazy\".js".into(),
+                    size: None,
+                    is_async: true,
+                    is_entry: false,
+                    merged_into: None,
+                    used_exports: ModuleGraphExportUsedExports::Names(vec!["default".into()]),
+                    chunk_groups: vec![1],
+                },
+            ],
+            edges: vec![ModuleGraphExportEdge {
+                from: 0,
+                to: 1,
+                chunking_type: "async".into(),
+                export: "default".into(),
+            }],
+            chunk_groups: vec![],
+        };
+        assert_eq!(
+            export.to_dot(),
+            r#"digraph modules {
+  node [shape=ellipse];
+  m0 [label="[project]/index.js", shape=box, tooltip="10 bytes"];
+  m1 [label="[project]/\"lazy\".js", style=dashed];
+  m0 -> m1 [label="default", style=dashed];
+}
+"#
+        );
+    }
+}
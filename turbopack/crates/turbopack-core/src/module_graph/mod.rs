@@ -43,7 +43,10 @@ use crate::{
 
 pub mod async_module_info;
 pub mod chunk_group_info;
+pub mod cycles;
+pub mod duplicate_packages;
 pub mod export_usage;
+pub mod graph_export;
 pub mod merged_modules;
 pub mod module_batch;
 pub(crate) mod module_batches;
@@ -1859,19 +1862,24 @@ impl Visit<(SingleModuleGraphBuilderNode, ExportUsage)> for SingleModuleGraphBui
 #[cfg(test)]
 pub mod tests {
     use anyhow::Result;
-    use rustc_hash::FxHashMap;
+    use rustc_hash::{FxHashMap, FxHashSet};
     use turbo_rcstr::{RcStr, rcstr};
-    use turbo_tasks::{ReadRef, ResolvedVc, TryJoinIterExt, Vc};
+    use turbo_tasks::{CollectiblesSource, ReadRef, ResolvedVc, TryJoinIterExt, Vc};
     use turbo_tasks_backend::{BackendOptions, TurboTasksBackend, noop_backing_storage};
     use turbo_tasks_fs::{FileSystem, FileSystemPath, VirtualFileSystem};
 
     use crate::{
         asset::{Asset, AssetContent},
         ident::AssetIdent,
+        issue::{Issue, IssueSeverity},
         module::Module,
         module_graph::{
-            GraphEntries, GraphTraversalAction, SingleModuleGraph,
+            GraphEntries, GraphTraversalAction, ModuleGraph, SingleModuleGraph, VisitedModules,
             chunk_group_info::ChunkGroupEntry,
+            cycles::{
+                CircularDependencyIssue, CircularDependencyOptions, check_circular_dependencies,
+                shortest_cycle,
+            },
         },
         reference::{ModuleReference, ModuleReferences, SingleChunkableModuleReference},
         resolve::ExportUsage,
@@ -1999,6 +2007,125 @@ pub mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn shortest_cycle_in_component() {
+        run_graph_test(
+            vec![rcstr!("a.js")],
+            {
+                let mut deps = FxHashMap::default();
+                // A cycle of length 3 with a shortcut back to a
+                deps.insert(rcstr!("a.js"), vec![rcstr!("b.js")]);
+                deps.insert(rcstr!("b.js"), vec![rcstr!("c.js"), rcstr!("a.js")]);
+                deps.insert(rcstr!("c.js"), vec![rcstr!("a.js")]);
+                deps
+            },
+            |graph, entry_modules, module_to_name| {
+                let mut components = Vec::new();
+                graph.traverse_cycles(
+                    |_| true,
+                    |cycle| {
+                        components.push(cycle.iter().map(|node| node.module).collect::<Vec<_>>())
+                    },
+                );
+                assert_eq!(components.len(), 1);
+                assert_eq!(components[0].len(), 3);
+
+                let members = components[0]
+                    .iter()
+                    .map(|&module| graph.get_module(module))
+                    .collect::<Result<FxHashSet<_>>>()?;
+                let cycle =
+                    shortest_cycle(&graph, graph.get_module(entry_modules[0])?, &members).unwrap();
+                assert_eq!(
+                    cycle
+                        .iter()
+                        .map(|&(node, _)| {
+                            let module = graph.graph.node_weight(node).unwrap().module();
+                            module_to_name.get(&module).unwrap().clone()
+                        })
+                        .collect::<Vec<_>>(),
+                    vec![rcstr!("a.js"), rcstr!("b.js")]
+                );
+                Ok(())
+            },
+        )
+        .await;
+    }
+
+    #[turbo_tasks::function(operation)]
+    fn check_circular_dependencies_operation(
+        graph: ResolvedVc<ModuleGraph>,
+        options: CircularDependencyOptions,
+    ) -> Vc<()> {
+        check_circular_dependencies(*graph, options)
+    }
+
+    #[tokio::test]
+    async fn circular_dependencies_across_graphs() {
+        crate::register();
+
+        let tt = turbo_tasks::TurboTasks::new(TurboTasksBackend::new(
+            BackendOptions::default(),
+            noop_backing_storage(),
+        ));
+        tt.run_once(async move {
+            let fs = VirtualFileSystem::new_with_name(rcstr!("test"));
+            let root = fs.root().await?;
+            // a.js and b.js import each other, c.js imports the cycle from a second graph
+            let repo = TestRepo {
+                repo: FxHashMap::from_iter([
+                    (root.join("a.js")?, vec![root.join("b.js")?]),
+                    (root.join("b.js")?, vec![root.join("a.js")?]),
+                    (root.join("c.js")?, vec![root.join("a.js")?]),
+                ]),
+            }
+            .cell();
+            let entry = async |path: &str| -> Result<Vc<GraphEntries>> {
+                let module = Vc::upcast::<Box<dyn Module>>(MockModule::new(root.join(path)?, repo))
+                    .to_resolved()
+                    .await?;
+                Ok(GraphEntries::cell(GraphEntries(vec![
+                    ChunkGroupEntry::Entry(vec![module]),
+                ])))
+            };
+            let first = SingleModuleGraph::new_with_entries(entry("a.js").await?, false);
+            let second = SingleModuleGraph::new_with_entries_visited(
+                entry("c.js").await?,
+                VisitedModules::from_graph(first),
+                false,
+            );
+            let graph = ModuleGraph::from_graphs(vec![first, second])
+                .to_resolved()
+                .await?;
+
+            let operation = check_circular_dependencies_operation(
+                graph,
+                CircularDependencyOptions {
+                    allow: vec![],
+                    max_cycle_length: None,
+                    severity: IssueSeverity::Warning,
+                },
+            );
+            operation.read_strongly_consistent().await?;
+            let issues = operation.peek_collectibles::<Box<dyn Issue>>();
+            // The second graph only references the cycle through `VisitedModule` nodes, so the
+            // cycle is reported once, by the first graph.
+            assert_eq!(issues.len(), 1);
+            let issue = ResolvedVc::try_downcast_type::<CircularDependencyIssue>(
+                *issues.iter().next().unwrap(),
+            )
+            .unwrap()
+            .await?;
+            assert_eq!(
+                issue.cycle,
+                vec![rcstr!("[test]/a.js"), rcstr!("[test]/b.js")]
+            );
+            anyhow::Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn traverse_edges_from_entries_fixed_point_cycle() {
         run_graph_test(
@@ -46,6 +46,8 @@ pub enum CommonJsReferenceSubType {
     Undefined,
 }
 
+/// The `type` import attribute (`import ... with { type: "..." }`), which determines how the
+/// imported resource is interpreted.
 #[derive(
     PartialEq,
     Eq,
@@ -55,11 +57,47 @@ pub enum CommonJsReferenceSubType {
     serde::Deserialize,
     Debug,
     Clone,
+    Copy,
     Hash,
     TaskInput,
 )]
 pub enum ImportWithType {
+    /// The default export is the parsed JSON.
     Json,
+    /// The default export is a constructable `CSSStyleSheet`.
+    Css,
+    /// The default export is the content as string. This is not standardized.
+    Text,
+    /// The default export is the content as `Uint8Array`. This is not standardized.
+    Bytes,
+}
+
+impl ImportWithType {
+    /// Parses the value of the `type` import attribute. Returns `None` for unsupported types.
+    pub fn from_attribute(value: &str) -> Option<Self> {
+        Some(match value {
+            "json" => ImportWithType::Json,
+            "css" => ImportWithType::Css,
+            "text" => ImportWithType::Text,
+            "bytes" => ImportWithType::Bytes,
+            _ => return None,
+        })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportWithType::Json => "json",
+            ImportWithType::Css => "css",
+            ImportWithType::Text => "text",
+            ImportWithType::Bytes => "bytes",
+        }
+    }
+}
+
+impl Display for ImportWithType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 #[derive(
@@ -79,6 +117,9 @@ pub enum EcmaScriptModulesReferenceSubType {
     ImportPart(ModulePart),
     Import,
     ImportWithType(ImportWithType),
+    /// A source phase import (`import source x from "..."`), which imports the compiled source of
+    /// a module (e.g. a `WebAssembly.Module`) without evaluating it.
+    ImportSourcePhase,
     DynamicImport,
     Custom(u8),
     #[default]
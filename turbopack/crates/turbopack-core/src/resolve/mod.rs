@@ -57,7 +57,9 @@ pub mod origin;
 pub mod parse;
 pub mod pattern;
 pub mod plugin;
+pub mod pnp;
 pub(crate) mod remap;
+pub mod web_import_map;
 
 pub use alias_map::{
     AliasMap, AliasMapIntoIter, AliasMapLookupIterator, AliasMatch, AliasPattern, AliasTemplate,
@@ -1470,6 +1472,23 @@ async fn find_package(
                     }
                 }
             }
+            ResolveModules::PnP { manifest } => {
+                let Some(name) = package_name.as_constant_string() else {
+                    continue;
+                };
+                affecting_sources.push(ResolvedVc::upcast(
+                    FileSource::new(manifest.clone()).to_resolved().await?,
+                ));
+                if let Some(dir) =
+                    &*pnp::resolve_pnp_package(manifest.clone(), lookup_path.clone(), name.clone())
+                        .await?
+                {
+                    packages.push(FindPackageItem::PackageDirectory {
+                        name: name.clone(),
+                        dir: dir.clone(),
+                    });
+                }
+            }
         }
     }
     Ok(FindPackageResult::cell(FindPackageResult {
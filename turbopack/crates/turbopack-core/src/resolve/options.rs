@@ -47,6 +47,8 @@ pub enum ResolveModules {
         dir: FileSystemPath,
         excluded_extensions: ResolvedVc<ExcludedExtensions>,
     },
+    /// look up packages in a Yarn Plug'n'Play manifest, see [super::pnp]
+    PnP { manifest: FileSystemPath },
 }
 
 #[derive(
@@ -0,0 +1,489 @@
+//! Support for [Yarn Plug'n'Play](https://yarnpkg.com/features/pnp) manifests.
+//!
+//! Instead of installing packages into `node_modules` directories, Yarn PnP writes a manifest
+//! (`.pnp.cjs`, or `.pnp.data.json` with `pnpEnableInlining: false`) which lists the location and
+//! the dependencies of every package. Package locations are usually inside of zip archives in the
+//! Yarn cache, which are read through a [ZipFileSystem].
+
+use std::borrow::Cow;
+
+use anyhow::{Context, Result, bail};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::RcStr;
+use turbo_tasks::{NonLocalValue, ResolvedVc, Vc, trace::TraceRawVcs};
+use turbo_tasks_fs::{
+    FileContent, FileSystem, FileSystemEntryType, FileSystemPath, FileSystemPathOption,
+    ZipFileSystem,
+};
+
+/// The file names of PnP manifests, in the order they are looked up.
+const PNP_MANIFEST_NAMES: [&str; 2] = [".pnp.cjs", ".pnp.data.json"];
+
+/// Identifies a package in a PnP manifest. The top-level workspace has neither a name nor a
+/// reference.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+struct PnpLocator {
+    name: Option<RcStr>,
+    reference: Option<RcStr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+struct PnpPackage {
+    /// The location of the package relative to the manifest directory, without a trailing slash.
+    location: RcStr,
+    /// The dependencies of the package. A `None` value is a missing peer dependency.
+    dependencies: FxHashMap<RcStr, Option<PnpLocator>>,
+}
+
+/// A parsed PnP manifest, see [pnp_manifest].
+#[turbo_tasks::value(serialization = "none")]
+#[derive(Debug)]
+pub struct PnpManifest {
+    packages: FxHashMap<PnpLocator, PnpPackage>,
+    /// The locations of all packages, longest first, to find the package owning a path.
+    locations: Vec<(RcStr, PnpLocator)>,
+    enable_top_level_fallback: bool,
+    fallback_pool: FxHashMap<RcStr, Option<PnpLocator>>,
+    fallback_exclusion_list: FxHashSet<PnpLocator>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPnpManifest {
+    #[serde(default)]
+    enable_top_level_fallback: bool,
+    #[serde(default)]
+    fallback_exclusion_list: Vec<(RcStr, Vec<RcStr>)>,
+    #[serde(default)]
+    fallback_pool: Vec<(RcStr, Option<RawDependencyTarget>)>,
+    package_registry_data: RawPackageRegistryData,
+}
+
+/// The packages by name and reference. The top-level workspace has neither a name nor a reference.
+type RawPackageRegistryData = Vec<(Option<RcStr>, Vec<(Option<RcStr>, RawPackageInformation)>)>;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPackageInformation {
+    package_location: RcStr,
+    #[serde(default)]
+    package_dependencies: Vec<(RcStr, Option<RawDependencyTarget>)>,
+}
+
+/// A dependency is either a reference of the package with the same name or an alias to another
+/// package, e.g. `["string-width-cjs", ["string-width", "npm:4.2.3"]]`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawDependencyTarget {
+    Reference(RcStr),
+    Alias(RcStr, RcStr),
+}
+
+impl RawDependencyTarget {
+    fn into_locator(self, name: &RcStr) -> PnpLocator {
+        match self {
+            RawDependencyTarget::Reference(reference) => PnpLocator {
+                name: Some(name.clone()),
+                reference: Some(reference),
+            },
+            RawDependencyTarget::Alias(name, reference) => PnpLocator {
+                name: Some(name),
+                reference: Some(reference),
+            },
+        }
+    }
+}
+
+fn into_dependencies(
+    dependencies: Vec<(RcStr, Option<RawDependencyTarget>)>,
+) -> FxHashMap<RcStr, Option<PnpLocator>> {
+    dependencies
+        .into_iter()
+        .map(|(name, target)| {
+            let locator = target.map(|target| target.into_locator(&name));
+            (name, locator)
+        })
+        .collect()
+}
+
+/// Turns a `packageLocation` like `./.yarn/cache/foo.zip/node_modules/foo/` into a path relative
+/// to the manifest directory without a trailing slash.
+fn normalize_location(location: &str) -> RcStr {
+    let location = location.strip_prefix("./").unwrap_or(location);
+    let location = location.strip_suffix('/').unwrap_or(location);
+    if location == "." {
+        RcStr::default()
+    } else {
+        location.into()
+    }
+}
+
+/// Maps a path inside of a `__virtual__` directory to the path it refers to. Yarn gives packages
+/// with peer dependencies a distinct virtual location per set of peers, e.g.
+/// `.yarn/__virtual__/foo-virtual-1234/0/cache/foo.zip/node_modules/foo` refers to
+/// `.yarn/cache/foo.zip/node_modules/foo`. The number is how many directories to go up from the
+/// parent of the `__virtual__` directory.
+fn resolve_virtual_path(path: &str) -> Cow<'_, str> {
+    let Some(index) = path.find("__virtual__/") else {
+        return Cow::Borrowed(path);
+    };
+    if index > 0 && !path[..index].ends_with('/') {
+        return Cow::Borrowed(path);
+    }
+    let mut segments = path[index + "__virtual__/".len()..].splitn(3, '/');
+    let (Some(_hash), Some(Ok(depth))) =
+        (segments.next(), segments.next().map(str::parse::<usize>))
+    else {
+        return Cow::Borrowed(path);
+    };
+    let subpath = segments.next().unwrap_or_default();
+
+    let mut base = path[..index]
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let mut ups = 0;
+    for _ in 0..depth {
+        if base.pop().is_none() {
+            ups += 1;
+        }
+    }
+    let mut resolved = "../".repeat(ups);
+    for segment in base {
+        resolved.push_str(segment);
+        resolved.push('/');
+    }
+    resolved.push_str(subpath);
+    Cow::Owned(resolved.trim_end_matches('/').to_string())
+}
+
+/// Splits a path into the path of a zip archive and the path inside of it, e.g.
+/// `.yarn/cache/foo.zip/node_modules/foo` into `.yarn/cache/foo.zip` and `node_modules/foo`.
+fn split_zip_path(path: &str) -> Option<(&str, &str)> {
+    if let Some(index) = path.find(".zip/") {
+        Some((&path[..index + 4], &path[index + 5..]))
+    } else if path.ends_with(".zip") {
+        Some((path, ""))
+    } else {
+        None
+    }
+}
+
+/// Extracts the JSON state that `.pnp.cjs` inlines as the `RAW_RUNTIME_STATE` string literal.
+fn extract_raw_runtime_state(code: &str) -> Option<String> {
+    let declaration = &code[code.find("RAW_RUNTIME_STATE")?..];
+    let literal = declaration[declaration.find('=')? + 1..].trim_start();
+    let quote = literal.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+
+    let mut state = String::new();
+    let mut chars = literal[1..].chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                // line continuations
+                '\n' => {}
+                '\r' => {
+                    chars.next_if_eq(&'\n');
+                }
+                'n' => state.push('\n'),
+                'r' => state.push('\r'),
+                't' => state.push('\t'),
+                c => state.push(c),
+            },
+            c if c == quote => return Some(state),
+            c => state.push(c),
+        }
+    }
+    None
+}
+
+impl PnpManifest {
+    fn parse(json: &str) -> Result<Self> {
+        let raw: RawPnpManifest = serde_json::from_str(json)?;
+
+        let mut packages = FxHashMap::default();
+        let mut locations = Vec::new();
+        let mut seen_locations = FxHashSet::default();
+        for (name, versions) in raw.package_registry_data {
+            for (reference, information) in versions {
+                let locator = PnpLocator {
+                    name: name.clone(),
+                    reference,
+                };
+                let location = normalize_location(&information.package_location);
+                // Files of virtual packages can also be imported through their physical path, which
+                // is mapped to the first virtual instance of the package.
+                let physical_location = RcStr::from(resolve_virtual_path(&location));
+                for location in [location.clone(), physical_location] {
+                    if seen_locations.insert(location.clone()) {
+                        locations.push((location, locator.clone()));
+                    }
+                }
+                packages.insert(
+                    locator,
+                    PnpPackage {
+                        location,
+                        dependencies: into_dependencies(information.package_dependencies),
+                    },
+                );
+            }
+        }
+        locations.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+
+        let fallback_exclusion_list = raw
+            .fallback_exclusion_list
+            .into_iter()
+            .flat_map(|(name, references)| {
+                references.into_iter().map(move |reference| PnpLocator {
+                    name: Some(name.clone()),
+                    reference: Some(reference),
+                })
+            })
+            .collect();
+
+        Ok(PnpManifest {
+            packages,
+            locations,
+            enable_top_level_fallback: raw.enable_top_level_fallback,
+            fallback_pool: into_dependencies(raw.fallback_pool),
+            fallback_exclusion_list,
+        })
+    }
+
+    /// Returns the package owning `path`, which is relative to the manifest directory.
+    fn find_locator(&self, path: &str) -> Option<&PnpLocator> {
+        self.locations
+            .iter()
+            .find(|(location, _)| {
+                location.is_empty()
+                    || path
+                        .strip_prefix(location.as_str())
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .map(|(_, locator)| locator)
+    }
+
+    /// Returns the location of the package `name` when it's imported from `issuer_path`. Both
+    /// paths are relative to the manifest directory. Returns `None` when the issuer isn't managed
+    /// by the manifest or doesn't depend on the package.
+    fn resolve_package_location(&self, issuer_path: &str, name: &str) -> Option<&RcStr> {
+        let issuer = self.find_locator(issuer_path)?;
+        let dependency = match self.packages.get(issuer)?.dependencies.get(name) {
+            Some(dependency) => dependency.as_ref(),
+            None if self.enable_top_level_fallback
+                && !self.fallback_exclusion_list.contains(issuer) =>
+            {
+                self.fallback_pool.get(name)?.as_ref()
+            }
+            None => None,
+        }?;
+        Some(&self.packages.get(dependency)?.location)
+    }
+}
+
+/// Reads and parses a PnP manifest. Reads are tracked, so changes to the manifest invalidate
+/// everything that was resolved through it.
+#[turbo_tasks::function]
+pub async fn pnp_manifest(manifest: FileSystemPath) -> Result<Vc<PnpManifest>> {
+    let FileContent::Content(file) = &*manifest.read().await? else {
+        bail!("Yarn PnP manifest {} doesn't exist", manifest.path);
+    };
+    let code = file.content().to_str()?;
+    let json = if manifest.path.ends_with(".json") {
+        code.into_owned()
+    } else if let Some(state) = extract_raw_runtime_state(&code) {
+        state
+    } else {
+        // Without inlining, `.pnp.cjs` loads its state from `.pnp.data.json`
+        return Ok(pnp_manifest(manifest.parent().join(".pnp.data.json")?));
+    };
+    Ok(PnpManifest::parse(&json)
+        .with_context(|| format!("failed to parse Yarn PnP manifest {}", manifest.path))?
+        .cell())
+}
+
+/// Looks for a PnP manifest in `dir`.
+#[turbo_tasks::function]
+pub async fn find_pnp_manifest(dir: FileSystemPath) -> Result<Vc<FileSystemPathOption>> {
+    for name in PNP_MANIFEST_NAMES {
+        let manifest = dir.join(name)?;
+        if *manifest.get_type().await? == FileSystemEntryType::File {
+            return Ok(Vc::cell(Some(manifest)));
+        }
+    }
+    Ok(Vc::cell(None))
+}
+
+/// Returns the path of `path` relative to `manifest_dir` as used in the manifest. Paths inside of
+/// zip archives opened by [resolve_pnp_package] are mapped back to their location in the
+/// manifest.
+async fn manifest_relative_path(
+    manifest_dir: &FileSystemPath,
+    path: &FileSystemPath,
+) -> Result<Option<RcStr>> {
+    if path == manifest_dir {
+        return Ok(Some(RcStr::default()));
+    }
+    if let Some(relative) = manifest_dir.get_path_to(path) {
+        return Ok(Some(relative.into()));
+    }
+    if let Some(zip_fs) = ResolvedVc::try_downcast_type::<ZipFileSystem>(path.fs) {
+        let archive = zip_fs.archive().await?;
+        if manifest_dir.fs == archive.fs {
+            let archive_location = zip_fs.name().await?;
+            return Ok(Some(if path.path.is_empty() {
+                (*archive_location).clone()
+            } else {
+                format!("{archive_location}/{}", path.path).into()
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// Resolves the directory of the package `name` when it's imported from `issuer` through the PnP
+/// manifest at `manifest`. Packages inside of zip archives are read through a [ZipFileSystem]
+/// named after the location of the archive in the manifest.
+#[turbo_tasks::function]
+pub async fn resolve_pnp_package(
+    manifest: FileSystemPath,
+    issuer: FileSystemPath,
+    name: RcStr,
+) -> Result<Vc<FileSystemPathOption>> {
+    let manifest_dir = manifest.parent();
+    let Some(issuer_path) = manifest_relative_path(&manifest_dir, &issuer).await? else {
+        return Ok(Vc::cell(None));
+    };
+    let pnp_manifest = pnp_manifest(manifest).await?;
+    let Some(location) = pnp_manifest.resolve_package_location(&issuer_path, &name) else {
+        return Ok(Vc::cell(None));
+    };
+
+    let physical_location = resolve_virtual_path(location);
+    let dir = match (split_zip_path(location), split_zip_path(&physical_location)) {
+        (Some((archive_location, inner_path)), Some((archive_path, _))) => {
+            let zip_fs =
+                ZipFileSystem::new(archive_location.into(), manifest_dir.join(archive_path)?);
+            Vc::upcast::<Box<dyn FileSystem>>(zip_fs)
+                .root()
+                .await?
+                .join(inner_path)?
+        }
+        _ => manifest_dir.join(&physical_location)?,
+    };
+    Ok(Vc::cell(Some(dir)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"{
+        "enableTopLevelFallback": true,
+        "fallbackExclusionList": [["lib", ["npm:1.0.0"]]],
+        "fallbackPool": [["shared", "npm:2.0.0"]],
+        "packageRegistryData": [
+            [null, [[null, {
+                "packageLocation": "./",
+                "packageDependencies": [["lib", "npm:1.0.0"], ["peer-user", "virtual:abc#npm:1.0.0"]]
+            }]]],
+            ["lib", [["npm:1.0.0", {
+                "packageLocation": "./.yarn/cache/lib-npm-1.0.0-abc.zip/node_modules/lib/",
+                "packageDependencies": [["lib", "npm:1.0.0"], ["alias", ["shared", "npm:2.0.0"]]]
+            }]]],
+            ["peer-user", [["virtual:abc#npm:1.0.0", {
+                "packageLocation": "./.yarn/__virtual__/peer-user-virtual-abc/0/cache/peer-user-npm-1.0.0-def.zip/node_modules/peer-user/",
+                "packageDependencies": [["peer-user", "virtual:abc#npm:1.0.0"], ["missing-peer", null]]
+            }]]],
+            ["shared", [["npm:2.0.0", {
+                "packageLocation": "./.yarn/unplugged/shared-npm-2.0.0-ghi/node_modules/shared/",
+                "packageDependencies": [["shared", "npm:2.0.0"]]
+            }]]]
+        ]
+    }"#;
+
+    #[test]
+    fn test_resolve_package_location() {
+        let manifest = PnpManifest::parse(MANIFEST).unwrap();
+
+        assert_eq!(
+            manifest
+                .resolve_package_location("src/index.js", "lib")
+                .map(|l| l.as_str()),
+            Some(".yarn/cache/lib-npm-1.0.0-abc.zip/node_modules/lib")
+        );
+        assert_eq!(
+            manifest
+                .resolve_package_location(
+                    ".yarn/cache/lib-npm-1.0.0-abc.zip/node_modules/lib/index.js",
+                    "alias"
+                )
+                .map(|l| l.as_str()),
+            Some(".yarn/unplugged/shared-npm-2.0.0-ghi/node_modules/shared")
+        );
+        // The top-level fallback is used for the workspace, but `lib` is excluded from it
+        assert!(
+            manifest
+                .resolve_package_location("src/index.js", "shared")
+                .is_some()
+        );
+        assert!(
+            manifest
+                .resolve_package_location(
+                    ".yarn/cache/lib-npm-1.0.0-abc.zip/node_modules/lib/index.js",
+                    "shared"
+                )
+                .is_none()
+        );
+        assert!(
+            manifest
+                .resolve_package_location(
+                    ".yarn/__virtual__/peer-user-virtual-abc/0/cache/peer-user-npm-1.0.0-def.zip/\
+                     node_modules/peer-user/index.js",
+                    "missing-peer"
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_resolve_virtual_path() {
+        assert_eq!(
+            resolve_virtual_path(
+                ".yarn/__virtual__/foo-virtual-abc/0/cache/foo.zip/node_modules/foo"
+            ),
+            ".yarn/cache/foo.zip/node_modules/foo"
+        );
+        assert_eq!(
+            resolve_virtual_path(".yarn/__virtual__/foo-virtual-abc/1/packages/foo"),
+            "packages/foo"
+        );
+        assert_eq!(
+            resolve_virtual_path("__virtual__/foo-virtual-abc/2/foo"),
+            "../../foo"
+        );
+        assert_eq!(resolve_virtual_path("packages/foo"), "packages/foo");
+    }
+
+    #[test]
+    fn test_split_zip_path() {
+        assert_eq!(
+            split_zip_path(".yarn/cache/foo.zip/node_modules/foo"),
+            Some((".yarn/cache/foo.zip", "node_modules/foo"))
+        );
+        assert_eq!(split_zip_path("packages/foo"), None);
+    }
+
+    #[test]
+    fn test_extract_raw_runtime_state() {
+        let code = "#!/usr/bin/env node\n/* eslint-disable */\n\"use strict\";\n\nconst \
+                    RAW_RUNTIME_STATE =\n'{\\\n  \"__info\": [],\\\n  \"pattern\": \
+                    \"(^\\\\/)\"\\\n}';\n";
+        assert_eq!(
+            extract_raw_runtime_state(code).as_deref(),
+            Some("{  \"__info\": [],  \"pattern\": \"(^\\/)\"}")
+        );
+        assert_eq!(extract_raw_runtime_state("module.exports = {}"), None);
+    }
+}
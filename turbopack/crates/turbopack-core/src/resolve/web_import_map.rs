@@ -0,0 +1,452 @@
+//! Support for [import maps](https://html.spec.whatwg.org/multipage/webappapis.html#import-maps)
+//! as used by browsers, e.g. `{ "imports": { "react": "./vendor/react.js" }, "scopes": { ... } }`.
+//!
+//! The import map is loaded into an [ImportMap] with one alias per specifier key. All aliases use
+//! the same [WebImportMap] replacement, which implements the resolution algorithm of the spec, so
+//! scoped mappings are chosen based on the directory of the importing module.
+//!
+//! Addresses and scopes starting with `/`, `./` or `../` are resolved against the directory
+//! containing the import map file, i.e. the import map is expected to be served from the root of
+//! the site. Addresses that are absolute URLs, e.g. `https://esm.sh/react`, become URL externals.
+//! A `null` address blocks the specifier, so requests for it fail to resolve.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use turbo_rcstr::{RcStr, rcstr};
+use turbo_tasks::{NonLocalValue, ResolvedVc, Vc, trace::TraceRawVcs};
+use turbo_tasks_fs::{FileJsonContent, FileSystemPath};
+
+use super::{
+    ExternalTraced, ExternalType, ResolveResult,
+    options::{
+        ImportMap, ImportMapResult, ImportMapping, ImportMappingReplacement, ReplacedImportMapping,
+    },
+    parse::Request,
+    pattern::Pattern,
+};
+use crate::issue::{Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString};
+
+/// The target of a specifier in an import map.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+enum ImportMapAddress {
+    /// A path relative to the directory of the import map, e.g. `vendor/react.js` or `../lib/`.
+    Path(RcStr),
+    /// An absolute URL, which is kept as an external.
+    Url(RcStr),
+    /// A `null` address, which prevents the specifier from being resolved at all.
+    Blocked,
+}
+
+impl ImportMapAddress {
+    fn parse(address: &str) -> Option<Self> {
+        if let Some(path) = address.strip_prefix("./") {
+            Some(ImportMapAddress::Path(path.into()))
+        } else if address.starts_with("../") {
+            Some(ImportMapAddress::Path(address.into()))
+        } else if let Some(path) = address.strip_prefix('/') {
+            Some(ImportMapAddress::Path(path.into()))
+        } else if is_absolute_url(address) {
+            Some(ImportMapAddress::Url(address.into()))
+        } else {
+            // Bare specifiers are not valid addresses
+            None
+        }
+    }
+
+    fn with_suffix(&self, suffix: &str) -> Self {
+        match self {
+            ImportMapAddress::Path(path) => {
+                ImportMapAddress::Path(format!("{path}{suffix}").into())
+            }
+            ImportMapAddress::Url(url) => ImportMapAddress::Url(format!("{url}{suffix}").into()),
+            ImportMapAddress::Blocked => ImportMapAddress::Blocked,
+        }
+    }
+}
+
+/// Whether `value` starts with a URL scheme, e.g. `https:` or `node:`.
+fn is_absolute_url(value: &str) -> bool {
+    let Some((scheme, _)) = value.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// A specifier map of an import map, sorted by descending key length so that the first matching
+/// key is the most specific one.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue,
+)]
+struct SpecifierMap(Vec<(RcStr, ImportMapAddress)>);
+
+impl SpecifierMap {
+    fn parse(value: &Value, invalid: &mut Vec<RcStr>) -> Self {
+        let mut entries: Vec<(RcStr, _)> = Vec::new();
+        for (key, address) in value.as_object().into_iter().flatten() {
+            // Keys that look like relative paths would be resolved against the importing module,
+            // which isn't supported.
+            let is_valid_key = !key.is_empty()
+                && !key.starts_with("./")
+                && !key.starts_with("../")
+                && !key.starts_with('/');
+            let address = if address.is_null() {
+                Some(ImportMapAddress::Blocked)
+            } else {
+                address
+                    .as_str()
+                    .filter(|address| !key.ends_with('/') || address.ends_with('/'))
+                    .and_then(ImportMapAddress::parse)
+            };
+            match address {
+                Some(address) if is_valid_key => entries.push((key.as_str().into(), address)),
+                _ => invalid.push(key.as_str().into()),
+            }
+        }
+        entries.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        SpecifierMap(entries)
+    }
+
+    fn resolve(&self, specifier: &str) -> Option<ImportMapAddress> {
+        self.0.iter().find_map(|(key, address)| {
+            if key == specifier {
+                Some(address.clone())
+            } else if key.ends_with('/') {
+                let suffix = specifier.strip_prefix(key.as_str())?;
+                Some(address.with_suffix(suffix))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// An import map without the location it was loaded from, see [WebImportMap].
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue,
+)]
+struct ParsedImportMap {
+    imports: SpecifierMap,
+    /// Scopes as paths relative to the directory of the import map, without a trailing slash,
+    /// sorted from the most to the least specific.
+    scopes: Vec<(RcStr, SpecifierMap)>,
+}
+
+impl ParsedImportMap {
+    /// Parses an import map. Returns the import map and the keys of all entries that are invalid or
+    /// not supported, which are skipped.
+    fn parse(value: &Value) -> (Self, Vec<RcStr>) {
+        let mut invalid = Vec::new();
+        let imports = value
+            .get("imports")
+            .map(|imports| SpecifierMap::parse(imports, &mut invalid))
+            .unwrap_or_default();
+        let mut scopes: Vec<(RcStr, _)> = Vec::new();
+        for (scope, map) in value
+            .get("scopes")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            let scope_path = if let Some(path) = scope.strip_prefix("./") {
+                path
+            } else if let Some(path) = scope.strip_prefix('/') {
+                path
+            } else if scope.starts_with("../") {
+                scope.as_str()
+            } else {
+                invalid.push(scope.as_str().into());
+                continue;
+            };
+            let scope_path = scope_path.trim_end_matches('/');
+            scopes.push((scope_path.into(), SpecifierMap::parse(map, &mut invalid)));
+        }
+        scopes.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        (ParsedImportMap { imports, scopes }, invalid)
+    }
+
+    /// All keys of all specifier maps, which are the specifiers handled by the import map.
+    fn keys(&self) -> impl Iterator<Item = &RcStr> {
+        self.imports
+            .0
+            .iter()
+            .chain(self.scopes.iter().flat_map(|(_, map)| map.0.iter()))
+            .map(|(key, _)| key)
+    }
+
+    /// Resolves `specifier` with the mappings of all scopes `is_in_scope` returns true for, falling
+    /// back to the top-level mappings.
+    fn resolve(
+        &self,
+        specifier: &str,
+        is_in_scope: impl Fn(&str) -> bool,
+    ) -> Option<ImportMapAddress> {
+        self.scopes
+            .iter()
+            .filter(|(scope, _)| is_in_scope(scope))
+            .find_map(|(_, map)| map.resolve(specifier))
+            .or_else(|| self.imports.resolve(specifier))
+    }
+}
+
+/// An [ImportMappingReplacement] that resolves requests with a WHATWG import map.
+#[turbo_tasks::value(shared)]
+pub struct WebImportMap {
+    /// The directory containing the import map file.
+    dir: FileSystemPath,
+    map: ParsedImportMap,
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for WebImportMap {
+    #[turbo_tasks::function]
+    fn replace(self: ResolvedVc<Self>, _capture: Vc<Pattern>) -> Vc<ReplacedImportMapping> {
+        // The scope depends on the lookup path, so the whole request is resolved in `result`
+        ReplacedImportMapping::Dynamic(ResolvedVc::upcast(self)).cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        lookup_path: FileSystemPath,
+        request: Vc<Request>,
+    ) -> Result<Vc<ImportMapResult>> {
+        let Some(specifier) = request.await?.request() else {
+            return Ok(ImportMapResult::NoEntry.cell());
+        };
+        let address = self.map.resolve(&specifier, |scope| {
+            self.dir
+                .join(scope)
+                .is_ok_and(|scope| lookup_path.is_inside_or_equal_ref(&scope))
+        });
+        Ok(match address {
+            Some(ImportMapAddress::Path(path)) => {
+                let target = self.dir.join(&path)?;
+                ImportMapResult::Alias(
+                    Request::parse_string(format!("./{}", target.path).into())
+                        .to_resolved()
+                        .await?,
+                    Some(target.root().owned().await?),
+                )
+            }
+            Some(ImportMapAddress::Url(url)) => {
+                ImportMapResult::External(url, ExternalType::Url, ExternalTraced::Untraced)
+            }
+            Some(ImportMapAddress::Blocked) => {
+                ImportMapResult::Result(ResolveResult::unresolvable())
+            }
+            None => ImportMapResult::NoEntry,
+        }
+        .cell())
+    }
+}
+
+/// Loads the import map JSON file at `path` into an [ImportMap]. Problems with the file are
+/// reported as issues, an unreadable file results in an empty [ImportMap].
+#[turbo_tasks::function]
+pub async fn web_import_map(path: FileSystemPath) -> Result<Vc<ImportMap>> {
+    let json = match &*path.read_json().await? {
+        FileJsonContent::Content(json) => json.clone(),
+        FileJsonContent::Unparsable(e) => {
+            WebImportMapIssue {
+                severity: IssueSeverity::Error,
+                path,
+                title: rcstr!("Import map is not valid JSON"),
+                message: e.message.clone(),
+            }
+            .resolved_cell()
+            .emit();
+            return Ok(ImportMap::empty().cell());
+        }
+        FileJsonContent::NotFound => {
+            WebImportMapIssue {
+                severity: IssueSeverity::Error,
+                path,
+                title: rcstr!("Import map not found"),
+                message: rcstr!("The configured import map file doesn't exist."),
+            }
+            .resolved_cell()
+            .emit();
+            return Ok(ImportMap::empty().cell());
+        }
+    };
+
+    let (map, invalid) = ParsedImportMap::parse(&json);
+    if !invalid.is_empty() {
+        WebImportMapIssue {
+            severity: IssueSeverity::Warning,
+            path: path.clone(),
+            title: rcstr!("Unsupported import map entries"),
+            message: format!(
+                "The following entries are invalid or not supported and are ignored: {}. \
+                 Specifiers must be bare specifiers or URLs, addresses and scopes must be paths \
+                 starting with `/`, `./` or `../` or absolute URLs, and addresses of keys ending \
+                 with `/` must end with `/` too.",
+                invalid
+                    .iter()
+                    .map(|key| format!("`{key}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .into(),
+        }
+        .resolved_cell()
+        .emit();
+    }
+
+    let mut keys = map.keys().cloned().collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+
+    let mapping = ImportMapping::Dynamic(ResolvedVc::upcast(
+        WebImportMap {
+            dir: path.parent(),
+            map,
+        }
+        .resolved_cell(),
+    ))
+    .resolved_cell();
+
+    let mut import_map = ImportMap::empty();
+    for key in keys {
+        if key.ends_with('/') {
+            import_map.insert_wildcard_alias(key, mapping);
+        } else {
+            import_map.insert_exact_alias(key, mapping);
+        }
+    }
+    Ok(import_map.cell())
+}
+
+#[turbo_tasks::value(shared)]
+struct WebImportMapIssue {
+    severity: IssueSeverity,
+    path: FileSystemPath,
+    title: RcStr,
+    message: RcStr,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for WebImportMapIssue {
+    fn severity(&self) -> IssueSeverity {
+        self.severity
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Text(self.title.clone()).cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Resolve.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path.clone().cell()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        Vc::cell(Some(
+            StyledString::Text(self.message.clone()).resolved_cell(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn path(path: &str) -> Option<ImportMapAddress> {
+        Some(ImportMapAddress::Path(path.into()))
+    }
+
+    #[test]
+    fn test_resolve_import_map() {
+        let (map, mut invalid) = ParsedImportMap::parse(&json!({
+            "imports": {
+                "react": "/vendor/react.js",
+                "lodash/": "./vendor/lodash/",
+                "lodash/fp": "./vendor/lodash-fp.js",
+                "cdn": "https://esm.sh/cdn@1",
+                "bare": "other",
+                "./relative": "./relative.js",
+                "prefix/": "./not-a-prefix.js",
+                "blocked": null,
+                "blocked-prefix/": null,
+            },
+            "scopes": {
+                "/legacy/": {
+                    "react": "./vendor/react-17.js",
+                },
+                "/legacy/nested/": {
+                    "react": null,
+                },
+                "https://example.com/": {},
+            },
+        }));
+        invalid.sort();
+        assert_eq!(
+            invalid,
+            vec![
+                RcStr::from("./relative"),
+                RcStr::from("bare"),
+                RcStr::from("https://example.com/"),
+                RcStr::from("prefix/"),
+            ]
+        );
+
+        let top_level = |_: &str| false;
+        assert_eq!(map.resolve("react", top_level), path("vendor/react.js"));
+        assert_eq!(
+            map.resolve("lodash/map.js", top_level),
+            path("vendor/lodash/map.js")
+        );
+        assert_eq!(
+            map.resolve("lodash/fp", top_level),
+            path("vendor/lodash-fp.js")
+        );
+        assert_eq!(
+            map.resolve("cdn", top_level),
+            Some(ImportMapAddress::Url("https://esm.sh/cdn@1".into()))
+        );
+        assert_eq!(map.resolve("lodash", top_level), None);
+        assert_eq!(
+            map.resolve("blocked", top_level),
+            Some(ImportMapAddress::Blocked)
+        );
+        assert_eq!(
+            map.resolve("blocked-prefix/a.js", top_level),
+            Some(ImportMapAddress::Blocked)
+        );
+
+        let legacy = |scope: &str| scope == "legacy";
+        assert_eq!(map.resolve("react", legacy), path("vendor/react-17.js"));
+        assert_eq!(
+            map.resolve("lodash/map.js", legacy),
+            path("vendor/lodash/map.js")
+        );
+        // The `null` mapping in the nested scope blocks the specifier instead of falling back to
+        // the mappings of the outer scopes
+        let nested = |scope: &str| scope == "legacy" || scope == "legacy/nested";
+        assert_eq!(
+            map.resolve("react", nested),
+            Some(ImportMapAddress::Blocked)
+        );
+    }
+
+    #[test]
+    fn test_is_absolute_url() {
+        assert!(is_absolute_url("https://esm.sh/react"));
+        assert!(is_absolute_url("node:fs"));
+        assert!(!is_absolute_url("react"));
+        assert!(!is_absolute_url("./a:b"));
+        assert!(!is_absolute_url("1ab:c"));
+    }
+}
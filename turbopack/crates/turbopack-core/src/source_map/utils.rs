@@ -5,6 +5,7 @@ use const_format::concatcp;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use turbo_rcstr::RcStr;
 use turbo_tasks::Vc;
 use turbo_tasks_fs::{
     DiskFileSystem, FileContent, FileSystemPath, rope::Rope, util::uri_from_file,
@@ -12,16 +13,21 @@ use turbo_tasks_fs::{
 
 use crate::SOURCE_URL_PROTOCOL;
 
+/// Whether a source should be part of the `ignoreList` by default, i.e. if it's runtime code or
+/// a third-party dependency.
+fn is_ignored_by_default(source: &str) -> bool {
+    source.starts_with(concatcp!(SOURCE_URL_PROTOCOL, "///[next]"))
+        || source.starts_with(concatcp!(SOURCE_URL_PROTOCOL, "///[turbopack]"))
+        || source.contains("/node_modules/")
+        || source.ends_with("__nextjs-internal-proxy.cjs")
+        || source.ends_with("__nextjs-internal-proxy.mjs")
+}
+
 pub fn add_default_ignore_list(map: &mut swc_sourcemap::SourceMap) {
     let mut ignored_ids = HashSet::new();
 
     for (source_id, source) in map.sources().enumerate() {
-        if source.starts_with(concatcp!(SOURCE_URL_PROTOCOL, "///[next]"))
-            || source.starts_with(concatcp!(SOURCE_URL_PROTOCOL, "///[turbopack]"))
-            || source.contains("/node_modules/")
-            || source.ends_with("__nextjs-internal-proxy.cjs")
-            || source.ends_with("__nextjs-internal-proxy.mjs")
-        {
+        if is_ignored_by_default(source) {
             ignored_ids.insert(source_id);
         }
     }
@@ -34,7 +40,7 @@ pub fn add_default_ignore_list(map: &mut swc_sourcemap::SourceMap) {
 #[derive(Serialize, Deserialize)]
 struct SourceMapSectionOffsetJson {
     line: u32,
-    offset: u32,
+    column: u32,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -60,11 +66,14 @@ struct SourceMapJson {
     sources_content: Option<Vec<Option<String>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     names: Option<Vec<String>>,
-    mappings: String,
+    // Index maps have `sections` instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mappings: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     ignore_list: Option<Vec<u32>>,
 
     // A somewhat widespread non-standard extension
+    #[serde(skip_serializing_if = "Option::is_none")]
     debug_id: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -203,3 +212,170 @@ pub async fn fileify_source_map(
 
     Ok(Some(map))
 }
+
+/// Formats a content hash as a debug ID, which is a UUID (see
+/// https://github.com/tc39/ecma426/blob/main/proposals/debug-id.md). The hash is spread over both halves of the UUID and the version and variant
+/// bits are set, so the result is a valid (if not random) v4 UUID.
+pub fn debug_id_from_hash(hash: u64) -> RcStr {
+    let mut uuid = (((hash as u128) << 64) | hash.rotate_left(32) as u128).to_be_bytes();
+    uuid[6] = (uuid[6] & 0x0f) | 0x40;
+    uuid[8] = (uuid[8] & 0x3f) | 0x80;
+    let hex = uuid.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+    .into()
+}
+
+/// Cheaply checks whether any source of the stringified `map` could be ignored by default (see
+/// [`is_ignored_by_default`]), so that maps without such sources don't need to be parsed.
+fn may_contain_ignored_sources(map: &Rope) -> Result<bool> {
+    let map = map.to_str()?;
+    Ok([
+        "[next]",
+        "[turbopack]",
+        "node_modules",
+        "__nextjs-internal-proxy",
+    ]
+    .iter()
+    .any(|marker| map.contains(marker)))
+}
+
+/// Post-processes the source map of a chunk: Adds runtime code and third-party dependencies to the
+/// `ignoreList` of the map and each of its sections, sets the `debugId` and strips the
+/// `sourcesContent` if requested. Source maps that have been passed through without being decoded
+/// (e.g. after minification) don't carry an `ignoreList` otherwise. The map is returned unchanged,
+/// and without being parsed if possible, when there is nothing to add or strip.
+pub fn finalize_chunk_source_map(
+    map: &Rope,
+    debug_id: Option<&str>,
+    sources_content: bool,
+) -> Result<Rope> {
+    /// Returns whether the map was changed.
+    fn finalize_map(map: &mut SourceMapJson, sources_content: bool) -> bool {
+        let mut changed = false;
+        if !sources_content {
+            changed |= map.sources_content.take().is_some();
+        }
+        let Some(sources) = &map.sources else {
+            return changed;
+        };
+        let mut ignore_list = map.ignore_list.take().unwrap_or_default();
+        for (source_id, source) in sources.iter().enumerate() {
+            if let Some(source) = source
+                && is_ignored_by_default(source)
+                && !ignore_list.contains(&(source_id as u32))
+            {
+                ignore_list.push(source_id as u32);
+                changed = true;
+            }
+        }
+        if !ignore_list.is_empty() {
+            map.ignore_list = Some(ignore_list);
+        }
+        changed
+    }
+
+    let original = map;
+    if debug_id.is_none() && sources_content && !may_contain_ignored_sources(original)? {
+        return Ok(original.clone());
+    }
+    let Ok(mut map): serde_json::Result<SourceMapJson> = serde_json::from_reader(original.read())
+    else {
+        // Silently ignore invalid sourcemaps
+        return Ok(original.clone());
+    };
+
+    let mut changed = finalize_map(&mut map, sources_content);
+    for section in map.sections.iter_mut().flatten() {
+        changed |= finalize_map(&mut section.map, sources_content);
+    }
+    if let Some(debug_id) = debug_id {
+        map.debug_id = Some(debug_id.to_string());
+        changed = true;
+    }
+
+    if !changed {
+        return Ok(original.clone());
+    }
+    Ok(Rope::from(serde_json::to_vec(&map)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use turbo_tasks_fs::rope::Rope;
+
+    use super::{debug_id_from_hash, finalize_chunk_source_map};
+
+    #[test]
+    fn test_debug_id_from_hash() {
+        let debug_id = debug_id_from_hash(0x0123_4567_89ab_cdef);
+        assert_eq!(&*debug_id, "01234567-89ab-4def-89ab-cdef01234567");
+        assert_eq!(debug_id_from_hash(0x0123_4567_89ab_cdef), debug_id);
+        assert_ne!(debug_id_from_hash(0xfedc_ba98_7654_3210), debug_id);
+    }
+
+    #[test]
+    fn test_finalize_chunk_source_map() {
+        let map = Rope::from(
+            r#"{
+  "version": 3,
+  "sources": [],
+  "sections": [
+    {"offset": {"line": 0, "column": 0}, "map": {"version":3,"sources":["turbopack:///[turbopack]/runtime.ts","turbopack:///[project]/src/index.js","turbopack:///[project]/node_modules/foo/index.js"],"sourcesContent":["a","b","c"],"mappings":"AAAA"}}]
+}"#,
+        );
+
+        let finalized =
+            finalize_chunk_source_map(&map, Some("01234567-89ab-4def-89ab-cdef01234567"), true)
+                .unwrap();
+        let finalized: serde_json::Value = serde_json::from_reader(finalized.read()).unwrap();
+        assert_eq!(finalized["debugId"], "01234567-89ab-4def-89ab-cdef01234567");
+        assert_eq!(finalized.get("ignoreList"), None);
+        assert_eq!(
+            finalized["sections"][0]["offset"],
+            serde_json::json!({"line": 0, "column": 0})
+        );
+        assert_eq!(
+            finalized["sections"][0]["map"]["ignoreList"],
+            serde_json::json!([0, 2])
+        );
+        assert_eq!(
+            finalized["sections"][0]["map"]["sourcesContent"],
+            serde_json::json!(["a", "b", "c"])
+        );
+
+        let finalized = finalize_chunk_source_map(&map, None, false).unwrap();
+        let finalized: serde_json::Value = serde_json::from_reader(finalized.read()).unwrap();
+        assert_eq!(finalized.get("debugId"), None);
+        assert_eq!(finalized["sections"][0]["map"].get("sourcesContent"), None);
+
+        // Without a debug ID and with sources content, the ignore list is still filled in
+        let finalized = finalize_chunk_source_map(&map, None, true).unwrap();
+        let finalized: serde_json::Value = serde_json::from_reader(finalized.read()).unwrap();
+        assert_eq!(
+            finalized["sections"][0]["map"]["ignoreList"],
+            serde_json::json!([0, 2])
+        );
+
+        // Maps that don't need any changes are passed through as they are
+        let map = Rope::from(
+            r#"{"version":3,"sources":["turbopack:///[project]/src/index.js"],"mappings":"AAAA"}"#,
+        );
+        let finalized = finalize_chunk_source_map(&map, None, true).unwrap();
+        assert_eq!(finalized.to_str().unwrap(), map.to_str().unwrap());
+
+        // ...and aren't even parsed when none of their sources could be ignored
+        let map = Rope::from("not a source map");
+        let finalized = finalize_chunk_source_map(&map, None, true).unwrap();
+        assert_eq!(finalized.to_str().unwrap(), "not a source map");
+        // Invalid maps are passed through when they would have to be parsed, too
+        let finalized = finalize_chunk_source_map(&map, None, false).unwrap();
+        assert_eq!(finalized.to_str().unwrap(), "not a source map");
+    }
+}
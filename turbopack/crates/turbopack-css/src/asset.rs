@@ -322,7 +322,10 @@ impl CssChunkItem for CssModuleChunkItem {
 
         let result = self
             .module
-            .finalize_css(*chunking_context, *chunking_context.minify_type().await?)
+            .finalize_css(
+                *chunking_context,
+                chunking_context.minify_type().owned().await?,
+            )
             .await?;
 
         if let FinalCssResult::Ok {
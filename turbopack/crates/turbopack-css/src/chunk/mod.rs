@@ -9,10 +9,7 @@ use turbo_rcstr::{RcStr, rcstr};
 use turbo_tasks::{
     FxIndexSet, ResolvedVc, TryFlatJoinIterExt, TryJoinIterExt, ValueDefault, ValueToString, Vc,
 };
-use turbo_tasks_fs::{
-    File, FileSystem, FileSystemPath,
-    rope::{Rope, RopeBuilder},
-};
+use turbo_tasks_fs::{FileSystem, FileSystemPath, rope::Rope};
 use turbopack_core::{
     asset::{Asset, AssetContent},
     chunk::{
@@ -21,7 +18,7 @@ use turbopack_core::{
         ChunkableModule, ChunkingContext, MinifyType, OutputChunk, OutputChunkRuntimeInfo,
         round_chunk_item_size,
     },
-    code_builder::{Code, CodeBuilder},
+    code_builder::{Code, CodeBuilder, FooterCommentStyle},
     ident::AssetIdent,
     introspect::{
         Introspectable, IntrospectableChildren,
@@ -130,24 +127,12 @@ impl CssChunk {
 
     #[turbo_tasks::function]
     async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
-        let code = self.code().await?;
-
-        let rope = if code.has_source_map() {
-            use std::io::Write;
-            let mut rope_builder = RopeBuilder::default();
-            rope_builder.concat(code.source_code());
-            let source_map_path = CssChunkSourceMapAsset::new(self).path().await?;
-            write!(
-                rope_builder,
-                "/*# sourceMappingURL={}*/",
-                urlencoding::encode(source_map_path.file_name())
-            )?;
-            rope_builder.build()
-        } else {
-            code.source_code().clone()
-        };
-
-        Ok(AssetContent::file(File::from(rope).into()))
+        let this = self.await?;
+        Ok(self.code().chunk_file_content(
+            *this.chunking_context,
+            Vc::upcast(CssChunkSourceMapAsset::new(self)),
+            FooterCommentStyle::Css,
+        ))
     }
 
     #[turbo_tasks::function]
@@ -392,8 +377,11 @@ impl Asset for CssChunk {
 #[turbo_tasks::value_impl]
 impl GenerateSourceMap for CssChunk {
     #[turbo_tasks::function]
-    fn generate_source_map(self: Vc<Self>) -> Vc<OptionStringifiedSourceMap> {
-        self.code().generate_source_map()
+    async fn generate_source_map(self: Vc<Self>) -> Result<Vc<OptionStringifiedSourceMap>> {
+        let this = self.await?;
+        Ok(self
+            .code()
+            .generate_chunk_source_map(*this.chunking_context))
     }
 }
 
@@ -506,6 +494,8 @@ impl ChunkType for CssChunkType {
         chunk_items_or_batches: Vec<ChunkItemOrBatchWithAsyncModuleInfo>,
         _batch_groups: Vec<ResolvedVc<ChunkItemBatchGroup>>,
         referenced_output_assets: ResolvedVc<OutputAssets>,
+        // Cache groups only apply to JavaScript
+        _name: Option<RcStr>,
     ) -> Result<Vc<Box<dyn Chunk>>> {
         let mut chunk_items = Vec::new();
         // TODO operate with batches
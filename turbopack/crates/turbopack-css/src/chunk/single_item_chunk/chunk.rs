@@ -3,11 +3,11 @@ use std::fmt::Write;
 use anyhow::Result;
 use turbo_rcstr::{RcStr, rcstr};
 use turbo_tasks::{ResolvedVc, ValueToString, Vc};
-use turbo_tasks_fs::{File, FileSystemPath, rope::RopeBuilder};
+use turbo_tasks_fs::FileSystemPath;
 use turbopack_core::{
     asset::{Asset, AssetContent},
     chunk::{Chunk, ChunkItem, ChunkingContext, MinifyType},
-    code_builder::{Code, CodeBuilder},
+    code_builder::{Code, CodeBuilder, FooterCommentStyle},
     ident::AssetIdent,
     introspect::Introspectable,
     output::{OutputAsset, OutputAssets},
@@ -130,32 +130,23 @@ impl OutputAsset for SingleItemCssChunk {
 impl Asset for SingleItemCssChunk {
     #[turbo_tasks::function]
     async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
-        let code = self.code().await?;
-
-        let rope = if code.has_source_map() {
-            use std::io::Write;
-            let mut rope_builder = RopeBuilder::default();
-            rope_builder.concat(code.source_code());
-            let source_map_path = SingleItemCssChunkSourceMapAsset::new(self).path().await?;
-            write!(
-                rope_builder,
-                "\n/*# sourceMappingURL={}*/",
-                urlencoding::encode(source_map_path.file_name())
-            )?;
-            rope_builder.build()
-        } else {
-            code.source_code().clone()
-        };
-
-        Ok(AssetContent::file(File::from(rope).into()))
+        let this = self.await?;
+        Ok(self.code().chunk_file_content(
+            *this.chunking_context,
+            Vc::upcast(SingleItemCssChunkSourceMapAsset::new(self)),
+            FooterCommentStyle::Css,
+        ))
     }
 }
 
 #[turbo_tasks::value_impl]
 impl GenerateSourceMap for SingleItemCssChunk {
     #[turbo_tasks::function]
-    fn generate_source_map(self: Vc<Self>) -> Vc<OptionStringifiedSourceMap> {
-        self.code().generate_source_map()
+    async fn generate_source_map(self: Vc<Self>) -> Result<Vc<OptionStringifiedSourceMap>> {
+        let this = self.await?;
+        Ok(self
+            .code()
+            .generate_chunk_source_map(*this.chunking_context))
     }
 }
 
@@ -71,6 +71,22 @@ function loadRuntimeChunk(sourcePath: ChunkPath, chunkData: ChunkData): void {
   }
 }
 
+/**
+ * Installs the module factories of a chunk that was imported statically by an
+ * ES module entry chunk.
+ */
+function installRuntimeChunk(
+  chunkPath: ChunkPath,
+  chunkModules: CompressedModuleFactories
+): void {
+  if (loadedChunks.has(chunkPath)) {
+    return
+  }
+
+  installCompressedModuleFactories(chunkModules, 0, moduleFactories)
+  loadedChunks.add(chunkPath)
+}
+
 const loadedChunks = new Set<ChunkPath>()
 const unsupportedLoadChunk = Promise.resolve(undefined)
 const loadedChunk: Promise<void> = Promise.resolve(undefined)
@@ -124,7 +140,22 @@ function loadChunkAsync(
   }
 
   let entry = chunkCache.get(chunkPath)
-  if (entry === undefined) {
+  if (entry === undefined && isEsm(chunkPath)) {
+    // ES module chunks can't be required, they are loaded asynchronously instead.
+    const resolved = path.resolve(RUNTIME_ROOT, chunkPath)
+    const errorMessage = `Failed to load chunk ${chunkPath} from module ${this.m.id}`
+    entry = import(url.pathToFileURL(resolved).href).then(
+      (chunk: { default: CompressedModuleFactories }) => {
+        installCompressedModuleFactories(chunk.default, 0, moduleFactories)
+      },
+      (e) => {
+        throw new Error(errorMessage, {
+          cause: e,
+        })
+      }
+    )
+    chunkCache.set(chunkPath, entry)
+  } else if (entry === undefined) {
     try {
       // resolve to an absolute path to simplify `require` handling
       const resolved = path.resolve(RUNTIME_ROOT, chunkPath)
@@ -290,15 +321,25 @@ function getOrInstantiateRuntimeModule(
   return instantiateRuntimeModule(chunkPath, moduleId)
 }
 
-const regexJsUrl = /\.js(?:\?[^#]*)?(?:#.*)?$/
+const regexJsUrl = /\.m?js(?:\?[^#]*)?(?:#.*)?$/
 /**
- * Checks if a given path/URL ends with .js, optionally followed by ?query or #fragment.
+ * Checks if a given path/URL ends with .js or .mjs, optionally followed by ?query or #fragment.
  */
 function isJs(chunkUrlOrPath: ChunkUrl | ChunkPath): boolean {
   return regexJsUrl.test(chunkUrlOrPath)
 }
 
+const regexEsmUrl = /\.mjs(?:\?[^#]*)?(?:#.*)?$/
+/**
+ * Checks if a given path/URL ends with .mjs, optionally followed by ?query or #fragment.
+ */
+function isEsm(chunkUrlOrPath: ChunkUrl | ChunkPath): boolean {
+  return regexEsmUrl.test(chunkUrlOrPath)
+}
+
 module.exports = (sourcePath: ChunkPath) => ({
   m: (id: ModuleId) => getOrInstantiateRuntimeModule(sourcePath, id),
   c: (chunkData: ChunkData) => loadRuntimeChunk(sourcePath, chunkData),
+  i: (chunkPath: ChunkPath, chunkModules: CompressedModuleFactories) =>
+    installRuntimeChunk(chunkPath, chunkModules),
 })
@@ -261,6 +261,15 @@ pub struct VarGraph {
     pub free_var_ids: FxHashMap<Atom, Id>,
 
     pub effects: Vec<Effect>,
+
+    /// The arguments of all calls of local functions that are only ever called directly, keyed by
+    /// function. These are used as the possible values of the parameters when the body of such a
+    /// function is evaluated outside of a call.
+    pub call_site_args: FxHashMap<u32, Vec<Vec<JsValue>>>,
+
+    /// The default values of function parameters, keyed by function and parameter index. They are
+    /// used when a call doesn't pass the argument.
+    pub param_defaults: FxHashMap<(u32, usize), JsValue>,
 }
 
 impl VarGraph {
@@ -268,6 +277,12 @@ impl VarGraph {
         for value in self.values.values_mut() {
             value.normalize();
         }
+        for value in self.call_site_args.values_mut().flatten().flatten() {
+            value.normalize();
+        }
+        for value in self.param_defaults.values_mut() {
+            value.normalize();
+        }
         for effect in self.effects.iter_mut() {
             effect.normalize();
         }
@@ -281,6 +296,8 @@ pub fn create_graph(m: &Program, eval_context: &EvalContext) -> VarGraph {
         values: Default::default(),
         free_var_ids: Default::default(),
         effects: Default::default(),
+        call_site_args: Default::default(),
+        param_defaults: Default::default(),
     };
 
     m.visit_with_ast_path(
@@ -294,6 +311,8 @@ pub fn create_graph(m: &Program, eval_context: &EvalContext) -> VarGraph {
             var_decl_kind: Default::default(),
             cur_fn_return_values: Default::default(),
             cur_fn_ident: Default::default(),
+            var_calls: Default::default(),
+            escaped_vars: Default::default(),
         },
         &mut Default::default(),
     );
@@ -773,6 +792,32 @@ enum EarlyReturn {
     },
 }
 
+/// Functions with more calls than this are not evaluated with the arguments of their calls.
+const MAX_CALL_SITES: usize = 10;
+
+/// Returns true when the [Ident] at the end of `ast_path` declares a binding or is the callee of a
+/// call, which are the only uses of a function that allow to know all of its calls.
+fn is_declaration_or_callee(ast_path: &AstNodePath<AstParentNodeRef<'_>>) -> bool {
+    let mut kinds = ast_path.iter().rev().map(|n| n.kind());
+    match kinds.next() {
+        Some(
+            AstParentKind::BindingIdent(BindingIdentField::Id)
+            | AstParentKind::FnDecl(FnDeclField::Ident)
+            | AstParentKind::FnExpr(FnExprField::Ident)
+            | AstParentKind::ClassDecl(ClassDeclField::Ident)
+            | AstParentKind::ClassExpr(ClassExprField::Ident),
+        ) => true,
+        Some(AstParentKind::Expr(ExprField::Ident)) => matches!(
+            (kinds.next(), kinds.next()),
+            (
+                Some(AstParentKind::Callee(CalleeField::Expr)),
+                Some(AstParentKind::CallExpr(CallExprField::Callee))
+            )
+        ),
+        _ => false,
+    }
+}
+
 pub fn as_parent_path_skip(
     ast_path: &AstNodePath<AstParentNodeRef<'_>>,
     skip: usize,
@@ -803,6 +848,13 @@ struct Analyzer<'a> {
     cur_fn_return_values: Option<Vec<JsValue>>,
 
     cur_fn_ident: u32,
+
+    /// The arguments of the direct calls of local variables.
+    var_calls: FxHashMap<Id, Vec<Vec<JsValue>>>,
+
+    /// Local variables that are used in other ways than declaring or directly calling them, so
+    /// that not all of their calls are known.
+    escaped_vars: FxHashSet<Id>,
 }
 
 mod analyzer_state {
@@ -925,6 +977,44 @@ impl Analyzer<'_> {
         self.effects.push(effect);
     }
 
+    fn add_var_call(&mut self, id: Id, args: &[EffectArg]) {
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                EffectArg::Value(value) | EffectArg::Closure(value, _) => Some(value.clone()),
+                EffectArg::Spread => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        match args {
+            Some(args) => self.var_calls.entry(id).or_default().push(args),
+            // The arguments of calls with spread arguments are unknown
+            None => {
+                self.escaped_vars.insert(id);
+            }
+        }
+    }
+
+    /// Stores the arguments of the calls of all local functions that are only called directly, as
+    /// all of their calls are known then.
+    fn add_call_site_args(&mut self) {
+        for (id, calls) in take(&mut self.var_calls) {
+            if calls.len() > MAX_CALL_SITES
+                || self.escaped_vars.contains(&id)
+                || self.eval_context.imports.exports.values().any(|e| e == &id)
+            {
+                continue;
+            }
+            let mut value = self.data.values.get(&id);
+            // Function and arrow expressions are stored in a separate variable
+            if let Some(JsValue::Variable(inner)) = value {
+                value = self.data.values.get(inner);
+            }
+            if let Some(JsValue::Function(_, func_ident, _)) = value {
+                self.data.call_site_args.insert(*func_ident, calls);
+            }
+        }
+    }
+
     fn check_iife<'ast: 'r, 'r>(
         &mut self,
         n: &'ast CallExpr,
@@ -1241,6 +1331,9 @@ impl Analyzer<'_> {
                     });
                 } else {
                     let fn_value = Box::new(self.eval_context.eval(expr));
+                    if !new && let JsValue::Variable(id) = &*fn_value {
+                        self.add_var_call(id.clone(), &args);
+                    }
                     self.add_effect(Effect::Call {
                         func: fn_value,
                         args,
@@ -1443,9 +1536,9 @@ impl VisitAstPath for Analyzer<'_> {
         n: &'ast CallExpr,
         ast_path: &mut AstNodePath<AstParentNodeRef<'r>>,
     ) {
-        // We handle `define(function (require) {})` here.
+        // We handle `define(function (require) {})` and `define(['require'], function (require)
+        // {})` here.
         if let Callee::Expr(callee) = &n.callee
-            && n.args.len() == 1
             && let Some(require_var_id) = extract_var_from_umd_factory(callee, &n.args)
         {
             self.add_value(
@@ -1925,6 +2018,29 @@ impl VisitAstPath for Analyzer<'_> {
                 self.handle_object_pat_with_value(obj, value, &mut ast_path);
             }
 
+            // A parameter with a default value, the linker falls back to the default when the
+            // argument is not passed
+            Pat::Assign(assign @ AssignPat { left, right, .. }) => {
+                let Some(JsValue::Argument(func_ident, index)) = value else {
+                    pat.visit_children_with_ast_path(self, ast_path);
+                    return;
+                };
+                let mut ast_path =
+                    ast_path.with_guard(AstParentNodeRef::Pat(pat, PatField::Assign));
+                let default = self.eval_context.eval(right);
+                self.data
+                    .param_defaults
+                    .insert((func_ident, index), default);
+                self.with_pat_value(Some(JsValue::Argument(func_ident, index)), |this| {
+                    let mut ast_path = ast_path
+                        .with_guard(AstParentNodeRef::AssignPat(assign, AssignPatField::Left));
+                    left.visit_with_ast_path(this, &mut ast_path);
+                });
+                let mut ast_path =
+                    ast_path.with_guard(AstParentNodeRef::AssignPat(assign, AssignPatField::Right));
+                right.visit_with_ast_path(self, &mut ast_path);
+            }
+
             _ => pat.visit_children_with_ast_path(self, ast_path),
         }
     }
@@ -2014,6 +2130,8 @@ impl VisitAstPath for Analyzer<'_> {
                 span: ident.span(),
                 in_try: is_in_try(ast_path),
             })
+        } else if !is_declaration_or_callee(ast_path) {
+            self.escaped_vars.insert(ident.to_id());
         }
     }
 
@@ -2075,6 +2193,7 @@ impl VisitAstPath for Analyzer<'_> {
         self.end_early_return_block();
         self.effects.append(&mut self.hoisted_effects);
         self.data.effects = take(&mut self.effects);
+        self.add_call_site_args();
     }
 
     fn visit_cond_expr<'ast: 'r, 'r>(
@@ -2600,17 +2719,35 @@ impl Analyzer<'_> {
 
 fn extract_var_from_umd_factory(callee: &Expr, args: &[ExprOrSpread]) -> Option<Id> {
     match unparen(callee) {
-        Expr::Ident(Ident { sym, .. }) => {
-            if &**sym == "define"
-                && let Expr::Fn(FnExpr { function, .. }) = &*args[0].expr
-            {
-                let params = &*function.params;
-                if params.len() == 1
-                    && let Pat::Ident(param) = &params[0].pat
-                    && &*param.id.sym == "require"
-                {
-                    return Some(param.to_id());
+        Expr::Ident(Ident { sym, .. }) if &**sym == "define" => {
+            let (deps, factory) = match args {
+                [factory] => (None, factory),
+                [deps, factory] | [_, deps, factory] if deps.expr.is_array() => {
+                    (deps.expr.as_array(), factory)
                 }
+                [_, factory] => (None, factory),
+                _ => return None,
+            };
+            // Without dependencies, the factory is called with `require`, `exports` and `module`
+            let index = match deps {
+                Some(deps) => deps.elems.iter().position(|elem| {
+                    matches!(
+                        elem,
+                        Some(ExprOrSpread {
+                            spread: None,
+                            expr: box Expr::Lit(Lit::Str(s)),
+                        }) if s.value == "require"
+                    )
+                })?,
+                None => 0,
+            };
+            let param = match unparen(&factory.expr) {
+                Expr::Fn(FnExpr { function, .. }) => &function.params.get(index)?.pat,
+                Expr::Arrow(ArrowExpr { params, .. }) => params.get(index)?,
+                _ => return None,
+            };
+            if let Pat::Ident(param) = param {
+                return Some(param.to_id());
             }
         }
 
@@ -2623,6 +2760,7 @@ fn extract_var_from_umd_factory(callee: &Expr, args: &[ExprOrSpread]) -> Option<
         Expr::Fn(FnExpr { function, .. }) => {
             let params = &*function.params;
             if params.len() == 1
+                && args.len() == 1
                 && let Some(FnExpr { function, .. }) =
                     args.first().and_then(|arg| arg.expr.as_fn_expr())
             {
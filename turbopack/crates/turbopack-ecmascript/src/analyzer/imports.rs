@@ -29,6 +29,8 @@ pub struct ImportAnnotations {
     // TODO store this in more structured way
     #[turbo_tasks(trace_ignore)]
     map: BTreeMap<Atom, Atom>,
+    /// Whether this is a source phase import (`import source x from "..."`)
+    source_phase: bool,
 }
 
 /// Enables a specified transition for the annotated import
@@ -72,7 +74,10 @@ impl ImportAnnotations {
             map.insert(key.into(), value.value.as_str().into());
         }
 
-        ImportAnnotations { map }
+        ImportAnnotations {
+            map,
+            source_phase: false,
+        }
     }
 
     pub fn parse_dynamic(with: &JsValue) -> Option<ImportAnnotations> {
@@ -97,7 +102,10 @@ impl ImportAnnotations {
             map.insert(key.as_str().into(), value.as_str().into());
         }
 
-        Some(ImportAnnotations { map })
+        Some(ImportAnnotations {
+            map,
+            source_phase: false,
+        })
     }
 
     /// Returns the content on the transition annotation
@@ -115,6 +123,12 @@ impl ImportAnnotations {
         self.get(&ATTRIBUTE_MODULE_TYPE)
     }
 
+    /// Whether this is a source phase import (`import source x from "..."`), which imports the
+    /// compiled source of the module instead of its exports.
+    pub fn is_source_phase(&self) -> bool {
+        self.source_phase
+    }
+
     pub fn get(&self, key: &Atom) -> Option<&str> {
         self.map.get(key).map(|w| w.as_str())
     }
@@ -462,11 +476,13 @@ impl Visit for Analyzer<'_> {
     fn visit_import_decl(&mut self, import: &ImportDecl) {
         self.data.has_imports = true;
 
-        let annotations = ImportAnnotations::parse(import.with.as_deref());
+        let mut annotations = ImportAnnotations::parse(import.with.as_deref());
+        // Source phase imports don't evaluate the imported module
+        annotations.source_phase = import.phase == ImportPhase::Source;
 
         let internal_symbol = parse_with(import.with.as_deref());
 
-        if internal_symbol.is_none() {
+        if internal_symbol.is_none() && !annotations.source_phase {
             self.ensure_reference(
                 import.span,
                 import.src.value.clone(),
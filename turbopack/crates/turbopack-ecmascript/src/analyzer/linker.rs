@@ -5,7 +5,7 @@ use parking_lot::Mutex;
 use rustc_hash::{FxHashMap, FxHashSet};
 use swc_core::ecma::ast::Id;
 
-use super::{JsValue, graph::VarGraph};
+use super::{ConstantValue, JsValue, graph::VarGraph};
 
 pub async fn link<'a, B, RB, F, RF>(
     graph: &VarGraph,
@@ -34,6 +34,16 @@ where
     Ok((val, steps))
 }
 
+/// Whether a parameter with a default value is set to the default for the argument `arg`, which is
+/// the case when the argument is not passed or `undefined`.
+fn uses_default(arg: Option<&JsValue>) -> bool {
+    match arg {
+        None | Some(JsValue::Constant(ConstantValue::Undefined)) => true,
+        Some(JsValue::FreeVar(name)) => &**name == "undefined",
+        Some(_) => false,
+    }
+}
+
 const LIMIT_NODE_SIZE: u32 = 100;
 const LIMIT_IN_PROGRESS_NODES: u32 = 500;
 const LIMIT_LINK_STEPS: u32 = 1500;
@@ -53,6 +63,9 @@ enum Step {
     EarlyVisit(JsValue),
     /// Remove the call from `fun_args_values`
     LeaveCall(u32),
+    /// Remove the function from `call_site_stack` which detects recursive functions when
+    /// evaluating arguments from the call sites
+    LeaveCallSites(u32),
     /// Placeholder that is used to momentarily reserve a slot that is only filled after
     /// pushing some more steps
     TemporarySlot,
@@ -68,6 +81,7 @@ impl Display for Step {
             Step::LeaveLate(val) => write!(f, "LeaveLate({val})"),
             Step::Visit(val) => write!(f, "Visit({val})"),
             Step::LeaveCall(func_ident) => write!(f, "LeaveCall({func_ident})"),
+            Step::LeaveCallSites(func_ident) => write!(f, "LeaveCallSites({func_ident})"),
             Step::TemporarySlot => write!(f, "TemporarySlot"),
         }
     }
@@ -93,6 +107,7 @@ where
     // Tracks the number of nodes in the queue and done combined
     let mut total_nodes = 0;
     let mut cycle_stack: FxHashSet<Id> = FxHashSet::default();
+    let mut call_site_stack: FxHashSet<u32> = FxHashSet::default();
     // Tracks the number linking steps so far
     let mut steps = 0;
 
@@ -149,7 +164,14 @@ where
             Step::Enter(JsValue::Argument(func_ident, index)) => {
                 total_nodes -= 1;
                 if let Some(args) = fun_args_values.lock().get(&func_ident) {
-                    if let Some(val) = args.get(index) {
+                    let arg = args.get(index);
+                    if let Some(default) = graph.param_defaults.get(&(func_ident, index))
+                        && uses_default(arg)
+                    {
+                        // Unlike the arguments, the default value hasn't been linked yet
+                        total_nodes += default.total_nodes();
+                        work_queue_stack.push(Step::Enter(default.clone()));
+                    } else if let Some(val) = arg {
                         total_nodes += val.total_nodes();
                         done.push(val.clone());
                     } else {
@@ -159,6 +181,28 @@ where
                             "unknown function argument (out of bounds)",
                         ));
                     }
+                } else if let Some(calls) = graph.call_site_args.get(&func_ident)
+                    && !call_site_stack.contains(&func_ident)
+                {
+                    // Outside of a call the argument can have the value of any of the calls
+                    let val = JsValue::alternatives(
+                        calls
+                            .iter()
+                            .map(|args| {
+                                let arg = args.get(index);
+                                match graph.param_defaults.get(&(func_ident, index)) {
+                                    Some(default) if uses_default(arg) => default.clone(),
+                                    _ => arg
+                                        .cloned()
+                                        .unwrap_or(JsValue::Constant(ConstantValue::Undefined)),
+                                }
+                            })
+                            .collect(),
+                    );
+                    call_site_stack.insert(func_ident);
+                    work_queue_stack.push(Step::LeaveCallSites(func_ident));
+                    total_nodes += val.total_nodes();
+                    work_queue_stack.push(Step::Enter(val));
                 } else {
                     total_nodes += 1;
                     done.push(JsValue::unknown(
@@ -203,6 +247,11 @@ where
             Step::LeaveCall(func_ident) => {
                 fun_args_values.lock().remove(&func_ident);
             }
+            // Leaving the evaluation of a function argument from the call sites
+            // - remove the function from the stack
+            Step::LeaveCallSites(func_ident) => {
+                call_site_stack.remove(&func_ident);
+            }
             // Enter a function
             // We don't want to process the function return value yet, this will happen after
             // function calls
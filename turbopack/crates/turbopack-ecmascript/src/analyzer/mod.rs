@@ -1843,6 +1843,7 @@ impl JsValue {
                     WellKnownFunctionKind::RequireContextRequire(..) => ("require.context(...)".to_string(), "The require.context(...) method from webpack: https://webpack.js.org/api/module-methods/#requirecontext"),
                     WellKnownFunctionKind::RequireContextRequireKeys(..) => ("require.context(...).keys".to_string(), "The require.context(...).keys method from webpack: https://webpack.js.org/guides/dependency-management/#requirecontext"),
                     WellKnownFunctionKind::RequireContextRequireResolve(..) => ("require.context(...).resolve".to_string(), "The require.context(...).resolve method from webpack: https://webpack.js.org/guides/dependency-management/#requirecontext"),
+                    WellKnownFunctionKind::ImportMetaGlob => ("import.meta.glob".to_string(), "The import.meta.glob method from Vite: https://vite.dev/guide/features.html#glob-import"),
                     WellKnownFunctionKind::Define => ("define".to_string(), "The define method from AMD"),
                     WellKnownFunctionKind::FsReadMethod(name) => (
                         format!("fs.{name}"),
@@ -3804,6 +3805,150 @@ pub fn parse_require_context(args: &[JsValue]) -> Result<RequireContextOptions>
     })
 }
 
+#[derive(Debug, Clone)]
+pub struct ImportMetaGlobOptions {
+    /// The glob patterns, negated patterns start with `!`
+    pub patterns: Vec<RcStr>,
+    pub eager: bool,
+    /// The export to select from the matched modules, the whole namespace when `None`
+    pub import: Option<RcStr>,
+    /// The query appended to the requests of the matched modules, including the leading `?`
+    pub query: Option<RcStr>,
+    /// Whether files in `node_modules` are matched
+    pub exhaustive: bool,
+}
+
+/// Parse the arguments passed to an import.meta.glob invocation, validate them
+/// and convert them to the appropriate rust values.
+///
+/// https://vite.dev/guide/features.html#glob-import
+pub fn parse_import_meta_glob(args: &[JsValue]) -> Result<ImportMetaGlobOptions> {
+    if !(1..=2).contains(&args.len()) {
+        bail!("import.meta.glob() only supports 1-2 arguments");
+    }
+
+    let patterns = match &args[0] {
+        JsValue::Array { items, .. } => items
+            .iter()
+            .map(|item| item.as_str().map(RcStr::from))
+            .collect::<Option<Vec<_>>>(),
+        pattern => pattern.as_str().map(|pattern| vec![pattern.into()]),
+    };
+    let Some(patterns) = patterns else {
+        bail!(
+            "import.meta.glob(patterns, ...) requires patterns to be a constant string or an \
+             array of constant strings"
+        );
+    };
+    let mut has_positive_pattern = false;
+    for pattern in &patterns {
+        if pattern.starts_with('!') {
+            continue;
+        }
+        if !(pattern.starts_with("./") || pattern.starts_with("../") || pattern.starts_with('/')) {
+            bail!(
+                "import.meta.glob(patterns, ...) requires patterns to be relative (`./`) or \
+                 absolute (`/`), but got `{pattern}`"
+            );
+        }
+        has_positive_pattern = true;
+    }
+    if !has_positive_pattern {
+        bail!("import.meta.glob(patterns, ...) requires at least one pattern that isn't negated");
+    }
+
+    let mut options = ImportMetaGlobOptions {
+        patterns,
+        eager: false,
+        import: None,
+        query: None,
+        exhaustive: false,
+    };
+
+    let Some(arg) = args.get(1) else {
+        return Ok(options);
+    };
+    let JsValue::Object { parts, .. } = arg else {
+        bail!("import.meta.glob(..., options) requires options to be a constant object");
+    };
+    for part in parts {
+        let ObjectPart::KeyValue(key, value) = part else {
+            bail!("import.meta.glob(..., options) doesn't support spreading options");
+        };
+        match key.as_str() {
+            Some("eager") => {
+                let Some(eager) = value.as_bool() else {
+                    bail!(
+                        "import.meta.glob(..., {{ eager }}) requires eager to be a constant \
+                         boolean"
+                    );
+                };
+                options.eager = eager;
+            }
+            Some("import") => {
+                let Some(import) = value.as_str() else {
+                    bail!(
+                        "import.meta.glob(..., {{ import }}) requires import to be a constant \
+                         string"
+                    );
+                };
+                options.import = Some(import.into());
+            }
+            Some("query") => {
+                let Some(query) = import_meta_glob_query(value) else {
+                    bail!(
+                        "import.meta.glob(..., {{ query }}) requires query to be a constant \
+                         string or an object of constant values"
+                    );
+                };
+                options.query = query;
+            }
+            Some("exhaustive") => {
+                let Some(exhaustive) = value.as_bool() else {
+                    bail!(
+                        "import.meta.glob(..., {{ exhaustive }}) requires exhaustive to be a \
+                         constant boolean"
+                    );
+                };
+                options.exhaustive = exhaustive;
+            }
+            _ => bail!("import.meta.glob(..., options) doesn't support the option {key}"),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Converts the `query` option of import.meta.glob into a query string, e.g. `"raw"` into `?raw`
+/// and `{ foo: "bar", baz: true }` into `?foo=bar&baz=true`.
+fn import_meta_glob_query(value: &JsValue) -> Option<Option<RcStr>> {
+    let query = match value {
+        JsValue::Constant(ConstantValue::Str(query)) => query.as_str().to_string(),
+        JsValue::Object { parts, .. } => {
+            let mut query = Vec::new();
+            for part in parts {
+                let ObjectPart::KeyValue(key, value) = part else {
+                    return None;
+                };
+                let value = match value {
+                    JsValue::Constant(ConstantValue::Str(value)) => value.as_str().to_string(),
+                    JsValue::Constant(ConstantValue::Num(ConstantNumber(value))) => {
+                        value.to_string()
+                    }
+                    JsValue::Constant(ConstantValue::True) => "true".to_string(),
+                    JsValue::Constant(ConstantValue::False) => "false".to_string(),
+                    _ => return None,
+                };
+                query.push(format!("{}={value}", key.as_str()?));
+            }
+            query.join("&")
+        }
+        _ => return None,
+    };
+    let query = query.strip_prefix('?').unwrap_or(&query);
+    Some((!query.is_empty()).then(|| format!("?{query}").into()))
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct RequireContextValue(FxIndexMap<RcStr, RcStr>);
 
@@ -3845,6 +3990,7 @@ pub enum WellKnownFunctionKind {
     RequireContextRequire(RequireContextValue),
     RequireContextRequireKeys(RequireContextValue),
     RequireContextRequireResolve(RequireContextValue),
+    ImportMetaGlob,
     Define,
     FsReadMethod(Atom),
     PathToFileUrl,
@@ -596,6 +596,9 @@ pub async fn well_known_object_member(
         WellKnownObjectKind::NodePreGyp => node_pre_gyp(prop),
         WellKnownObjectKind::NodeExpressApp => express(prop),
         WellKnownObjectKind::NodeProtobufLoader => protobuf_loader(prop),
+        WellKnownObjectKind::ImportMeta if prop.as_str() == Some("glob") => {
+            JsValue::WellKnownFunction(WellKnownFunctionKind::ImportMetaGlob)
+        }
         #[allow(unreachable_patterns)]
         _ => {
             return Ok((
@@ -38,6 +38,7 @@ impl ChunkType for EcmascriptChunkType {
         chunk_items: Vec<ChunkItemOrBatchWithAsyncModuleInfo>,
         batch_groups: Vec<ResolvedVc<ChunkItemBatchGroup>>,
         referenced_output_assets: Vc<OutputAssets>,
+        name: Option<RcStr>,
     ) -> Result<Vc<Box<dyn Chunk>>> {
         let Some(chunking_context) =
             Vc::try_resolve_downcast::<Box<dyn ChunkingContext>>(chunking_context).await?
@@ -62,7 +63,11 @@ impl ChunkType for EcmascriptChunkType {
             referenced_output_assets: referenced_output_assets.owned().await?,
         }
         .cell();
-        Ok(Vc::upcast(EcmascriptChunk::new(chunking_context, content)))
+        Ok(Vc::upcast(EcmascriptChunk::new(
+            chunking_context,
+            content,
+            name,
+        )))
     }
 
     #[turbo_tasks::function]
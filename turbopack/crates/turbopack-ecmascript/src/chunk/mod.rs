@@ -43,6 +43,8 @@ pub use self::{
 pub struct EcmascriptChunk {
     pub chunking_context: ResolvedVc<Box<dyn ChunkingContext>>,
     pub content: ResolvedVc<EcmascriptChunkContent>,
+    /// The name of the chunk cache group the chunk was created for, if any.
+    pub name: Option<RcStr>,
 }
 
 #[turbo_tasks::value_impl]
@@ -51,14 +53,23 @@ impl EcmascriptChunk {
     pub fn new(
         chunking_context: ResolvedVc<Box<dyn ChunkingContext>>,
         content: ResolvedVc<EcmascriptChunkContent>,
+        name: Option<RcStr>,
     ) -> Vc<Self> {
         EcmascriptChunk {
             chunking_context,
             content,
+            name,
         }
         .cell()
     }
 
+    /// The name of the chunk cache group the chunk was created for, which is used as prefix of the
+    /// file name of the chunk.
+    #[turbo_tasks::function]
+    pub fn name(&self) -> Vc<Option<RcStr>> {
+        Vc::cell(self.name.clone())
+    }
+
     #[turbo_tasks::function]
     pub fn entry_ids(self: Vc<Self>) -> Vc<ModuleIds> {
         // TODO return something useful
@@ -113,7 +124,7 @@ impl Chunk for EcmascriptChunk {
             query: RcStr::default(),
             fragment: RcStr::default(),
             assets,
-            modifiers: Vec::new(),
+            modifiers: self.name.iter().cloned().collect(),
             parts: Vec::new(),
             layer: None,
             content_type: None,
@@ -17,5 +17,7 @@ pub mod failed_to_analyse {
         pub const NEW_URL_IMPORT_META: &str = "TP1201";
         pub const FREE_VAR_REFERENCE: &str = "TP1202";
         pub const NEW_WORKER: &str = "TP1203";
+        pub const IMPORT_META_GLOB: &str = "TP1204";
+        pub const WEBPACK_CHUNK: &str = "TP1205";
     }
 }
@@ -30,6 +30,7 @@ pub mod text;
 pub(crate) mod transform;
 pub mod tree_shake;
 pub mod typescript;
+pub mod unused_exports;
 pub mod utils;
 pub mod webpack;
 pub mod worker_chunk;
@@ -963,7 +964,7 @@ impl EcmascriptModuleContent {
         } = &*input;
 
         async {
-            let minify = chunking_context.minify_type().await?;
+            let minify = chunking_context.minify_type().owned().await?;
 
             let content = process_parse_result(
                 *parsed,
@@ -971,7 +972,7 @@ impl EcmascriptModuleContent {
                 *specified_module_type,
                 *generate_source_map,
                 *original_source_map,
-                *minify,
+                minify,
                 Some(&*input),
                 None,
             )
@@ -1060,7 +1061,7 @@ impl EcmascriptModuleContent {
                         *specified_module_type,
                         *generate_source_map,
                         *original_source_map,
-                        *chunking_context.minify_type().await?,
+                        chunking_context.minify_type().owned().await?,
                         Some(&*options),
                         Some(ScopeHoistingOptions {
                             module: *module,
@@ -1097,7 +1098,7 @@ impl EcmascriptModuleContent {
                 original_source_map: CodeGenResultOriginalSourceMap::ScopeHoisting(
                     original_source_maps,
                 ),
-                minify: *options.chunking_context.minify_type().await?,
+                minify: options.chunking_context.minify_type().owned().await?,
                 scope_hoisting_syntax_contexts: None,
             };
 
@@ -1,9 +1,9 @@
-use std::sync::Arc;
+use std::{iter, sync::Arc};
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
 use bytes_str::BytesStr;
 use swc_core::{
-    atoms::atom,
+    atoms::{Atom, atom},
     base::try_with_handler,
     common::{
         BytePos, FileName, FilePathMapping, GLOBALS, LineCol, Mark, SourceMap as SwcSourceMap,
@@ -11,7 +11,7 @@ use swc_core::{
     },
     ecma::{
         self,
-        ast::{EsVersion, Program},
+        ast::{EsVersion, Expr, Program},
         codegen::{
             Emitter,
             text_writer::{self, JsWriter, WriteJs},
@@ -25,15 +25,18 @@ use swc_core::{
     },
 };
 use tracing::{Level, instrument};
+use turbo_rcstr::RcStr;
 use turbopack_core::{
-    chunk::MangleType,
+    chunk::{MangleType, MinifyOptions as MinifierOptions},
     code_builder::{Code, CodeBuilder},
 };
 
 use crate::parse::generate_js_source_map;
 
 #[instrument(level = Level::INFO, skip_all)]
-pub fn minify(code: Code, source_maps: bool, mangle: Option<MangleType>) -> Result<Code> {
+pub fn minify(code: Code, source_maps: bool, options: &MinifierOptions) -> Result<Code> {
+    let mangle = options.mangle;
+    let ecma = options.ecma.map(es_version).transpose()?;
     let source_maps = source_maps
         .then(|| code.generate_source_map_ref())
         .transpose()?;
@@ -82,25 +85,26 @@ pub fn minify(code: Code, source_maps: bool, mangle: Option<MangleType>) -> Resu
                     None,
                     &MinifyOptions {
                         compress: Some(CompressOptions {
-                            // Only run 2 passes, this is a tradeoff between performance and
-                            // compression size. Default is 3 passes.
-                            passes: 2,
-                            keep_classnames: mangle.is_none(),
-                            keep_fnames: mangle.is_none(),
+                            passes: options.passes,
+                            drop_console: options.drop_console,
+                            drop_debugger: options.drop_debugger,
+                            pure_funcs: pure_funcs(&cm, &options.pure_funcs)?,
+                            keep_classnames: options.keep_classnames || mangle.is_none(),
+                            keep_fnames: options.keep_fnames || mangle.is_none(),
+                            ecma: ecma.unwrap_or_default(),
                             ..Default::default()
                         }),
                         mangle: mangle.map(|mangle| {
-                            let reserved = vec![atom!("AbortSignal")];
-                            match mangle {
-                                MangleType::OptimalSize => MangleOptions {
-                                    reserved,
-                                    ..Default::default()
-                                },
-                                MangleType::Deterministic => MangleOptions {
-                                    reserved,
-                                    disable_char_freq: true,
-                                    ..Default::default()
-                                },
+                            let reserved = iter::once(atom!("AbortSignal"))
+                                .chain(options.reserved.iter().map(|name| Atom::from(&**name)))
+                                .collect();
+                            MangleOptions {
+                                reserved,
+                                top_level: options.top_level,
+                                keep_class_names: options.keep_classnames,
+                                keep_fn_names: options.keep_fnames,
+                                disable_char_freq: mangle == MangleType::Deterministic,
+                                ..Default::default()
                             }
                         }),
                         ..Default::default()
@@ -151,6 +155,45 @@ pub fn minify(code: Code, source_maps: bool, mangle: Option<MangleType>) -> Resu
     Ok(builder.build())
 }
 
+/// The SWC version for an ECMAScript version as configured in terser, e.g. `5` or `2020`.
+fn es_version(ecma: u32) -> Result<EsVersion> {
+    Ok(match ecma {
+        3 => EsVersion::Es3,
+        5 => EsVersion::Es5,
+        2015 | 6 => EsVersion::Es2015,
+        2016 => EsVersion::Es2016,
+        2017 => EsVersion::Es2017,
+        2018 => EsVersion::Es2018,
+        2019 => EsVersion::Es2019,
+        2020 => EsVersion::Es2020,
+        2021 => EsVersion::Es2021,
+        2022 => EsVersion::Es2022,
+        _ => bail!("unsupported ECMAScript version for the minifier: {ecma}"),
+    })
+}
+
+/// Parses the names of pure functions, e.g. `console.info`, into the expressions the minifier
+/// matches calls against.
+// `CompressOptions::pure_funcs` is a `Vec<Box<Expr>>`
+#[allow(clippy::vec_box)]
+fn pure_funcs(cm: &SwcSourceMap, names: &[RcStr]) -> Result<Vec<Box<Expr>>> {
+    names
+        .iter()
+        .map(|name| {
+            let fm = cm.new_source_file(FileName::Anon.into(), name.to_string());
+            let lexer = Lexer::new(
+                Syntax::default(),
+                EsVersion::latest(),
+                StringInput::from(&*fm),
+                None,
+            );
+            Parser::new_from(lexer)
+                .parse_expr()
+                .map_err(|_| anyhow!("invalid pure function name for the minifier: {name}"))
+        })
+        .collect()
+}
+
 // From https://github.com/swc-project/swc/blob/11efd4e7c5e8081f8af141099d3459c3534c1e1d/crates/swc/src/lib.rs#L523-L560
 fn print_program(
     cm: Arc<SwcSourceMap>,
@@ -187,3 +230,165 @@ fn print_program(
 
     Ok((src, src_map_buf))
 }
+
+#[cfg(test)]
+mod tests {
+    use turbo_rcstr::rcstr;
+    use turbopack_core::{chunk::MinifyOptions, code_builder::CodeBuilder};
+
+    use super::minify;
+
+    /// Declares a class and a function in a nested scope, where their names can be mangled.
+    const NESTED_NAMES: &str = "(function () {
+        class LongClassName {}
+        function longFunctionName() {}
+        globalThis.values = [new LongClassName(), longFunctionName];
+    })();";
+
+    fn minify_source(source: &'static str, options: &MinifyOptions) -> String {
+        let mut code = CodeBuilder::new(false);
+        code += source;
+        let code = minify(code.build(), false, options).unwrap();
+        code.source_code().to_str().unwrap().into_owned()
+    }
+
+    #[test]
+    fn test_minify_mangles_names() {
+        let output = minify_source(NESTED_NAMES, &MinifyOptions::default());
+        assert!(!output.contains("LongClassName"), "{output}");
+        assert!(!output.contains("longFunctionName"), "{output}");
+    }
+
+    #[test]
+    fn test_minify_keep_classnames() {
+        let output = minify_source(
+            NESTED_NAMES,
+            &MinifyOptions {
+                keep_classnames: true,
+                ..Default::default()
+            },
+        );
+        assert!(output.contains("LongClassName"), "{output}");
+        assert!(!output.contains("longFunctionName"), "{output}");
+    }
+
+    #[test]
+    fn test_minify_keep_fnames() {
+        let output = minify_source(
+            NESTED_NAMES,
+            &MinifyOptions {
+                keep_fnames: true,
+                ..Default::default()
+            },
+        );
+        assert!(!output.contains("LongClassName"), "{output}");
+        assert!(output.contains("longFunctionName"), "{output}");
+    }
+
+    #[test]
+    fn test_minify_without_mangling() {
+        let output = minify_source(
+            NESTED_NAMES,
+            &MinifyOptions {
+                mangle: None,
+                ..Default::default()
+            },
+        );
+        assert!(output.contains("LongClassName"), "{output}");
+        assert!(output.contains("longFunctionName"), "{output}");
+    }
+
+    #[test]
+    fn test_minify_top_level() {
+        const SOURCE: &str = "function longTopLevelName() { return Math.random(); }
+            globalThis.a = longTopLevelName;
+            globalThis.b = longTopLevelName;";
+
+        let output = minify_source(
+            SOURCE,
+            &MinifyOptions {
+                top_level: Some(false),
+                ..Default::default()
+            },
+        );
+        assert!(output.contains("longTopLevelName"), "{output}");
+
+        let output = minify_source(
+            SOURCE,
+            &MinifyOptions {
+                top_level: Some(true),
+                ..Default::default()
+            },
+        );
+        assert!(!output.contains("longTopLevelName"), "{output}");
+    }
+
+    #[test]
+    fn test_minify_drop_console_and_debugger() {
+        const SOURCE: &str = "globalThis.a = 1; console.log('logged'); debugger;";
+
+        let output = minify_source(SOURCE, &MinifyOptions::default());
+        assert!(output.contains("logged"), "{output}");
+        assert!(output.contains("debugger"), "{output}");
+
+        let output = minify_source(
+            SOURCE,
+            &MinifyOptions {
+                drop_console: true,
+                drop_debugger: true,
+                ..Default::default()
+            },
+        );
+        assert!(!output.contains("logged"), "{output}");
+        assert!(!output.contains("debugger"), "{output}");
+    }
+
+    #[test]
+    fn test_minify_pure_funcs() {
+        const SOURCE: &str = "globalThis.a = 1; console.info('informed');";
+
+        let output = minify_source(
+            SOURCE,
+            &MinifyOptions {
+                pure_funcs: vec![rcstr!("console.info")],
+                ..Default::default()
+            },
+        );
+        assert!(!output.contains("informed"), "{output}");
+    }
+
+    #[test]
+    fn test_minify_reserved() {
+        const SOURCE: &str = "(function () {
+            var reservedName = Math.random(), otherName = Math.random();
+            globalThis.values = [reservedName, reservedName, otherName, otherName];
+        })();";
+
+        let output = minify_source(
+            SOURCE,
+            &MinifyOptions {
+                reserved: vec![rcstr!("reservedName")],
+                ..Default::default()
+            },
+        );
+        assert!(output.contains("reservedName"), "{output}");
+        assert!(!output.contains("otherName"), "{output}");
+    }
+
+    #[test]
+    fn test_minify_invalid_options() {
+        let mut code = CodeBuilder::new(false);
+        code += "globalThis.a = 1;";
+        assert!(
+            minify(
+                code.build(),
+                false,
+                &MinifyOptions {
+                    ecma: Some(2000),
+                    ..Default::default()
+                },
+            )
+            .is_err()
+        );
+    }
+}
@@ -384,8 +384,22 @@ impl EsmAssetReference {
 impl ModuleReference for EsmAssetReference {
     #[turbo_tasks::function]
     async fn resolve_reference(&self) -> Result<Vc<ModuleResolveResult>> {
-        let ty = if matches!(self.annotations.module_type(), Some("json")) {
-            EcmaScriptModulesReferenceSubType::ImportWithType(ImportWithType::Json)
+        let import_with_type = self.annotations.module_type().and_then(|module_type| {
+            let ty = ImportWithType::from_attribute(module_type);
+            if ty.is_none() {
+                UnsupportedImportType {
+                    module_type: module_type.into(),
+                    source: self.issue_source,
+                }
+                .resolved_cell()
+                .emit();
+            }
+            ty
+        });
+        let ty = if self.annotations.is_source_phase() {
+            EcmaScriptModulesReferenceSubType::ImportSourcePhase
+        } else if let Some(import_with_type) = import_with_type {
+            EcmaScriptModulesReferenceSubType::ImportWithType(import_with_type)
         } else if let Some(part) = &self.export_name {
             EcmaScriptModulesReferenceSubType::ImportPart(part.clone())
         } else {
@@ -849,6 +863,58 @@ impl Issue for InvalidExport {
     }
 }
 
+#[turbo_tasks::value(shared)]
+pub struct UnsupportedImportType {
+    module_type: RcStr,
+    source: IssueSource,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for UnsupportedImportType {
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::Error
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Line(vec![
+            StyledString::Text(rcstr!("Import type ")),
+            StyledString::Code(self.module_type.clone()),
+            StyledString::Text(rcstr!(" is not supported")),
+        ])
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Resolve.into()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.source.file_path()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        Vc::cell(Some(
+            StyledString::Line(vec![
+                StyledString::Text(rcstr!("The ")),
+                StyledString::Code(rcstr!("type")),
+                StyledString::Text(rcstr!(
+                    " import attribute must be one of \"json\", \"css\", \"text\" or \"bytes\"."
+                )),
+            ])
+            .resolved_cell(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn source(&self) -> Vc<OptionIssueSource> {
+        Vc::cell(Some(self.source))
+    }
+}
+
 #[turbo_tasks::value(shared)]
 pub struct CircularReExport {
     export: RcStr,
@@ -0,0 +1,147 @@
+//! Evaluates the exports of ES modules that are compile time constants (e.g. feature flags), so
+//! that the analysis of importing modules can use them for constant conditions and for resolving
+//! dynamic requests.
+//!
+//! The exports are evaluated from the parse result of the imported module, which uses the same
+//! transforms as the module itself, instead of from its analysis. The analysis of a module resolves
+//! its imports, so depending on the analysis of the imported module would create cycles for modules
+//! that import each other.
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use swc_core::common::GLOBALS;
+use turbo_rcstr::RcStr;
+use turbo_tasks::{FxIndexMap, ResolvedVc, Vc};
+use turbopack_core::{
+    compile_time_info::CompileTimeDefineValue,
+    module::Module,
+    reference_type::{EcmaScriptModulesReferenceSubType, ReferenceType},
+    resolve::{
+        origin::{ResolveOrigin, ResolveOriginExt},
+        parse::Request,
+    },
+};
+use turbopack_resolve::ecmascript::apply_esm_specific_options;
+
+use crate::{
+    EcmascriptParsable,
+    analyzer::{
+        ConstantNumber, ConstantValue, JsValue,
+        builtin::{early_replace_builtin, replace_builtin},
+        graph::create_graph,
+        linker::link,
+    },
+    parse::ParseResult,
+};
+
+/// The exports of a module that evaluate to compile time constants, keyed by export name.
+#[turbo_tasks::value(transparent)]
+pub struct ConstantExports(FxIndexMap<RcStr, CompileTimeDefineValue>);
+
+/// Returns the constant exports of the ES module that `request` resolves to from `origin`. Requests
+/// that don't resolve to exactly one ECMAScript module outside of `node_modules` have no constant
+/// exports.
+#[turbo_tasks::function]
+pub async fn imported_constant_exports(
+    origin: Vc<Box<dyn ResolveOrigin>>,
+    request: RcStr,
+) -> Result<Vc<ConstantExports>> {
+    let ty = ReferenceType::EcmaScriptModules(EcmaScriptModulesReferenceSubType::Import);
+    let options = apply_esm_specific_options(origin.resolve_options(ty.clone()).await?, ty.clone());
+    let result = origin
+        .resolve_asset(Request::parse(request.into()), options, ty)
+        .await?;
+    let modules = result.primary_modules().await?;
+    let [module] = &modules[..] else {
+        return Ok(Vc::cell(Default::default()));
+    };
+    // Dependencies are unlikely to be configured through constants of the application and are
+    // skipped to avoid parsing them early
+    if module
+        .ident()
+        .path()
+        .await?
+        .path
+        .split('/')
+        .any(|segment| segment == "node_modules")
+    {
+        return Ok(Vc::cell(Default::default()));
+    }
+    let Some(module) = ResolvedVc::try_sidecast::<Box<dyn EcmascriptParsable>>(*module) else {
+        return Ok(Vc::cell(Default::default()));
+    };
+    Ok(constant_exports(*module))
+}
+
+#[turbo_tasks::function]
+async fn constant_exports(module: Vc<Box<dyn EcmascriptParsable>>) -> Result<Vc<ConstantExports>> {
+    let parsed = module.failsafe_parse().await?;
+    let ParseResult::Ok {
+        program,
+        eval_context,
+        globals,
+        ..
+    } = &*parsed
+    else {
+        return Ok(Vc::cell(Default::default()));
+    };
+
+    let var_graph = GLOBALS.set(globals, || create_graph(program, eval_context));
+    let fun_args_values = Mutex::new(FxHashMap::default());
+    let var_cache = Mutex::new(FxHashMap::default());
+
+    let mut exports = Vec::new();
+    for (name, id) in &eval_context.imports.exports {
+        let (value, _) = link(
+            &var_graph,
+            JsValue::Variable(id.clone()),
+            &early_value_visitor,
+            &value_visitor,
+            &fun_args_values,
+            &var_cache,
+        )
+        .await?;
+        // Reassigned bindings evaluate to alternatives and are skipped here
+        if let JsValue::Constant(value) = value
+            && let Some(value) = constant_to_define_value(&value)
+        {
+            exports.push((name.clone(), value));
+        }
+    }
+    exports.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(Vc::cell(exports.into_iter().collect()))
+}
+
+async fn early_value_visitor(mut v: JsValue) -> Result<(JsValue, bool)> {
+    let modified = early_replace_builtin(&mut v);
+    Ok((v, modified))
+}
+
+async fn value_visitor(mut v: JsValue) -> Result<(JsValue, bool)> {
+    let modified = match v {
+        // Values of other modules and of the environment are not followed, as the source is
+        // evaluated without the compile time info of its module.
+        JsValue::Module(..) | JsValue::FreeVar(..) | JsValue::Argument(..) => {
+            v.make_unknown(true, "not a constant export");
+            true
+        }
+        _ => replace_builtin(&mut v) || v.make_nested_operations_unknown(),
+    };
+    Ok((v, modified))
+}
+
+fn constant_to_define_value(value: &ConstantValue) -> Option<CompileTimeDefineValue> {
+    Some(match value {
+        ConstantValue::Undefined => CompileTimeDefineValue::Undefined,
+        ConstantValue::Null => CompileTimeDefineValue::Null,
+        ConstantValue::True => CompileTimeDefineValue::Bool(true),
+        ConstantValue::False => CompileTimeDefineValue::Bool(false),
+        ConstantValue::Str(s) => CompileTimeDefineValue::String(s.as_str().into()),
+        ConstantValue::Num(ConstantNumber(n)) => {
+            CompileTimeDefineValue::Number(n.to_string().into())
+        }
+        ConstantValue::BigInt(..) | ConstantValue::Regex(..) => return None,
+    })
+}
@@ -1,5 +1,6 @@
 pub(crate) mod base;
 pub(crate) mod binding;
+pub(crate) mod constant_exports;
 pub(crate) mod dynamic;
 pub(crate) mod export;
 pub(crate) mod meta;
@@ -0,0 +1,610 @@
+use std::{borrow::Cow, sync::Arc};
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{
+            Expr, ExprStmt, KeyValueProp, Lit, ModuleItem, ObjectLit, Prop, PropName, PropOrSpread,
+            Stmt, {self},
+        },
+        codegen::{Emitter, text_writer::JsWriter},
+    },
+    quote, quote_expr,
+};
+use turbo_rcstr::{RcStr, rcstr};
+use turbo_tasks::{
+    FxIndexMap, NonLocalValue, ResolvedVc, ValueToString, Vc, debug::ValueDebugFormat,
+    trace::TraceRawVcs,
+};
+use turbo_tasks_fs::{DirectoryEntry, FileSystemPath, glob::Glob};
+use turbopack_core::{
+    asset::{Asset, AssetContent},
+    chunk::{
+        ChunkItem, ChunkType, ChunkableModule, ChunkableModuleReference, ChunkingContext,
+        ChunkingType, ChunkingTypeOption, MinifyType, ModuleChunkItemIdExt,
+    },
+    ident::AssetIdent,
+    issue::IssueSource,
+    module::Module,
+    module_graph::ModuleGraph,
+    reference::{ModuleReference, ModuleReferences},
+    reference_type::EcmaScriptModulesReferenceSubType,
+    resolve::{
+        FindContextFileResult, ModuleResolveResult, find_context_file, origin::ResolveOrigin,
+        package_json, parse::Request,
+    },
+    source::Source,
+};
+use turbopack_resolve::ecmascript::esm_resolve;
+
+use crate::{
+    EcmascriptChunkPlaceable,
+    analyzer::ImportMetaGlobOptions,
+    chunk::{
+        EcmascriptChunkItem, EcmascriptChunkItemContent, EcmascriptChunkType, EcmascriptExports,
+    },
+    code_gen::{CodeGen, CodeGeneration, IntoCodeGenReference},
+    create_visitor,
+    references::{
+        AstPath,
+        pattern_mapping::{PatternMapping, ResolveType, SinglePatternMapping},
+    },
+    runtime_functions::{TURBOPACK_EXPORT_VALUE, TURBOPACK_IMPORT, TURBOPACK_REQUIRE},
+    utils::module_id_to_lit,
+};
+
+#[turbo_tasks::value]
+#[derive(Debug)]
+pub struct ImportMetaGlobMapEntry {
+    pub request: ResolvedVc<Request>,
+    pub result: ResolvedVc<ModuleResolveResult>,
+}
+
+/// The resolved map for an `import.meta.glob(..)` call, keyed by the paths that are exposed to the
+/// application.
+#[turbo_tasks::value(transparent)]
+pub struct ImportMetaGlobMap(FxIndexMap<RcStr, ImportMetaGlobMapEntry>);
+
+#[turbo_tasks::value_impl]
+impl ImportMetaGlobMap {
+    /// Expands the glob patterns relative to the directory of `origin`. Absolute patterns (e.g.
+    /// `/src/*.ts`) are relative to the project root, like in Vite. The matches are read with
+    /// `read_glob`, so the map is invalidated when matching files are added or removed.
+    #[turbo_tasks::function]
+    pub(crate) async fn generate(
+        origin: Vc<Box<dyn ResolveOrigin>>,
+        patterns: Vec<RcStr>,
+        eager: bool,
+        query: Option<RcStr>,
+        exhaustive: bool,
+        issue_source: Option<IssueSource>,
+        is_optional: bool,
+    ) -> Result<Vc<Self>> {
+        let origin_path = origin.origin_path().owned().await?;
+        let origin_dir = origin_path.parent();
+        let root = project_root(origin_dir.clone()).owned().await?;
+
+        let mut negated_globs = Vec::new();
+        for pattern in patterns
+            .iter()
+            .filter_map(|pattern| pattern.strip_prefix('!'))
+        {
+            negated_globs.push(Glob::parse(strip_key_prefix(pattern))?);
+        }
+
+        let mut files = FxIndexMap::default();
+        for pattern in patterns.iter().filter(|pattern| !pattern.starts_with('!')) {
+            let (dir, is_absolute, pattern) = match pattern.strip_prefix('/') {
+                Some(pattern) => (&root, true, pattern),
+                None => (&origin_dir, false, pattern.as_str()),
+            };
+            let (base, glob) = split_glob_base(pattern);
+
+            let mut queue = vec![dir.join(base)?.read_glob(Glob::new(glob.into())).await?];
+            while let Some(result) = queue.pop() {
+                for entry in result.results.values() {
+                    let DirectoryEntry::File(path) = entry.clone().resolve_symlink().await? else {
+                        continue;
+                    };
+                    // Vite never matches the importing module itself
+                    if path == origin_path
+                        || (!exhaustive && path.path.split('/').any(|s| s == "node_modules"))
+                    {
+                        continue;
+                    }
+                    let Some(origin_relative) = origin_dir.get_relative_path_to(&path) else {
+                        continue;
+                    };
+                    let key: RcStr = if is_absolute {
+                        let Some(root_relative) = root.get_path_to(&path) else {
+                            continue;
+                        };
+                        format!("/{root_relative}").into()
+                    } else {
+                        origin_relative.clone()
+                    };
+                    if negated_globs
+                        .iter()
+                        .any(|glob| glob.matches(strip_key_prefix(&key)))
+                    {
+                        continue;
+                    }
+                    files.entry(key).or_insert(origin_relative);
+                }
+                for inner in result.inner.values() {
+                    queue.push(inner.await?);
+                }
+            }
+        }
+        files.sort_keys();
+
+        let ty = if eager {
+            EcmaScriptModulesReferenceSubType::Import
+        } else {
+            EcmaScriptModulesReferenceSubType::DynamicImport
+        };
+        let mut map = FxIndexMap::default();
+        for (key, origin_relative) in files {
+            let request: RcStr = match &query {
+                Some(query) => format!("{origin_relative}{query}").into(),
+                None => origin_relative,
+            };
+            let request = Request::parse(request.into()).to_resolved().await?;
+            let result = esm_resolve(origin, *request, ty.clone(), is_optional, issue_source)
+                .await?
+                .to_resolved()
+                .await?;
+
+            map.insert(key, ImportMetaGlobMapEntry { request, result });
+        }
+
+        Ok(Vc::cell(map))
+    }
+}
+
+/// The root of the project that contains `dir`, which absolute glob patterns are relative to. This
+/// is the directory of the closest `package.json`, or the root of the file system when there is
+/// none.
+#[turbo_tasks::function]
+async fn project_root(dir: FileSystemPath) -> Result<Vc<FileSystemPath>> {
+    Ok(
+        match &*find_context_file(dir.clone(), package_json()).await? {
+            FindContextFileResult::Found(package_json_path, _) => package_json_path.parent().cell(),
+            FindContextFileResult::NotFound(_) => dir.root(),
+        },
+    )
+}
+
+/// Splits a glob pattern into the directory that precedes the first segment with glob syntax, and
+/// the glob relative to that directory, e.g. `./pages/**/*.tsx` into `./pages/` and `**/*.tsx`.
+fn split_glob_base(pattern: &str) -> (&str, &str) {
+    let mut offset = 0;
+    for segment in pattern.split('/') {
+        if segment.contains(['*', '?', '[', '{']) {
+            return pattern.split_at(offset);
+        }
+        offset += segment.len() + 1;
+    }
+    pattern.split_at(pattern.rfind('/').map_or(0, |index| index + 1))
+}
+
+/// Strips the leading `./` or `/` of a key, so that negated patterns like `!**/*.test.ts` can be
+/// matched against it.
+fn strip_key_prefix(key: &str) -> &str {
+    key.strip_prefix("./")
+        .or_else(|| key.strip_prefix('/'))
+        .unwrap_or(key)
+}
+
+/// A reference for `import.meta.glob()`, will replace it with an inlined map of the matched
+/// modules.
+#[turbo_tasks::value]
+#[derive(Hash, Debug)]
+pub struct ImportMetaGlobAssetReference {
+    pub inner: ResolvedVc<ImportMetaGlobAsset>,
+    pub patterns: Vec<RcStr>,
+
+    pub issue_source: Option<IssueSource>,
+    pub in_try: bool,
+}
+
+impl ImportMetaGlobAssetReference {
+    pub async fn new(
+        source: ResolvedVc<Box<dyn Source>>,
+        origin: ResolvedVc<Box<dyn ResolveOrigin>>,
+        options: ImportMetaGlobOptions,
+        import_externals: bool,
+        issue_source: Option<IssueSource>,
+        in_try: bool,
+    ) -> Result<Self> {
+        let ImportMetaGlobOptions {
+            patterns,
+            eager,
+            import,
+            query,
+            exhaustive,
+        } = options;
+        let map = ImportMetaGlobMap::generate(
+            *origin,
+            patterns.clone(),
+            eager,
+            query.clone(),
+            exhaustive,
+            issue_source,
+            in_try,
+        )
+        .to_resolved()
+        .await?;
+        let inner = ImportMetaGlobAsset {
+            source,
+            origin,
+            map,
+
+            patterns: patterns.clone(),
+            eager,
+            import,
+            query,
+            import_externals,
+        }
+        .resolved_cell();
+
+        Ok(ImportMetaGlobAssetReference {
+            inner,
+            patterns,
+            issue_source,
+            in_try,
+        })
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ModuleReference for ImportMetaGlobAssetReference {
+    #[turbo_tasks::function]
+    fn resolve_reference(&self) -> Vc<ModuleResolveResult> {
+        *ModuleResolveResult::module(ResolvedVc::upcast(self.inner))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for ImportMetaGlobAssetReference {
+    #[turbo_tasks::function]
+    fn to_string(&self) -> Vc<RcStr> {
+        Vc::cell(format!("import.meta.glob {}", self.patterns.join(", ")).into())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ChunkableModuleReference for ImportMetaGlobAssetReference {}
+
+impl IntoCodeGenReference for ImportMetaGlobAssetReference {
+    fn into_code_gen_reference(
+        self,
+        path: AstPath,
+    ) -> (ResolvedVc<Box<dyn ModuleReference>>, CodeGen) {
+        let reference = self.resolved_cell();
+        (
+            ResolvedVc::upcast(reference),
+            CodeGen::ImportMetaGlobAssetReferenceCodeGen(ImportMetaGlobAssetReferenceCodeGen {
+                reference,
+                path,
+            }),
+        )
+    }
+}
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, ValueDebugFormat, NonLocalValue)]
+pub struct ImportMetaGlobAssetReferenceCodeGen {
+    path: AstPath,
+    reference: ResolvedVc<ImportMetaGlobAssetReference>,
+}
+
+impl ImportMetaGlobAssetReferenceCodeGen {
+    pub async fn code_generation(
+        &self,
+        chunking_context: Vc<Box<dyn ChunkingContext>>,
+    ) -> Result<CodeGeneration> {
+        let module_id = self
+            .reference
+            .await?
+            .inner
+            .chunk_item_id(Vc::upcast(chunking_context))
+            .await?;
+
+        let mut visitors = Vec::new();
+
+        visitors.push(create_visitor!(
+            self.path,
+            visit_mut_expr,
+            |expr: &mut Expr| {
+                if let Expr::Call(_) = expr {
+                    *expr = quote!(
+                        "$turbopack_require($id)" as Expr,
+                        turbopack_require: Expr = TURBOPACK_REQUIRE.into(),
+                        id: Expr = module_id_to_lit(&module_id)
+                    );
+                }
+            }
+        ));
+
+        Ok(CodeGeneration::visitors(visitors))
+    }
+}
+
+/// A reference from the glob map to one of the matched modules. Lazy matches are loaded in a
+/// separate chunk group.
+#[turbo_tasks::value]
+pub struct ImportMetaGlobEntryReference {
+    result: ResolvedVc<ModuleResolveResult>,
+    eager: bool,
+}
+
+#[turbo_tasks::value_impl]
+impl ModuleReference for ImportMetaGlobEntryReference {
+    #[turbo_tasks::function]
+    fn resolve_reference(&self) -> Vc<ModuleResolveResult> {
+        *self.result
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for ImportMetaGlobEntryReference {
+    #[turbo_tasks::function]
+    fn to_string(&self) -> Vc<RcStr> {
+        Vc::cell(rcstr!("import.meta.glob entry"))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ChunkableModuleReference for ImportMetaGlobEntryReference {
+    #[turbo_tasks::function]
+    fn chunking_type(&self) -> Vc<ChunkingTypeOption> {
+        Vc::cell(Some(if self.eager {
+            ChunkingType::Parallel {
+                inherit_async: false,
+                hoisted: false,
+            }
+        } else {
+            ChunkingType::Async
+        }))
+    }
+}
+
+#[turbo_tasks::value]
+pub struct ImportMetaGlobAsset {
+    source: ResolvedVc<Box<dyn Source>>,
+
+    origin: ResolvedVc<Box<dyn ResolveOrigin>>,
+    map: ResolvedVc<ImportMetaGlobMap>,
+
+    patterns: Vec<RcStr>,
+    eager: bool,
+    import: Option<RcStr>,
+    query: Option<RcStr>,
+    import_externals: bool,
+}
+
+impl ImportMetaGlobAsset {
+    fn modifier(&self) -> RcStr {
+        let mut modifier = format!("import.meta.glob {}", self.patterns.join(", "));
+        if self.eager {
+            modifier.push_str(" eager");
+        }
+        if let Some(import) = &self.import {
+            modifier.push_str(&format!(" import {import}"));
+        }
+        if let Some(query) = &self.query {
+            modifier.push_str(&format!(" query {query}"));
+        }
+        modifier.into()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Module for ImportMetaGlobAsset {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        self.source.ident().with_modifier(self.modifier())
+    }
+
+    #[turbo_tasks::function]
+    async fn references(&self) -> Result<Vc<ModuleReferences>> {
+        let map = &*self.map.await?;
+
+        Ok(Vc::cell(
+            map.values()
+                .map(|entry| {
+                    ResolvedVc::upcast(
+                        ImportMetaGlobEntryReference {
+                            result: entry.result,
+                            eager: self.eager,
+                        }
+                        .resolved_cell(),
+                    )
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for ImportMetaGlobAsset {
+    #[turbo_tasks::function]
+    fn content(&self) -> Result<Vc<AssetContent>> {
+        // The module has no source, its code is generated by its chunk item
+        bail!("import.meta.glob modules have no content")
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ChunkableModule for ImportMetaGlobAsset {
+    #[turbo_tasks::function]
+    fn as_chunk_item(
+        self: ResolvedVc<Self>,
+        module_graph: ResolvedVc<ModuleGraph>,
+        chunking_context: ResolvedVc<Box<dyn ChunkingContext>>,
+    ) -> Vc<Box<dyn turbopack_core::chunk::ChunkItem>> {
+        Vc::upcast(
+            ImportMetaGlobChunkItem {
+                module_graph,
+                chunking_context,
+                inner: self,
+            }
+            .cell(),
+        )
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl EcmascriptChunkPlaceable for ImportMetaGlobAsset {
+    #[turbo_tasks::function]
+    fn get_exports(&self) -> Vc<EcmascriptExports> {
+        EcmascriptExports::Value.cell()
+    }
+}
+
+#[turbo_tasks::value]
+pub struct ImportMetaGlobChunkItem {
+    module_graph: ResolvedVc<ModuleGraph>,
+    chunking_context: ResolvedVc<Box<dyn ChunkingContext>>,
+    inner: ResolvedVc<ImportMetaGlobAsset>,
+}
+
+#[turbo_tasks::value_impl]
+impl EcmascriptChunkItem for ImportMetaGlobChunkItem {
+    #[turbo_tasks::function]
+    async fn content(&self) -> Result<Vc<EcmascriptChunkItemContent>> {
+        let inner = self.inner.await?;
+        let map = &*inner.map.await?;
+        let minify = self.chunking_context.minify_type().await?;
+
+        let mut glob_map = ObjectLit {
+            span: DUMMY_SP,
+            props: vec![],
+        };
+
+        for (key, entry) in map {
+            let pm = PatternMapping::resolve_request(
+                *entry.request,
+                *inner.origin,
+                *ResolvedVc::upcast(self.chunking_context),
+                *entry.result,
+                if inner.eager {
+                    ResolveType::ChunkItem
+                } else {
+                    ResolveType::AsyncChunkLoader
+                },
+            )
+            .await?;
+
+            let PatternMapping::Single(pm) = &*pm else {
+                continue;
+            };
+
+            let key_expr = Expr::Lit(Lit::Str(key.as_str().into()));
+
+            let value = if inner.eager {
+                let module = match pm {
+                    SinglePatternMapping::Module(_) => quote!(
+                        "$turbopack_import($id)" as Expr,
+                        turbopack_import: Expr = TURBOPACK_IMPORT.into(),
+                        id: Expr = pm.create_id(Cow::Borrowed(&key_expr))
+                    ),
+                    _ => pm.create_require(Cow::Borrowed(&key_expr)),
+                };
+                match &inner.import {
+                    Some(import) => quote_expr!(
+                        "$module[$import]",
+                        module: Expr = module,
+                        import: Expr = import.as_str().into(),
+                    ),
+                    None => Box::new(module),
+                }
+            } else {
+                let module = pm.create_import(Cow::Borrowed(&key_expr), inner.import_externals);
+                match &inner.import {
+                    Some(import) => quote_expr!(
+                        "() => $module.then((m) => m[$import])",
+                        module: Expr = module,
+                        import: Expr = import.as_str().into(),
+                    ),
+                    None => quote_expr!("() => $module", module: Expr = module),
+                }
+            };
+
+            let prop = KeyValueProp {
+                key: PropName::Str(key.as_str().into()),
+                value,
+            };
+
+            glob_map
+                .props
+                .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(prop))));
+        }
+
+        let expr = quote_expr!(
+            "$turbopack_export_value($obj);",
+            turbopack_export_value: Expr = TURBOPACK_EXPORT_VALUE.into(),
+            obj: Expr = Expr::Object(glob_map),
+        );
+
+        let module = ast::Module {
+            span: DUMMY_SP,
+            body: vec![ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr,
+            }))],
+            shebang: None,
+        };
+
+        let source_map: Arc<swc_core::common::SourceMap> = Default::default();
+
+        let mut bytes: Vec<u8> = vec![];
+        let mut wr: JsWriter<'_, &mut Vec<u8>> =
+            JsWriter::new(source_map.clone(), "\n", &mut bytes, None);
+        if matches!(*minify, MinifyType::Minify { .. }) {
+            wr.set_indent_str("");
+        }
+
+        let mut emitter = Emitter {
+            cfg: swc_core::ecma::codegen::Config::default(),
+            cm: source_map.clone(),
+            comments: None,
+            wr,
+        };
+
+        emitter.emit_module(&module)?;
+
+        Ok(EcmascriptChunkItemContent {
+            inner_code: bytes.into(),
+            ..Default::default()
+        }
+        .cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ChunkItem for ImportMetaGlobChunkItem {
+    #[turbo_tasks::function]
+    fn asset_ident(&self) -> Vc<AssetIdent> {
+        self.inner.ident()
+    }
+
+    #[turbo_tasks::function]
+    fn chunking_context(&self) -> Vc<Box<dyn ChunkingContext>> {
+        *ResolvedVc::upcast(self.chunking_context)
+    }
+
+    #[turbo_tasks::function]
+    async fn ty(&self) -> Result<Vc<Box<dyn ChunkType>>> {
+        Ok(Vc::upcast(
+            Vc::<EcmascriptChunkType>::default().resolve().await?,
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn module(&self) -> Vc<Box<dyn Module>> {
+        *ResolvedVc::upcast(self.inner)
+    }
+}
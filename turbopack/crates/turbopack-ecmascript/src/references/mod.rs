@@ -7,6 +7,7 @@ pub mod dynamic_expression;
 pub mod esm;
 pub mod external_module;
 pub mod ident;
+pub mod import_meta_glob;
 pub mod member;
 pub mod node;
 pub mod pattern_mapping;
@@ -124,6 +125,7 @@ use super::{
     utils::js_value_to_pattern,
     webpack::{
         WebpackChunkAssetReference, WebpackEntryAssetReference, WebpackRuntimeAssetReference,
+        eval_chunk_id,
         parse::{WebpackRuntime, webpack_runtime},
     },
 };
@@ -132,11 +134,11 @@ use crate::{
     EcmascriptInputTransforms, EcmascriptModuleAsset, EcmascriptParsable, SpecifiedModuleType,
     TreeShakingMode,
     analyzer::{
-        ConstantNumber, ConstantString, JsValueUrlKind, RequireContextValue,
+        ConstantNumber, ConstantString, JsValueUrlKind, ModuleValue, RequireContextValue,
         builtin::early_replace_builtin,
         graph::{ConditionalKind, EffectArg, EvalContext, VarGraph},
         imports::{ImportAnnotations, ImportAttributes, ImportedSymbol, Reexport},
-        parse_require_context,
+        parse_import_meta_glob, parse_require_context,
         top_level_await::has_top_level_await,
     },
     chunk::EcmascriptExports,
@@ -149,9 +151,10 @@ use crate::{
         dynamic_expression::DynamicExpression,
         esm::{
             EsmBinding, UrlRewriteBehavior, base::EsmAssetReferences,
-            module_id::EsmModuleIdAssetReference,
+            constant_exports::imported_constant_exports, module_id::EsmModuleIdAssetReference,
         },
         ident::IdentReplacement,
+        import_meta_glob::ImportMetaGlobAssetReference,
         member::MemberReplacement,
         node::PackageJsonReference,
         require_context::{RequireContextAssetReference, RequireContextMap},
@@ -449,7 +452,7 @@ impl AnalysisState<'_> {
         Ok(link(
             self.var_graph,
             value,
-            &early_value_visitor,
+            &|value| early_value_visitor(*self.origin, value),
             &|value| {
                 value_visitor(
                     *self.origin,
@@ -933,6 +936,7 @@ pub(crate) async fn analyse_ecmascript_module_internal(
                 for chunk in webpack_chunks {
                     analysis.add_reference(
                         WebpackChunkAssetReference {
+                            source,
                             chunk_id: chunk,
                             runtime,
                             transforms,
@@ -1904,6 +1908,40 @@ async fn handle_call<G: Fn(Vec<Effect>) + Send + Sync>(
             );
         }
 
+        JsValue::WellKnownFunction(WellKnownFunctionKind::ImportMetaGlob) => {
+            let args = linked_args(args).await?;
+            let options = match parse_import_meta_glob(&args) {
+                Ok(options) => options,
+                Err(err) => {
+                    let (args, hints) = explain_args(&args);
+                    handler.span_err_with_code(
+                        span,
+                        &format!(
+                            "import.meta.glob({args}) is not statically analyze-able: {}{hints}",
+                            PrettyPrintError(&err)
+                        ),
+                        DiagnosticId::Error(
+                            errors::failed_to_analyse::ecmascript::IMPORT_META_GLOB.to_string(),
+                        ),
+                    );
+                    return Ok(());
+                }
+            };
+
+            analysis.add_reference_code_gen(
+                ImportMetaGlobAssetReference::new(
+                    source,
+                    origin,
+                    options,
+                    state.import_externals,
+                    Some(issue_source(source, span)),
+                    in_try,
+                )
+                .await?,
+                ast_path.to_vec().into(),
+            );
+        }
+
         JsValue::WellKnownFunction(WellKnownFunctionKind::FsReadMethod(name)) => {
             let args = linked_args(args).await?;
             if !args.is_empty() {
@@ -2661,7 +2699,11 @@ async fn analyze_amd_define(
     in_try: bool,
 ) -> Result<()> {
     match &args[..] {
-        [JsValue::Constant(id), JsValue::Array { items: deps, .. }, _] if id.as_str().is_some() => {
+        [
+            JsValue::Constant(id),
+            JsValue::Array { items: deps, .. },
+            factory,
+        ] if id.as_str().is_some() => {
             analyze_amd_define_with_deps(
                 source,
                 analysis,
@@ -2671,13 +2713,23 @@ async fn analyze_amd_define(
                 ast_path,
                 id.as_str(),
                 deps,
+                amd_factory_type(factory),
                 in_try,
             )
             .await?;
         }
-        [JsValue::Array { items: deps, .. }, _] => {
+        [JsValue::Array { items: deps, .. }, factory] => {
             analyze_amd_define_with_deps(
-                source, analysis, origin, handler, span, ast_path, None, deps, in_try,
+                source,
+                analysis,
+                origin,
+                handler,
+                span,
+                ast_path,
+                None,
+                deps,
+                amd_factory_type(factory),
+                in_try,
             )
             .await?;
         }
@@ -2768,6 +2820,7 @@ async fn analyze_amd_define_with_deps(
     ast_path: &[AstParentKind],
     id: Option<&str>,
     deps: &[JsValue],
+    factory_type: AmdDefineFactoryType,
     in_try: bool,
 ) -> Result<()> {
     let mut requests = Vec::new();
@@ -2778,13 +2831,8 @@ async fn analyze_amd_define_with_deps(
                     requests.push(AmdDefineDependencyElement::Exports);
                 }
                 "require" => {
-                    handler.span_warn_with_code(
-                        span,
-                        "using \"require\" as dependency in an AMD define() is not yet supported",
-                        DiagnosticId::Error(
-                            errors::failed_to_analyse::ecmascript::AMD_DEFINE.to_string(),
-                        ),
-                    );
+                    // `require` calls in the factory are analyzed as CommonJS requires, see
+                    // `extract_var_from_umd_factory`
                     requests.push(AmdDefineDependencyElement::Require);
                 }
                 "module" => {
@@ -2830,7 +2878,7 @@ async fn analyze_amd_define_with_deps(
         requests,
         origin,
         ast_path.to_vec().into(),
-        AmdDefineFactoryType::Function,
+        factory_type,
         issue_source(source, span),
         in_try,
     ));
@@ -2838,6 +2886,18 @@ async fn analyze_amd_define_with_deps(
     Ok(())
 }
 
+/// The factory of `define(deps, factory)` is called with the dependencies when it's a function,
+/// and is the value of the module otherwise.
+fn amd_factory_type(factory: &JsValue) -> AmdDefineFactoryType {
+    match factory {
+        JsValue::Function(..) => AmdDefineFactoryType::Function,
+        JsValue::Object { .. } | JsValue::Array { .. } | JsValue::Constant(..) => {
+            AmdDefineFactoryType::Value
+        }
+        _ => AmdDefineFactoryType::Unknown,
+    }
+}
+
 /// Used to generate the "root" path to a __filename/__dirname/import.meta.url
 /// reference.
 pub async fn as_abs_path(path: FileSystemPath) -> Result<String> {
@@ -2853,11 +2913,46 @@ async fn require_resolve(path: FileSystemPath) -> Result<String> {
     Ok(format!("/ROOT/{}", path.path.as_str()))
 }
 
-async fn early_value_visitor(mut v: JsValue) -> Result<(JsValue, bool)> {
+async fn early_value_visitor(
+    origin: Vc<Box<dyn ResolveOrigin>>,
+    mut v: JsValue,
+) -> Result<(JsValue, bool)> {
+    if let Some(value) = imported_constant(origin, &v).await? {
+        return Ok((value, true));
+    }
     let modified = early_replace_builtin(&mut v);
     Ok((v, modified))
 }
 
+/// Evaluates an access to an export of another module to the value of that export, if it's a
+/// compile time constant. The imported module has already been replaced with an unknown value
+/// at this point, which still holds the original [JsValue::Module].
+async fn imported_constant(
+    origin: Vc<Box<dyn ResolveOrigin>>,
+    v: &JsValue,
+) -> Result<Option<JsValue>> {
+    let JsValue::Member(_, obj, prop) = v else {
+        return Ok(None);
+    };
+    if let JsValue::Unknown {
+        original_value: Some(original),
+        ..
+    } = &**obj
+        && let JsValue::Module(ModuleValue {
+            module,
+            annotations,
+        }) = &**original
+        && annotations.module_type().is_none()
+        && let Some(export) = prop.as_str()
+    {
+        let exports = imported_constant_exports(origin, module.as_str().into()).await?;
+        if let Some(value) = exports.get(export) {
+            return Ok(Some(value.try_into()?));
+        }
+    }
+    Ok(None)
+}
+
 async fn value_visitor(
     origin: Vc<Box<dyn ResolveOrigin>>,
     v: JsValue,
@@ -3199,7 +3294,7 @@ struct ModuleReferencesVisitor<'a> {
     esm_exports: BTreeMap<RcStr, EsmExport>,
     webpack_runtime: Option<(RcStr, Span)>,
     webpack_entry: bool,
-    webpack_chunks: Vec<Lit>,
+    webpack_chunks: Vec<RcStr>,
 }
 
 impl<'a> ModuleReferencesVisitor<'a> {
@@ -3507,10 +3602,22 @@ impl VisitAstPath for ModuleReferencesVisitor<'_> {
                         && let Some(array) = chunk_ids.as_array()
                     {
                         for elem in array.elems.iter().flatten() {
-                            if let ExprOrSpread { spread: None, expr } = elem
-                                && let Some(lit) = expr.as_lit()
-                            {
-                                self.webpack_chunks.push(lit.clone());
+                            if let ExprOrSpread { spread: None, expr } = elem {
+                                if let Some(chunk_id) = eval_chunk_id(self.eval_context, expr) {
+                                    self.webpack_chunks.push(chunk_id);
+                                } else {
+                                    HANDLER.with(|handler| {
+                                        handler.span_warn_with_code(
+                                            expr.span(),
+                                            "__webpack_require__.X() is called with a chunk id \
+                                             that is not a constant",
+                                            DiagnosticId::Lint(
+                                                errors::failed_to_analyse::ecmascript::WEBPACK_CHUNK
+                                                    .to_string(),
+                                            ),
+                                        )
+                                    });
+                                }
                             }
                         }
                     }
@@ -1,10 +1,11 @@
 use anyhow::Result;
 use turbo_rcstr::{RcStr, rcstr};
 use turbo_tasks::{ResolvedVc, Vc};
-use turbo_tasks_fs::FileContent;
+use turbo_tasks_fs::{FileContent, FileSystemPath};
 use turbopack_core::{
     asset::{Asset, AssetContent},
     ident::AssetIdent,
+    issue::{Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString},
     source::Source,
 };
 
@@ -50,3 +51,167 @@ impl Asset for TextContentFileSource {
         Ok(AssetContent::file(content))
     }
 }
+
+/// A source asset that exports the binary content of an asset as `Uint8Array` in the default
+/// export of a JS module.
+#[turbo_tasks::value]
+pub struct BytesContentFileSource {
+    pub source: ResolvedVc<Box<dyn Source>>,
+}
+
+#[turbo_tasks::value_impl]
+impl BytesContentFileSource {
+    #[turbo_tasks::function]
+    pub fn new(source: ResolvedVc<Box<dyn Source>>) -> Vc<Self> {
+        BytesContentFileSource { source }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Source for BytesContentFileSource {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        self.source
+            .ident()
+            .with_modifier(rcstr!("bytes content"))
+            .rename_as(rcstr!("*.mjs"))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for BytesContentFileSource {
+    #[turbo_tasks::function]
+    async fn content(&self) -> Result<Vc<AssetContent>> {
+        let source = self.source.content().file_content();
+        let FileContent::Content(content) = &*source.await? else {
+            return Ok(AssetContent::file(FileContent::NotFound.cell()));
+        };
+        // Every byte is encoded as one character of a string, which is more compact than an array
+        // of numbers
+        let bytes: String = content
+            .content()
+            .to_bytes()
+            .iter()
+            .map(|&byte| byte as char)
+            .collect();
+        let code: RcStr = format!(
+            "export default Uint8Array.from({}, (c) => c.charCodeAt(0));",
+            StringifyJs(&bytes)
+        )
+        .into();
+        let content = FileContent::Content(code.into()).cell();
+        Ok(AssetContent::file(content))
+    }
+}
+
+/// A source asset that exports the content of a CSS asset as constructable `CSSStyleSheet` in the
+/// default export of a JS module.
+///
+/// The CSS is passed to the style sheet as is, so `url()`s aren't rewritten to the output paths and
+/// `@import` rules are ignored by the browser. A [CssStyleSheetIssue] is reported when the CSS
+/// contains them.
+#[turbo_tasks::value]
+pub struct CssStyleSheetFileSource {
+    pub source: ResolvedVc<Box<dyn Source>>,
+}
+
+#[turbo_tasks::value_impl]
+impl CssStyleSheetFileSource {
+    #[turbo_tasks::function]
+    pub fn new(source: ResolvedVc<Box<dyn Source>>) -> Vc<Self> {
+        CssStyleSheetFileSource { source }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Source for CssStyleSheetFileSource {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        self.source
+            .ident()
+            .with_modifier(rcstr!("css style sheet"))
+            .rename_as(rcstr!("*.mjs"))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for CssStyleSheetFileSource {
+    #[turbo_tasks::function]
+    async fn content(&self) -> Result<Vc<AssetContent>> {
+        let source = self.source.content().file_content();
+        let FileContent::Content(content) = &*source.await? else {
+            return Ok(AssetContent::file(FileContent::NotFound.cell()));
+        };
+        let text = content.content().to_str()?;
+        let lowercase_text = text.to_ascii_lowercase();
+        let unsupported = [("url(", rcstr!("url()")), ("@import", rcstr!("@import"))]
+            .into_iter()
+            .filter(|(pattern, _)| lowercase_text.contains(pattern))
+            .map(|(_, feature)| feature)
+            .collect::<Vec<_>>();
+        if !unsupported.is_empty() {
+            CssStyleSheetIssue {
+                path: self.source.ident().path().owned().await?,
+                unsupported,
+            }
+            .resolved_cell()
+            .emit();
+        }
+        let code: RcStr = format!(
+            "const sheet = new CSSStyleSheet();\nsheet.replaceSync({});\nexport default sheet;",
+            StringifyJs(&text)
+        )
+        .into();
+        let content = FileContent::Content(code.into()).cell();
+        Ok(AssetContent::file(content))
+    }
+}
+
+/// Reported when CSS imported with `with { type: "css" }` uses features that need the CSS to be
+/// processed, which [CssStyleSheetFileSource] doesn't do.
+#[turbo_tasks::value(shared)]
+pub struct CssStyleSheetIssue {
+    pub path: FileSystemPath,
+    /// The unsupported features, i.e. `url()` and `@import`.
+    pub unsupported: Vec<RcStr>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for CssStyleSheetIssue {
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Text(rcstr!("CSS imported as style sheet is not processed")).cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Transform.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path.clone().cell()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        let mut line = vec![StyledString::Text(rcstr!(
+            "The CSS is passed to the `CSSStyleSheet` as is, but it uses "
+        ))];
+        for (i, feature) in self.unsupported.iter().enumerate() {
+            if i > 0 {
+                line.push(StyledString::Text(rcstr!(" and ")));
+            }
+            line.push(StyledString::Code(feature.clone()));
+        }
+        line.push(StyledString::Text(rcstr!(
+            ". URLs are resolved relative to the document instead of the CSS file and `@import` \
+             rules are ignored. Import the CSS without `with { type: \"css\" }` to process it."
+        )));
+        Vc::cell(Some(StyledString::Line(line).resolved_cell()))
+    }
+}
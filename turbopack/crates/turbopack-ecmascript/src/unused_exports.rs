@@ -0,0 +1,290 @@
+//! Finds exports of first-party modules that are never imported anywhere in the module graph, to
+//! help with removing dead code.
+//!
+//! This is based on the export usage of the edges of the module graph, which is only tracked for
+//! individual exports when tree shaking is enabled. All modules created from the same file (e.g.
+//! in different layers or as tree shaking facades) are treated as one, so an export is only
+//! reported when none of them has it imported. The JSON report looks like this:
+//!
+//! ```json
+//! {
+//!   "modules": [
+//!     {
+//!       "path": "[project]/src/utils.ts",
+//!       "exports": ["formatDate", "parseDate"]
+//!     }
+//!   ]
+//! }
+//! ```
+
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use turbo_rcstr::{RcStr, rcstr};
+use turbo_tasks::{FxIndexMap, NonLocalValue, ResolvedVc, TryJoinIterExt, Vc, trace::TraceRawVcs};
+use turbo_tasks_fs::FileSystemPath;
+use turbopack_core::{
+    issue::{Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    module::Module,
+    module_graph::ModuleGraph,
+    resolve::ExportUsage,
+};
+
+use crate::{
+    chunk::{EcmascriptChunkPlaceable, EcmascriptExports},
+    references::esm::export::EsmExport,
+};
+
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedExports {
+    /// The modules with unused exports, sorted by path.
+    pub modules: Vec<ModuleUnusedExports>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs, NonLocalValue)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleUnusedExports {
+    /// The path of the module.
+    pub path: RcStr,
+    /// The names of the exports that are never imported, sorted by name.
+    pub exports: Vec<RcStr>,
+}
+
+impl UnusedExports {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// The exports of a file that are imported somewhere in the graph.
+#[derive(Default)]
+enum UsedExports {
+    #[default]
+    None,
+    Named(FxHashSet<RcStr>),
+    All,
+}
+
+impl UsedExports {
+    fn add(&mut self, usage: &ExportUsage) {
+        match (&mut *self, usage) {
+            (Self::All, _) | (_, ExportUsage::Evaluation) => {}
+            (_, ExportUsage::All) => *self = Self::All,
+            (Self::None, ExportUsage::Named(name)) => {
+                *self = Self::Named(FxHashSet::from_iter([name.clone()]))
+            }
+            (Self::Named(names), ExportUsage::Named(name)) => {
+                names.insert(name.clone());
+            }
+        }
+    }
+
+    fn is_used(&self, export: &RcStr) -> bool {
+        match self {
+            Self::None => false,
+            Self::Named(names) => names.contains(export),
+            Self::All => true,
+        }
+    }
+}
+
+#[turbo_tasks::value(transparent)]
+struct UnusedExportsByPath(Vec<(FileSystemPath, Vec<RcStr>)>);
+
+#[turbo_tasks::function]
+async fn unused_exports_by_path(
+    module_graph: ResolvedVc<ModuleGraph>,
+    project_path: FileSystemPath,
+) -> Result<Vc<UnusedExportsByPath>> {
+    let module_graph = module_graph.await?;
+
+    let mut usages: Vec<(ResolvedVc<Box<dyn Module>>, ExportUsage)> = Vec::new();
+    module_graph
+        .traverse_all_edges_unordered(|(_, ref_data), target| {
+            usages.push((target.module, ref_data.export.clone()));
+            Ok(())
+        })
+        .await?;
+
+    let graphs = module_graph.get_graphs().await?;
+    // The exports of entries are used from outside of the graph
+    usages.extend(
+        graphs
+            .iter()
+            .flat_map(|graph| graph.entry_modules())
+            .map(|module| (module, ExportUsage::All)),
+    );
+    let usage_paths = usages
+        .iter()
+        .map(async |(module, _)| module.ident().path().owned().await)
+        .try_join()
+        .await?;
+    let mut used_exports: FxHashMap<FileSystemPath, UsedExports> = FxHashMap::default();
+    for (path, (_, usage)) in usage_paths.into_iter().zip(usages.iter()) {
+        used_exports.entry(path).or_default().add(usage);
+    }
+
+    let modules = graphs
+        .iter()
+        .flat_map(|graph| graph.iter_nodes())
+        .filter_map(|node| {
+            ResolvedVc::try_sidecast::<Box<dyn EcmascriptChunkPlaceable>>(node.module)
+        })
+        .collect::<FxHashSet<_>>();
+    let module_exports = modules
+        .into_iter()
+        .map(async |module| {
+            let path = module.ident().path().owned().await?;
+            if !path.is_inside_ref(&project_path) || path.path.contains("/node_modules/") {
+                return Ok(None);
+            }
+            let EcmascriptExports::EsmExports(exports) = &*module.get_exports().await? else {
+                return Ok(None);
+            };
+            let exports = exports
+                .await?
+                .exports
+                .iter()
+                .filter(|(name, export)| {
+                    !matches!(export, EsmExport::Error) && !name.starts_with("__turbopack")
+                })
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>();
+            Ok(Some((path, exports)))
+        })
+        .try_join()
+        .await?;
+
+    let mut exports_by_path: FxIndexMap<FileSystemPath, BTreeSet<RcStr>> = FxIndexMap::default();
+    for (path, exports) in module_exports.into_iter().flatten() {
+        exports_by_path.entry(path).or_default().extend(exports);
+    }
+
+    let mut unused_exports = exports_by_path
+        .into_iter()
+        .filter_map(|(path, exports)| {
+            let used = used_exports.get(&path);
+            let unused = exports
+                .into_iter()
+                .filter(|export| !used.is_some_and(|used| used.is_used(export)))
+                .collect::<Vec<_>>();
+            (!unused.is_empty()).then_some((path, unused))
+        })
+        .collect::<Vec<_>>();
+    unused_exports.sort_by(|(a, _), (b, _)| a.path.cmp(&b.path));
+
+    Ok(Vc::cell(unused_exports))
+}
+
+/// Finds all exports of modules inside of `project_path` that are never imported in the module
+/// graph. Modules in `node_modules` and the exports of the entries of the graph are ignored.
+#[turbo_tasks::function]
+pub async fn find_unused_exports(
+    module_graph: ResolvedVc<ModuleGraph>,
+    project_path: FileSystemPath,
+) -> Result<Vc<UnusedExports>> {
+    let modules = unused_exports_by_path(*module_graph, project_path)
+        .await?
+        .iter()
+        .map(async |(path, exports)| {
+            Ok(ModuleUnusedExports {
+                path: path.value_to_string().owned().await?,
+                exports: exports.clone(),
+            })
+        })
+        .try_join()
+        .await?;
+
+    Ok(UnusedExports { modules }.cell())
+}
+
+/// Emits an [UnusedExportsIssue] for every module inside of `project_path` with exports that are
+/// never imported.
+#[turbo_tasks::function]
+pub async fn check_unused_exports(
+    module_graph: ResolvedVc<ModuleGraph>,
+    project_path: FileSystemPath,
+) -> Result<Vc<()>> {
+    for (path, exports) in &*unused_exports_by_path(*module_graph, project_path).await? {
+        UnusedExportsIssue {
+            path: path.clone(),
+            exports: exports.clone(),
+        }
+        .resolved_cell()
+        .emit();
+    }
+    Ok(Vc::cell(()))
+}
+
+#[turbo_tasks::value(shared)]
+pub struct UnusedExportsIssue {
+    pub path: FileSystemPath,
+    pub exports: Vec<RcStr>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for UnusedExportsIssue {
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Text(if self.exports.len() == 1 {
+            rcstr!("Export is never imported")
+        } else {
+            format!("{} exports are never imported", self.exports.len()).into()
+        })
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Analysis.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path.clone().cell()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        let mut line = vec![StyledString::Text(rcstr!(
+            "These exports are not imported by any module and could be removed: "
+        ))];
+        for (i, export) in self.exports.iter().enumerate() {
+            if i > 0 {
+                line.push(StyledString::Text(rcstr!(", ")));
+            }
+            line.push(StyledString::Code(export.clone()));
+        }
+        Vc::cell(Some(StyledString::Line(line).resolved_cell()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_used_exports() {
+        let mut used = UsedExports::default();
+        used.add(&ExportUsage::Evaluation);
+        assert!(!used.is_used(&rcstr!("a")));
+
+        used.add(&ExportUsage::Named(rcstr!("a")));
+        used.add(&ExportUsage::Named(rcstr!("b")));
+        assert!(used.is_used(&rcstr!("a")));
+        assert!(used.is_used(&rcstr!("b")));
+        assert!(!used.is_used(&rcstr!("c")));
+
+        used.add(&ExportUsage::All);
+        used.add(&ExportUsage::Named(rcstr!("a")));
+        assert!(used.is_used(&rcstr!("c")));
+    }
+}
@@ -1,11 +1,14 @@
+use std::mem::take;
+
 use anyhow::Result;
-use swc_core::ecma::ast::Lit;
+use swc_core::ecma::ast::Expr;
 use turbo_rcstr::{RcStr, rcstr};
 use turbo_tasks::{ResolvedVc, ValueToString, Vc};
 use turbopack_core::{
     asset::{Asset, AssetContent},
     file_source::FileSource,
     ident::AssetIdent,
+    issue::{IssueExt, IssueSeverity, StyledString, analyze::AnalyzeIssue},
     module::Module,
     reference::{ModuleReference, ModuleReferences},
     reference_type::{CommonJsReferenceSubType, ReferenceType},
@@ -20,7 +23,15 @@ use turbopack_core::{
 use turbopack_resolve::ecmascript::apply_cjs_specific_options;
 
 use self::{parse::WebpackRuntime, references::module_references};
-use crate::EcmascriptInputTransforms;
+use crate::{
+    EcmascriptInputTransforms,
+    analyzer::{
+        ConstantNumber, ConstantValue, JsValue, ObjectPart, builtin::replace_builtin,
+        graph::EvalContext,
+    },
+    errors,
+    utils::js_value_to_pattern,
+};
 
 pub mod parse;
 pub(crate) mod references;
@@ -71,8 +82,9 @@ impl Asset for WebpackModuleAsset {
 
 #[turbo_tasks::value(shared)]
 pub struct WebpackChunkAssetReference {
-    #[turbo_tasks(trace_ignore)]
-    pub chunk_id: Lit,
+    /// The module that loads the chunk, used to report chunks that can't be resolved.
+    pub source: ResolvedVc<Box<dyn Source>>,
+    pub chunk_id: RcStr,
     pub runtime: ResolvedVc<WebpackRuntime>,
     pub transforms: ResolvedVc<EcmascriptInputTransforms>,
 }
@@ -84,16 +96,32 @@ impl ModuleReference for WebpackChunkAssetReference {
         let runtime = self.runtime.await?;
         Ok(match &*runtime {
             WebpackRuntime::Webpack5 {
-                chunk_request_expr: _,
+                chunk_request_expr,
                 context_path,
             } => {
-                // TODO determine filename from chunk_request_expr
-                let chunk_id = match &self.chunk_id {
-                    Lit::Str(str) => str.value.to_string(),
-                    Lit::Num(num) => format!("{num}"),
-                    _ => todo!(),
+                let Some(filename) = chunk_request(chunk_request_expr, &self.chunk_id) else {
+                    AnalyzeIssue::new(
+                        IssueSeverity::Warning,
+                        self.source.ident(),
+                        Vc::cell(rcstr!("unsupported webpack chunk filename")),
+                        StyledString::Text(
+                            format!(
+                                "The filename of webpack chunk {} can't be determined from the \
+                                 webpack runtime ({}).",
+                                self.chunk_id,
+                                chunk_request_expr.explain(10, 0).0
+                            )
+                            .into(),
+                        )
+                        .cell(),
+                        Some(errors::failed_to_analyse::ecmascript::WEBPACK_CHUNK.into()),
+                        None,
+                    )
+                    .to_resolved()
+                    .await?
+                    .emit();
+                    return Ok(*ModuleResolveResult::unresolvable());
                 };
-                let filename = format!("./chunks/{chunk_id}.js");
                 let source = Vc::upcast(FileSource::new(context_path.join(&filename)?));
 
                 *ModuleResolveResult::module(ResolvedVc::upcast(
@@ -111,15 +139,63 @@ impl ModuleReference for WebpackChunkAssetReference {
 impl ValueToString for WebpackChunkAssetReference {
     #[turbo_tasks::function]
     fn to_string(&self) -> Vc<RcStr> {
-        let chunk_id = match &self.chunk_id {
-            Lit::Str(str) => str.value.to_string(),
-            Lit::Num(num) => format!("{num}"),
-            _ => todo!(),
-        };
-        Vc::cell(format!("webpack chunk {chunk_id}").into())
+        Vc::cell(format!("webpack chunk {}", self.chunk_id).into())
     }
 }
 
+/// Evaluates the chunk id passed to `__webpack_require__.e`, e.g. `179`, `"vendor"` or
+/// `` `vendor-${179}` ``. Returns `None` when the chunk id is not a constant.
+pub(crate) fn eval_chunk_id(eval_context: &EvalContext, expr: &Expr) -> Option<RcStr> {
+    let mut value = eval_context.eval(expr);
+    value.normalize();
+    js_value_to_pattern(&value).as_constant_string().cloned()
+}
+
+/// Evaluates the chunk filename function of the webpack runtime for a chunk, e.g.
+/// `"./chunks/" + ({ 179: "vendor" }[chunkId] || chunkId) + ".js"`. Returns `None` when the
+/// filename doesn't evaluate to a constant.
+fn chunk_request(chunk_request_expr: &JsValue, chunk_id: &RcStr) -> Option<RcStr> {
+    let mut value = chunk_request_expr.clone();
+    value.visit_mut(&mut |value| match value {
+        JsValue::FreeVar(name) if &**name == "chunkId" => {
+            *value = JsValue::from(chunk_id.clone());
+            true
+        }
+        // The chunk maps of the runtime are never mutated. Numeric keys are converted to strings
+        // as they are looked up with the chunk id as a string.
+        JsValue::Object { parts, mutable, .. } => {
+            let mut modified = take(mutable);
+            for part in parts {
+                if let ObjectPart::KeyValue(key, _) = part
+                    && let JsValue::Constant(ConstantValue::Num(ConstantNumber(num))) = key
+                {
+                    *key = JsValue::from(num.to_string());
+                    modified = true;
+                }
+            }
+            modified
+        }
+        _ => false,
+    });
+    loop {
+        value.normalize();
+        let modified = value.visit_mut(&mut |value| {
+            let modified = replace_builtin(value);
+            // Looking up a chunk that isn't in a chunk map results in `undefined`, which the `||`
+            // has to know is falsy to fall back to the chunk id.
+            if matches!(value, JsValue::FreeVar(name) if &**name == "undefined") {
+                *value = JsValue::Constant(ConstantValue::Undefined);
+                return true;
+            }
+            modified
+        });
+        if !modified {
+            break;
+        }
+    }
+    js_value_to_pattern(&value).as_constant_string().cloned()
+}
+
 #[turbo_tasks::value(shared)]
 pub struct WebpackEntryAssetReference {
     pub source: ResolvedVc<Box<dyn Source>>,
@@ -194,3 +270,65 @@ impl ValueToString for WebpackRuntimeAssetReference {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use swc_core::ecma::atoms::atom;
+
+    use super::*;
+
+    fn chunk_id() -> JsValue {
+        JsValue::FreeVar(atom!("chunkId"))
+    }
+
+    #[test]
+    fn test_chunk_request() {
+        // "./chunks/" + ({ 179: "lib", "vendor-react": "react" }[chunkId] || chunkId) + ".js"
+        let chunk_request_expr = JsValue::add(vec![
+            JsValue::add(vec![
+                "./chunks/".into(),
+                JsValue::logical_or(vec![
+                    JsValue::member(
+                        Box::new(JsValue::object(vec![
+                            ObjectPart::KeyValue(179.0.into(), "lib".into()),
+                            ObjectPart::KeyValue("vendor-react".into(), "react".into()),
+                        ])),
+                        Box::new(chunk_id()),
+                    ),
+                    chunk_id(),
+                ]),
+            ]),
+            ".js".into(),
+        ]);
+
+        assert_eq!(
+            chunk_request(&chunk_request_expr, &rcstr!("179")).as_deref(),
+            Some("./chunks/lib.js")
+        );
+        assert_eq!(
+            chunk_request(&chunk_request_expr, &rcstr!("vendor-react")).as_deref(),
+            Some("./chunks/react.js")
+        );
+        assert_eq!(
+            chunk_request(&chunk_request_expr, &rcstr!("42")).as_deref(),
+            Some("./chunks/42.js")
+        );
+    }
+
+    #[test]
+    fn test_chunk_request_unknown() {
+        // "./chunks/" + chunkId + "." + hashes[chunkId] + ".js" with an unknown hash map
+        let chunk_request_expr = JsValue::add(vec![
+            "./chunks/".into(),
+            chunk_id(),
+            ".".into(),
+            JsValue::member(
+                Box::new(JsValue::FreeVar(atom!("hashes"))),
+                Box::new(chunk_id()),
+            ),
+            ".js".into(),
+        ]);
+
+        assert_eq!(chunk_request(&chunk_request_expr, &rcstr!("42")), None);
+    }
+}
@@ -5,10 +5,11 @@ use swc_core::{
     common::GLOBALS,
     ecma::{
         ast::{
-            ArrowExpr, AssignOp, AssignTarget, BinExpr, BinaryOp, CallExpr, Callee, Expr,
-            ExprOrSpread, ExprStmt, FnExpr, Lit, Module, ModuleItem, Program, Script,
-            SimpleAssignTarget, Stmt,
+            ArrowExpr, AssignOp, AssignTarget, BinExpr, BinaryOp, BlockStmtOrExpr, CallExpr,
+            Callee, Expr, ExprOrSpread, ExprStmt, FnExpr, Ident, Lit, Module, ModuleItem, Program,
+            Script, SimpleAssignTarget, Stmt,
         },
+        atoms::atom,
         visit::{Visit, VisitWith},
     },
 };
@@ -27,8 +28,8 @@ use crate::{
 #[derive(Debug)]
 pub enum WebpackRuntime {
     Webpack5 {
-        /// There is a [JsValue]::FreeVar("chunkId") that need to be replaced
-        /// before converting to string
+        /// The request of a chunk relative to `context_path`. The id of the chunk is a
+        /// [JsValue]::FreeVar("chunkId") that need to be replaced before converting to string.
         #[turbo_tasks(trace_ignore)]
         chunk_request_expr: JsValue,
         context_path: FileSystemPath,
@@ -142,17 +143,55 @@ fn get_fn_body(expr: &Expr) -> Option<&Vec<Stmt>> {
     None
 }
 
-fn get_javascript_chunk_filename(stmts: &Vec<Stmt>, eval_context: &EvalContext) -> Option<JsValue> {
-    if let Some(expr) = get_assignment(stmts, "__webpack_require__.u")
-        && let Some(stmts) = get_fn_body(expr)
-        && let Some(ret) = stmts.iter().find_map(|stmt| stmt.as_return_stmt())
-        && let Some(expr) = &ret.arg
+/// Returns the first parameter and the returned expression of a function expression or an arrow
+/// function.
+fn get_fn_return(expr: &Expr) -> Option<(Option<&Ident>, &Expr)> {
+    let expr = unparen(expr);
+    if let Some(FnExpr { function, .. }) = expr.as_fn_expr()
+        && let Some(body) = &function.body
     {
-        return Some(eval_context.eval(expr));
+        let param = function
+            .params
+            .first()
+            .and_then(|param| param.pat.as_ident());
+        let ret = body.stmts.iter().find_map(|stmt| stmt.as_return_stmt())?;
+        return Some((param.map(|param| &param.id), ret.arg.as_deref()?));
+    }
+    if let Some(ArrowExpr { params, body, .. }) = expr.as_arrow() {
+        let param = params.first().and_then(|param| param.as_ident());
+        let ret = match &**body {
+            BlockStmtOrExpr::BlockStmt(block) => block
+                .stmts
+                .iter()
+                .find_map(|stmt| stmt.as_return_stmt())?
+                .arg
+                .as_deref()?,
+            BlockStmtOrExpr::Expr(expr) => expr,
+        };
+        return Some((param.map(|param| &param.id), ret));
     }
     None
 }
 
+fn get_javascript_chunk_filename(stmts: &Vec<Stmt>, eval_context: &EvalContext) -> Option<JsValue> {
+    let expr = get_assignment(stmts, "__webpack_require__.u")?;
+    let (param, ret) = get_fn_return(expr)?;
+    let mut value = eval_context.eval(ret);
+    if let Some(param) = param {
+        let param = param.to_id();
+        value.visit_mut(&mut |value| {
+            if let JsValue::Variable(id) = value
+                && *id == param
+            {
+                *value = JsValue::FreeVar(atom!("chunkId"));
+                return true;
+            }
+            false
+        });
+    }
+    Some(value)
+}
+
 struct RequirePrefixVisitor {
     result: Option<Lit>,
 }
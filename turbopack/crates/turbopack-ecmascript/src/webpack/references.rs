@@ -1,8 +1,11 @@
 use anyhow::Result;
 use swc_core::{
-    common::errors::{HANDLER, Handler},
+    common::{
+        GLOBALS, Spanned,
+        errors::{DiagnosticId, HANDLER, Handler},
+    },
     ecma::{
-        ast::{CallExpr, Expr, ExprOrSpread},
+        ast::{CallExpr, ExprOrSpread},
         visit::{Visit, VisitWith},
     },
 };
@@ -14,9 +17,11 @@ use turbopack_core::{
 };
 use turbopack_swc_utils::emitter::IssueEmitter;
 
-use super::{WebpackChunkAssetReference, parse::WebpackRuntime};
+use super::{WebpackChunkAssetReference, eval_chunk_id, parse::WebpackRuntime};
 use crate::{
     EcmascriptInputTransforms, EcmascriptModuleAssetType,
+    analyzer::graph::EvalContext,
+    errors,
     parse::{ParseResult, parse},
 };
 
@@ -31,11 +36,15 @@ pub async fn module_references(
         ParseResult::Ok {
             program,
             source_map,
+            eval_context,
+            globals,
             ..
         } => {
             let mut references = Vec::new();
             let mut visitor = ModuleReferencesVisitor {
+                eval_context,
                 references: &mut references,
+                source,
                 runtime,
                 transforms,
             };
@@ -45,8 +54,10 @@ pub async fn module_references(
                 Some(rcstr!("Parsing webpack bundle failed")),
             );
             let handler = Handler::with_emitter(true, false, Box::new(emitter));
-            HANDLER.set(&handler, || {
-                program.visit_with(&mut visitor);
+            GLOBALS.set(globals, || {
+                HANDLER.set(&handler, || {
+                    program.visit_with(&mut visitor);
+                })
             });
             collector.emit().await?;
             Ok(Vc::cell(references))
@@ -56,6 +67,8 @@ pub async fn module_references(
 }
 
 struct ModuleReferencesVisitor<'a> {
+    eval_context: &'a EvalContext,
+    source: ResolvedVc<Box<dyn Source>>,
     runtime: ResolvedVc<WebpackRuntime>,
     references: &'a mut Vec<ResolvedVc<Box<dyn ModuleReference>>>,
     transforms: ResolvedVc<EcmascriptInputTransforms>,
@@ -68,16 +81,28 @@ impl Visit for ModuleReferencesVisitor<'_> {
             && &*obj.sym == "__webpack_require__"
             && &*prop.sym == "e"
             && let [ExprOrSpread { spread: None, expr }] = &call.args[..]
-            && let Expr::Lit(lit) = &**expr
         {
-            self.references.push(ResolvedVc::upcast(
-                WebpackChunkAssetReference {
-                    chunk_id: lit.clone(),
-                    runtime: self.runtime,
-                    transforms: self.transforms,
-                }
-                .resolved_cell(),
-            ));
+            if let Some(chunk_id) = eval_chunk_id(self.eval_context, expr) {
+                self.references.push(ResolvedVc::upcast(
+                    WebpackChunkAssetReference {
+                        source: self.source,
+                        chunk_id,
+                        runtime: self.runtime,
+                        transforms: self.transforms,
+                    }
+                    .resolved_cell(),
+                ));
+            } else {
+                HANDLER.with(|handler| {
+                    handler.span_warn_with_code(
+                        expr.span(),
+                        "__webpack_require__.e() is called with a chunk id that is not a constant",
+                        DiagnosticId::Lint(
+                            errors::failed_to_analyse::ecmascript::WEBPACK_CHUNK.to_string(),
+                        ),
+                    )
+                });
+            }
         }
         call.visit_children_with(self);
     }
@@ -0,0 +1,7 @@
+function load(n = 'en') {
+  return import(`./locales/${n}.json`)
+}
+
+load()
+load(undefined)
+load('de')
@@ -0,0 +1,18 @@
+// Functions that are not only called directly can be called with any argument
+
+function passedAsValue(n) {
+  return import(`./a/${n}.js`)
+}
+passedAsValue('en')
+setTimeout(passedAsValue, 0)
+
+export function exported(n) {
+  return import(`./b/${n}.js`)
+}
+exported('en')
+
+function calledWithSpread(n) {
+  return import(`./c/${n}.js`)
+}
+calledWithSpread('en')
+calledWithSpread(...['de'])
@@ -0,0 +1,4 @@
+const load = (n) => import(`./locales/${n}.json`)
+
+load('en')
+load('de')
@@ -170,7 +170,7 @@ async fn emit_evaluate_pool_assets_operation(
     );
 
     let bootstrap = chunking_context.root_entry_chunk_group_asset(
-        entrypoint.clone(),
+        entrypoint,
         Vc::<EvaluatableAssets>::cell(runtime_entries)
             .with_entry(*ResolvedVc::try_downcast(entry_module).unwrap()),
         module_graph,
@@ -188,7 +188,8 @@ async fn emit_evaluate_pool_assets_operation(
     Ok(EmittedEvaluatePoolAssets {
         bootstrap: bootstrap.to_resolved().await?,
         output_root,
-        entrypoint: entrypoint.clone(),
+        // The chunking context might change the extension of the entry chunk, e.g. for ES modules.
+        entrypoint: bootstrap.path().owned().await?,
     }
     .cell())
 }
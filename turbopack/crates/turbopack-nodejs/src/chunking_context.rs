@@ -31,7 +31,8 @@ use turbopack_ecmascript::{
 use turbopack_ecmascript_runtime::RuntimeType;
 
 use crate::ecmascript::node::{
-    chunk::EcmascriptBuildNodeChunk, entry::chunk::EcmascriptBuildNodeEntryChunk,
+    chunk::EcmascriptBuildNodeChunk,
+    entry::{chunk::EcmascriptBuildNodeEntryChunk, library::EcmascriptBuildNodeLibraryChunk},
 };
 
 /// A builder for [`Vc<NodeJsChunkingContext>`].
@@ -74,6 +75,11 @@ impl NodeJsChunkingContextBuilder {
         self
     }
 
+    pub fn debug_ids(mut self, enable_debug_ids: bool) -> Self {
+        self.chunking_context.enable_debug_ids = enable_debug_ids;
+        self
+    }
+
     pub fn runtime_type(mut self, runtime_type: RuntimeType) -> Self {
         self.chunking_context.runtime_type = runtime_type;
         self
@@ -84,6 +90,18 @@ impl NodeJsChunkingContextBuilder {
         self
     }
 
+    pub fn chunk_format(mut self, chunk_format: NodeJsChunkFormat) -> Self {
+        self.chunking_context.chunk_format = chunk_format;
+        self
+    }
+
+    /// Emits entry chunks as library files, which contain the runtime and the modules of their
+    /// chunk group, and export the exports of the entry module in the chunk format.
+    pub fn library(mut self, library: bool) -> Self {
+        self.chunking_context.library = library;
+        self
+    }
+
     pub fn use_file_source_map_uris(mut self) -> Self {
         self.chunking_context.should_use_file_source_map_uris = true;
         self
@@ -118,6 +136,29 @@ impl NodeJsChunkingContextBuilder {
     }
 }
 
+/// The module format of the chunks emitted by a [`NodeJsChunkingContext`].
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone, Copy, Hash, TaskInput)]
+pub enum NodeJsChunkFormat {
+    /// Chunks are CommonJS modules which the runtime loads with `require()`.
+    CommonJs,
+    /// Chunks are ES modules. Entry chunks statically import the runtime and their chunks, and
+    /// async chunks are loaded with `import()`. ES module chunks use the `.mjs` extension, which
+    /// replaces a `.js` extension of the path of entry chunks. ES module externals should be
+    /// loaded with `import()` (`import_externals`), as they might not support `require()`.
+    EsModule,
+}
+
+impl NodeJsChunkFormat {
+    /// The file extension of chunks in this format, including the leading dot.
+    pub fn extension(self) -> RcStr {
+        match self {
+            NodeJsChunkFormat::CommonJs => rcstr!(".js"),
+            NodeJsChunkFormat::EsModule => rcstr!(".mjs"),
+        }
+    }
+}
+
 /// A chunking context for build mode.
 #[turbo_tasks::value]
 #[derive(Debug, Clone, Hash, TaskInput)]
@@ -146,12 +187,18 @@ pub struct NodeJsChunkingContext {
     enable_module_merging: bool,
     /// Enable dynamic chunk content loading.
     enable_dynamic_chunk_content_loading: bool,
+    /// Enable debug IDs for chunks and their source maps
+    enable_debug_ids: bool,
     /// Whether to minify resulting chunks
     minify_type: MinifyType,
     /// Whether to generate source maps
     source_maps_type: SourceMapsType,
     /// Whether to use manifest chunks for lazy compilation
     manifest_chunks: bool,
+    /// The module format of the emitted chunks
+    chunk_format: NodeJsChunkFormat,
+    /// Whether entry chunks are self-contained library files
+    library: bool,
     /// The strategy to use for generating module ids
     module_id_strategy: ResolvedVc<Box<dyn ModuleIdStrategy>>,
     /// The module export usage info, if available.
@@ -186,11 +233,14 @@ impl NodeJsChunkingContext {
                 enable_file_tracing: false,
                 enable_module_merging: false,
                 enable_dynamic_chunk_content_loading: false,
+                enable_debug_ids: false,
                 environment,
                 runtime_type,
                 minify_type: MinifyType::NoMinify,
                 source_maps_type: SourceMapsType::Full,
                 manifest_chunks: false,
+                chunk_format: NodeJsChunkFormat::CommonJs,
+                library: false,
                 should_use_file_source_map_uris: false,
                 module_id_strategy: ResolvedVc::upcast(DevModuleIdStrategy::new_resolved()),
                 export_usage: None,
@@ -234,13 +284,19 @@ impl NodeJsChunkingContext {
     /// Returns the minify type.
     #[turbo_tasks::function]
     pub fn minify_type(&self) -> Vc<MinifyType> {
-        self.minify_type.cell()
+        self.minify_type.clone().cell()
     }
 
     #[turbo_tasks::function]
     pub fn asset_prefix(&self) -> Vc<Option<RcStr>> {
         Vc::cell(self.asset_prefix.clone())
     }
+
+    /// Returns the module format of the emitted chunks.
+    #[turbo_tasks::function]
+    pub fn chunk_format(&self) -> Vc<NodeJsChunkFormat> {
+        self.chunk_format.cell()
+    }
 }
 
 #[turbo_tasks::value_impl]
@@ -285,9 +341,19 @@ impl ChunkingContext for NodeJsChunkingContext {
         Vc::cell(self.enable_dynamic_chunk_content_loading)
     }
 
+    #[turbo_tasks::function]
+    fn is_debug_ids_enabled(&self) -> Vc<bool> {
+        Vc::cell(self.enable_debug_ids)
+    }
+
+    #[turbo_tasks::function]
+    fn source_maps_type(&self) -> Vc<SourceMapsType> {
+        self.source_maps_type.cell()
+    }
+
     #[turbo_tasks::function]
     pub fn minify_type(&self) -> Vc<MinifyType> {
-        self.minify_type.cell()
+        self.minify_type.clone().cell()
     }
 
     #[turbo_tasks::function]
@@ -330,18 +396,12 @@ impl ChunkingContext for NodeJsChunkingContext {
 
     #[turbo_tasks::function]
     fn reference_chunk_source_maps(&self, _chunk: Vc<Box<dyn OutputAsset>>) -> Vc<bool> {
-        Vc::cell(match self.source_maps_type {
-            SourceMapsType::Full => true,
-            SourceMapsType::None => false,
-        })
+        Vc::cell(self.source_maps_type.is_enabled())
     }
 
     #[turbo_tasks::function]
     fn reference_module_source_maps(&self, _module: Vc<Box<dyn Module>>) -> Vc<bool> {
-        Vc::cell(match self.source_maps_type {
-            SourceMapsType::Full => true,
-            SourceMapsType::None => false,
-        })
+        Vc::cell(self.source_maps_type.is_enabled())
     }
 
     #[turbo_tasks::function]
@@ -439,35 +499,71 @@ impl ChunkingContext for NodeJsChunkingContext {
         )
         .await?;
 
-        let extra_chunks = extra_chunks.await?;
-        let other_chunks: Vec<_> = extra_chunks
-            .iter()
-            .copied()
-            .chain(
-                chunks
-                    .iter()
-                    .map(|chunk| self.generate_chunk(**chunk).to_resolved())
-                    .try_join()
-                    .await?,
-            )
-            .collect();
-
         let Some(module) = ResolvedVc::try_sidecast(*evaluatable_assets_ref.last().unwrap()) else {
             bail!("module must be placeable in an ecmascript chunk");
         };
 
-        let asset = ResolvedVc::upcast(
-            EcmascriptBuildNodeEntryChunk::new(
-                path,
-                Vc::cell(other_chunks),
-                evaluatable_assets,
-                *module,
-                module_graph,
-                *self,
+        let extra_chunks = extra_chunks.await?;
+        let asset = if self.await?.library {
+            // Ecmascript chunks are part of the library file, other chunks are emitted as usual
+            let mut ecmascript_chunks = Vec::new();
+            let mut other_chunks = extra_chunks.to_vec();
+            for chunk in chunks {
+                if let Some(ecmascript_chunk) =
+                    ResolvedVc::try_downcast_type::<EcmascriptChunk>(chunk)
+                {
+                    ecmascript_chunks.push(*ecmascript_chunk);
+                } else {
+                    other_chunks.push(self.generate_chunk(*chunk).to_resolved().await?);
+                }
+            }
+
+            ResolvedVc::upcast(
+                EcmascriptBuildNodeLibraryChunk::new(
+                    path,
+                    ecmascript_chunks,
+                    Vc::cell(other_chunks),
+                    evaluatable_assets,
+                    *module,
+                    *self,
+                )
+                .to_resolved()
+                .await?,
+            )
+        } else {
+            // Node.js loads `.js` files as CommonJS, unless the `package.json` says otherwise.
+            let path = if self.await?.chunk_format == NodeJsChunkFormat::EsModule
+                && path.has_extension(".js")
+            {
+                path.with_extension("mjs")
+            } else {
+                path
+            };
+            let other_chunks: Vec<_> = extra_chunks
+                .iter()
+                .copied()
+                .chain(
+                    chunks
+                        .iter()
+                        .map(|chunk| self.generate_chunk(**chunk).to_resolved())
+                        .try_join()
+                        .await?,
+                )
+                .collect();
+
+            ResolvedVc::upcast(
+                EcmascriptBuildNodeEntryChunk::new(
+                    path,
+                    Vc::cell(other_chunks),
+                    evaluatable_assets,
+                    *module,
+                    module_graph,
+                    *self,
+                )
+                .to_resolved()
+                .await?,
             )
-            .to_resolved()
-            .await?,
-        );
+        };
 
         Ok(EntryChunkGroupResult {
             asset,
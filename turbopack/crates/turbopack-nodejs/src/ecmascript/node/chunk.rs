@@ -80,9 +80,11 @@ impl OutputAsset for EcmascriptBuildNodeChunk {
     async fn path(self: Vc<Self>) -> Result<Vc<FileSystemPath>> {
         let this = self.await?;
         let ident = this.chunk.ident().with_modifier(modifier());
+        let extension = this.chunking_context.chunk_format().await?.extension();
+        let name = this.chunk.name().owned().await?;
         Ok(this
             .chunking_context
-            .chunk_path(Some(Vc::upcast(self)), ident, None, rcstr!(".js")))
+            .chunk_path(Some(Vc::upcast(self)), ident, name, extension))
     }
 
     #[turbo_tasks::function]
@@ -1,10 +1,9 @@
 use anyhow::Result;
 use turbo_tasks::{ResolvedVc, Vc};
-use turbo_tasks_fs::{File, rope::RopeBuilder};
 use turbopack_core::{
     asset::AssetContent,
     chunk::{ChunkingContext, MinifyType},
-    code_builder::{Code, CodeBuilder},
+    code_builder::{Code, CodeBuilder, FooterCommentStyle},
     output::OutputAsset,
     source_map::{GenerateSourceMap, OptionStringifiedSourceMap, SourceMapAsset},
     version::{Version, VersionedContent},
@@ -12,7 +11,7 @@ use turbopack_core::{
 use turbopack_ecmascript::{chunk::EcmascriptChunkContent, minify::minify, utils::StringifyJs};
 
 use super::{chunk::EcmascriptBuildNodeChunk, version::EcmascriptBuildNodeChunkVersion};
-use crate::NodeJsChunkingContext;
+use crate::{NodeJsChunkFormat, NodeJsChunkingContext};
 
 #[turbo_tasks::value]
 pub(super) struct EcmascriptBuildNodeChunkContent {
@@ -54,7 +53,20 @@ impl EcmascriptBuildNodeChunkContent {
 
         let mut code = CodeBuilder::default();
 
-        write!(code, "module.exports = [")?;
+        match *this.chunking_context.chunk_format().await? {
+            NodeJsChunkFormat::CommonJs => {
+                write!(code, "module.exports = [")?;
+            }
+            NodeJsChunkFormat::EsModule => {
+                // ES module externals are loaded with `import()`, but CommonJS externals (e.g.
+                // Node.js builtins) are still loaded with `require()`.
+                write!(
+                    code,
+                    "import {{ createRequire }} from \"node:module\";\nconst require = \
+                     createRequire(import.meta.url);\nexport default ["
+                )?;
+            }
+        }
 
         let content = this.content.await?;
         let chunk_items = content.chunk_item_code_and_ids().await?;
@@ -70,8 +82,8 @@ impl EcmascriptBuildNodeChunkContent {
 
         let mut code = code.build();
 
-        if let MinifyType::Minify { mangle } = *this.chunking_context.minify_type().await? {
-            code = minify(code, source_maps, mangle)?;
+        if let MinifyType::Minify(options) = &*this.chunking_context.minify_type().await? {
+            code = minify(code, source_maps, options)?;
         }
 
         Ok(code.cell())
@@ -83,7 +95,7 @@ impl EcmascriptBuildNodeChunkContent {
             self.chunking_context.output_root().owned().await?,
             self.chunk.path().owned().await?,
             *self.content,
-            *self.chunking_context.minify_type().await?,
+            self.chunking_context.minify_type().owned().await?,
         ))
     }
 }
@@ -91,8 +103,11 @@ impl EcmascriptBuildNodeChunkContent {
 #[turbo_tasks::value_impl]
 impl GenerateSourceMap for EcmascriptBuildNodeChunkContent {
     #[turbo_tasks::function]
-    fn generate_source_map(self: Vc<Self>) -> Vc<OptionStringifiedSourceMap> {
-        self.code().generate_source_map()
+    async fn generate_source_map(self: Vc<Self>) -> Result<Vc<OptionStringifiedSourceMap>> {
+        let this = self.await?;
+        Ok(self
+            .code()
+            .generate_chunk_source_map(Vc::upcast(*this.chunking_context)))
     }
 }
 
@@ -101,24 +116,11 @@ impl VersionedContent for EcmascriptBuildNodeChunkContent {
     #[turbo_tasks::function]
     async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
         let this = self.await?;
-        let code = self.code().await?;
-
-        let rope = if code.has_source_map() {
-            use std::io::Write;
-            let mut rope_builder = RopeBuilder::default();
-            rope_builder.concat(code.source_code());
-            let source_map_path = this.source_map.path().await?;
-            write!(
-                rope_builder,
-                "\n\n//# sourceMappingURL={}",
-                urlencoding::encode(source_map_path.file_name())
-            )?;
-            rope_builder.build()
-        } else {
-            code.source_code().clone()
-        };
-
-        Ok(AssetContent::file(File::from(rope).into()))
+        Ok(self.code().chunk_file_content(
+            Vc::upcast(*this.chunking_context),
+            Vc::upcast(*this.source_map),
+            FooterCommentStyle::JavaScript,
+        ))
     }
 
     #[turbo_tasks::function]
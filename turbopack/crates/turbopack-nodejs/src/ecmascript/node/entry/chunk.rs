@@ -3,7 +3,7 @@ use std::io::Write;
 use anyhow::{Result, bail};
 use indoc::writedoc;
 use turbo_rcstr::{RcStr, rcstr};
-use turbo_tasks::{ResolvedVc, ValueToString, Vc};
+use turbo_tasks::{ResolvedVc, TryJoinIterExt, ValueToString, Vc};
 use turbo_tasks_fs::{File, FileSystemPath};
 use turbopack_core::{
     asset::{Asset, AssetContent},
@@ -15,8 +15,11 @@ use turbopack_core::{
 };
 use turbopack_ecmascript::{chunk::EcmascriptChunkPlaceable, utils::StringifyJs};
 
-use super::runtime::EcmascriptBuildNodeRuntimeChunk;
-use crate::NodeJsChunkingContext;
+use super::{
+    exports::{esm_export_names, write_esm_exports},
+    runtime::EcmascriptBuildNodeRuntimeChunk,
+};
+use crate::{NodeJsChunkFormat, NodeJsChunkingContext};
 
 /// An Ecmascript chunk that loads a list of parallel chunks, then instantiates
 /// runtime entries.
@@ -81,33 +84,99 @@ impl EcmascriptBuildNodeEntryChunk {
             );
         };
 
+        let chunk_format = *this.chunking_context.chunk_format().await?;
+        let other_chunk_paths = this
+            .other_chunks
+            .await?
+            .iter()
+            .map(|other_chunk| other_chunk.path().owned())
+            .try_join()
+            .await?;
+
         let mut code = CodeBuilder::default();
 
-        writedoc!(
-            code,
-            r#"
-                var R=require({})({})
-            "#,
-            StringifyJs(&*runtime_relative_path),
-            StringifyJs(chunk_public_path),
-        )?;
+        match chunk_format {
+            NodeJsChunkFormat::CommonJs => {
+                writedoc!(
+                    code,
+                    r#"
+                        var R=require({})({})
+                    "#,
+                    StringifyJs(&*runtime_relative_path),
+                    StringifyJs(chunk_public_path),
+                )?;
+            }
+            NodeJsChunkFormat::EsModule => {
+                writedoc!(
+                    code,
+                    r#"
+                        import r from {}
+                    "#,
+                    StringifyJs(&*runtime_relative_path),
+                )?;
+
+                // ES module chunks are imported statically, so that they are loaded by Node.js
+                // before the entry is evaluated.
+                for (index, other_chunk_path) in other_chunk_paths.iter().enumerate() {
+                    if !other_chunk_path.has_extension(".mjs") {
+                        continue;
+                    }
+                    let Some(other_chunk_relative_path) =
+                        chunk_directory.get_relative_path_to(other_chunk_path)
+                    else {
+                        bail!(
+                            "cannot find a relative path from the chunk ({}) to the chunk ({})",
+                            chunk_path.to_string(),
+                            other_chunk_path.to_string(),
+                        );
+                    };
+                    writedoc!(
+                        code,
+                        r#"
+                            import c{} from {}
+                        "#,
+                        index,
+                        StringifyJs(&*other_chunk_relative_path),
+                    )?;
+                }
 
-        let other_chunks = this.other_chunks.await?;
-        for other_chunk in &*other_chunks {
-            let other_chunk_path = &*other_chunk.path().await?;
-            if let Some(other_chunk_public_path) = output_root.get_path_to(other_chunk_path) {
                 writedoc!(
                     code,
-                    // TODO(WEB-1112) This should call `require()` directly, perhaps as an argument
-                    // to `loadChunk`.
                     r#"
-                        R.c({})
+                        const R=r({})
                     "#,
-                    StringifyJs(&other_chunk_public_path)
+                    StringifyJs(chunk_public_path),
                 )?;
             }
         }
 
+        for (index, other_chunk_path) in other_chunk_paths.iter().enumerate() {
+            if let Some(other_chunk_public_path) = output_root.get_path_to(other_chunk_path) {
+                if chunk_format == NodeJsChunkFormat::EsModule
+                    && other_chunk_path.has_extension(".mjs")
+                {
+                    writedoc!(
+                        code,
+                        r#"
+                            R.i({}, c{})
+                        "#,
+                        StringifyJs(&other_chunk_public_path),
+                        index,
+                    )?;
+                } else {
+                    writedoc!(
+                        code,
+                        // TODO(WEB-1112) This should call `require()` directly, perhaps as an
+                        // argument to `loadChunk`.
+                        r#"
+                            R.c({})
+                        "#,
+                        StringifyJs(&other_chunk_public_path)
+                    )?;
+                }
+            }
+        }
+
         let evaluatable_assets = this.evaluatable_assets.await?;
         for evaluatable_asset in &*evaluatable_assets {
             if let Some(placeable) =
@@ -132,13 +201,22 @@ impl EcmascriptBuildNodeEntryChunk {
             .chunk_item_id(Vc::upcast(*this.chunking_context))
             .await?;
 
-        writedoc!(
-            code,
-            r#"
-                module.exports=R.m({}).exports
-            "#,
-            StringifyJs(&*runtime_module_id),
-        )?;
+        match chunk_format {
+            NodeJsChunkFormat::CommonJs => {
+                writedoc!(
+                    code,
+                    r#"
+                        module.exports=R.m({}).exports
+                    "#,
+                    StringifyJs(&*runtime_module_id),
+                )?;
+            }
+            NodeJsChunkFormat::EsModule => {
+                let export_names =
+                    esm_export_names(*this.exported_module, *this.chunking_context).await?;
+                write_esm_exports(&mut code, &runtime_module_id, export_names.as_deref())?;
+            }
+        }
 
         Ok(Code::cell(code.build()))
     }
@@ -0,0 +1,232 @@
+use std::io::Write;
+
+use anyhow::Result;
+use indoc::writedoc;
+use turbo_rcstr::{RcStr, rcstr};
+use turbo_tasks::{ResolvedVc, Vc};
+use turbo_tasks_fs::FileSystemPath;
+use turbopack_core::{
+    chunk::{ChunkingContext, ModuleId},
+    code_builder::CodeBuilder,
+    issue::{Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    module::Module,
+};
+use turbopack_ecmascript::{
+    chunk::{EcmascriptChunkPlaceable, EcmascriptExports},
+    references::esm::EsmExport,
+    utils::StringifyJs,
+};
+
+use crate::NodeJsChunkingContext;
+
+#[turbo_tasks::value(transparent)]
+pub(crate) struct OptionExportNames(Option<Vec<RcStr>>);
+
+/// Returns the names of the exports of `module`, if it is an ES module. Other modules are exported
+/// as a whole, like Node.js does for CommonJS modules.
+#[turbo_tasks::function]
+pub(crate) async fn esm_export_names(
+    module: ResolvedVc<Box<dyn EcmascriptChunkPlaceable>>,
+    chunking_context: Vc<NodeJsChunkingContext>,
+) -> Result<Vc<OptionExportNames>> {
+    let EcmascriptExports::EsmExports(esm_exports) = &*module.get_exports().await? else {
+        return Ok(Vc::cell(None));
+    };
+    let export_usage = chunking_context.module_export_usage(*ResolvedVc::upcast(module));
+    let expanded = esm_exports.expand_exports(export_usage).await?;
+    Ok(Vc::cell(Some(expanded.exports.keys().cloned().collect())))
+}
+
+/// The exports of the module that are reassigned after they are declared, e.g. `export let count`
+/// that is incremented by an exported function. ES module exports written by [write_esm_exports]
+/// are bound once after the module has been evaluated, so importers don't see the reassignments.
+/// An [EsmExportsNotLiveIssue] is reported for them.
+#[turbo_tasks::function]
+pub(crate) async fn report_mutable_esm_exports(
+    module: ResolvedVc<Box<dyn EcmascriptChunkPlaceable>>,
+    chunking_context: Vc<NodeJsChunkingContext>,
+) -> Result<Vc<()>> {
+    let EcmascriptExports::EsmExports(esm_exports) = &*module.get_exports().await? else {
+        return Ok(Vc::cell(()));
+    };
+    let export_usage = chunking_context.module_export_usage(*ResolvedVc::upcast(module));
+    let expanded = esm_exports.expand_exports(export_usage).await?;
+    let mutable_exports = expanded
+        .exports
+        .iter()
+        .filter(|(_, export)| {
+            matches!(
+                export,
+                EsmExport::LocalBinding(_, true) | EsmExport::ImportedBinding(_, _, true)
+            )
+        })
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    if !mutable_exports.is_empty() {
+        EsmExportsNotLiveIssue {
+            path: module.ident().path().owned().await?,
+            exports: mutable_exports,
+        }
+        .resolved_cell()
+        .emit();
+    }
+    Ok(Vc::cell(()))
+}
+
+/// Writes the exports of the module with the id `module_id` as ES module exports. Async modules
+/// export a promise, which is awaited at the top level.
+///
+/// ES modules can't export getters, so every export is bound to the value it has after the module
+/// has been evaluated. See [report_mutable_esm_exports] for the exports that this doesn't cover.
+pub(crate) fn write_esm_exports(
+    code: &mut CodeBuilder,
+    module_id: &ModuleId,
+    export_names: Option<&[RcStr]>,
+) -> Result<()> {
+    writedoc!(
+        code,
+        r#"
+            const e=await R.m({}).exports
+        "#,
+        StringifyJs(module_id),
+    )?;
+
+    let Some(export_names) = export_names else {
+        writedoc!(
+            code,
+            r#"
+                export default e
+            "#,
+        )?;
+        return Ok(());
+    };
+
+    let mut exports = Vec::with_capacity(export_names.len());
+    for (index, name) in export_names.iter().enumerate() {
+        writedoc!(
+            code,
+            r#"
+                const e{}=e[{}]
+            "#,
+            index,
+            StringifyJs(name),
+        )?;
+        exports.push(format!("e{index} as {}", StringifyJs(name)));
+    }
+    writedoc!(
+        code,
+        r#"
+            export{{{}}}
+        "#,
+        exports.join(","),
+    )?;
+    Ok(())
+}
+
+/// Writes the exports of the module with the id `module_id` as CommonJS exports. The exports of ES
+/// modules are defined one by one, in a form that Node.js detects as named exports when the file
+/// is imported from an ES module.
+pub(crate) fn write_cjs_exports(
+    code: &mut CodeBuilder,
+    module_id: &ModuleId,
+    export_names: Option<&[RcStr]>,
+) -> Result<()> {
+    writedoc!(
+        code,
+        r#"
+            const e=R.m({}).exports
+        "#,
+        StringifyJs(module_id),
+    )?;
+
+    let Some(export_names) = export_names else {
+        writedoc!(
+            code,
+            r#"
+                module.exports=e
+            "#,
+        )?;
+        return Ok(());
+    };
+
+    writedoc!(
+        code,
+        r#"
+            Object.defineProperty(exports,"__esModule",{{value:true}})
+        "#,
+    )?;
+    for name in export_names {
+        let member = if is_identifier(name) {
+            format!("e.{name}")
+        } else {
+            format!("e[{}]", StringifyJs(name))
+        };
+        writedoc!(
+            code,
+            r#"
+                Object.defineProperty(exports,{},{{enumerable:true,get:function(){{return {}}}}})
+            "#,
+            StringifyJs(name),
+            member,
+        )?;
+    }
+    Ok(())
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[turbo_tasks::value(shared)]
+pub(crate) struct EsmExportsNotLiveIssue {
+    path: FileSystemPath,
+    exports: Vec<RcStr>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for EsmExportsNotLiveIssue {
+    fn severity(&self) -> IssueSeverity {
+        IssueSeverity::Warning
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Text(rcstr!(
+            "Reassigned exports are not live in ES module output"
+        ))
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::CodeGen.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path.clone().cell()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        let mut line = vec![StyledString::Text(rcstr!(
+            "These exports are reassigned, but the ES module output exports the values they have \
+             after the module has been evaluated: "
+        ))];
+        for (i, export) in self.exports.iter().enumerate() {
+            if i > 0 {
+                line.push(StyledString::Text(rcstr!(", ")));
+            }
+            line.push(StyledString::Code(export.clone()));
+        }
+        line.push(StyledString::Text(rcstr!(
+            ". Export a function that returns the current value instead, or use the CommonJS \
+             output, which exports getters."
+        )));
+        Vc::cell(Some(StyledString::Line(line).resolved_cell()))
+    }
+}
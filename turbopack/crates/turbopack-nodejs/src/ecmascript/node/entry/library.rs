@@ -0,0 +1,259 @@
+use std::io::Write;
+
+use anyhow::{Result, bail};
+use indoc::writedoc;
+use turbo_rcstr::{RcStr, rcstr};
+use turbo_tasks::{ResolvedVc, TryFlatJoinIterExt, ValueToString, Vc};
+use turbo_tasks_fs::FileSystemPath;
+use turbopack_core::{
+    asset::{Asset, AssetContent},
+    chunk::{Chunk, ChunkingContext, EvaluatableAssets, MinifyType, ModuleChunkItemIdExt},
+    code_builder::{Code, CodeBuilder, FooterCommentStyle},
+    output::{OutputAsset, OutputAssets},
+    source_map::{GenerateSourceMap, OptionStringifiedSourceMap, SourceMapAsset},
+};
+use turbopack_ecmascript::{
+    chunk::{EcmascriptChunk, EcmascriptChunkPlaceable},
+    minify::minify,
+    utils::StringifyJs,
+};
+
+use super::{
+    exports::{esm_export_names, report_mutable_esm_exports, write_cjs_exports, write_esm_exports},
+    runtime::write_runtime_code,
+};
+use crate::{NodeJsChunkFormat, NodeJsChunkingContext};
+
+/// An Ecmascript chunk for library builds. Unlike [`super::chunk::EcmascriptBuildNodeEntryChunk`],
+/// it contains the runtime and the modules of its chunk group itself, so that every entry of a
+/// library is a single file, and it exports the exports of the entry module.
+#[turbo_tasks::value(shared)]
+pub(crate) struct EcmascriptBuildNodeLibraryChunk {
+    path: FileSystemPath,
+    chunks: Vec<ResolvedVc<EcmascriptChunk>>,
+    other_chunks: ResolvedVc<OutputAssets>,
+    evaluatable_assets: ResolvedVc<EvaluatableAssets>,
+    exported_module: ResolvedVc<Box<dyn EcmascriptChunkPlaceable>>,
+    chunking_context: ResolvedVc<NodeJsChunkingContext>,
+}
+
+#[turbo_tasks::value_impl]
+impl EcmascriptBuildNodeLibraryChunk {
+    /// Creates a new [`Vc<EcmascriptBuildNodeLibraryChunk>`].
+    #[turbo_tasks::function]
+    pub fn new(
+        path: FileSystemPath,
+        chunks: Vec<ResolvedVc<EcmascriptChunk>>,
+        other_chunks: ResolvedVc<OutputAssets>,
+        evaluatable_assets: ResolvedVc<EvaluatableAssets>,
+        exported_module: ResolvedVc<Box<dyn EcmascriptChunkPlaceable>>,
+        chunking_context: ResolvedVc<NodeJsChunkingContext>,
+    ) -> Vc<Self> {
+        EcmascriptBuildNodeLibraryChunk {
+            path,
+            chunks,
+            other_chunks,
+            evaluatable_assets,
+            exported_module,
+            chunking_context,
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn code(self: Vc<Self>) -> Result<Vc<Code>> {
+        let this = self.await?;
+
+        let output_root = this.chunking_context.output_root().owned().await?;
+        let chunk_path = self.path().owned().await?;
+        let Some(chunk_public_path) = output_root.get_path_to(&chunk_path) else {
+            bail!(
+                "chunk path ({}) is not in output root ({})",
+                chunk_path.to_string(),
+                output_root.to_string()
+            );
+        };
+        let chunk_format = *this.chunking_context.chunk_format().await?;
+        let source_maps = *this
+            .chunking_context
+            .reference_chunk_source_maps(Vc::upcast(self))
+            .await?;
+
+        let mut code = CodeBuilder::default();
+
+        if chunk_format == NodeJsChunkFormat::EsModule {
+            // Externals and the runtime rely on the CommonJS module scope.
+            writedoc!(
+                code,
+                r#"
+                    import {{ createRequire }} from "node:module";
+                    import {{ fileURLToPath }} from "node:url";
+                    const require = createRequire(import.meta.url);
+                    const __filename = fileURLToPath(import.meta.url);
+                "#,
+            )?;
+        }
+
+        // The runtime is scoped to a function, as it assigns its API to `module.exports`.
+        writedoc!(
+            code,
+            r#"
+                var R=(function(module){{
+            "#,
+        )?;
+        write_runtime_code(
+            &mut code,
+            *this.chunking_context,
+            chunk_public_path,
+            source_maps,
+        )
+        .await?;
+        writedoc!(
+            code,
+            r#"
+                return module.exports
+                }})({{exports:{{}}}})({})
+            "#,
+            StringifyJs(chunk_public_path),
+        )?;
+
+        write!(code, "R.i({}, [", StringifyJs(chunk_public_path))?;
+        for chunk in &this.chunks {
+            let content = chunk.chunk_content().await?;
+            for item in content.chunk_item_code_and_ids().await? {
+                for (id, item_code) in item {
+                    write!(code, "\n{}, ", StringifyJs(&id))?;
+                    code.push_code(item_code);
+                    write!(code, ",")?;
+                }
+            }
+        }
+        writeln!(code, "\n])")?;
+
+        for other_chunk in &*this.other_chunks.await? {
+            let other_chunk_path = &*other_chunk.path().await?;
+            if let Some(other_chunk_public_path) = output_root.get_path_to(other_chunk_path) {
+                writedoc!(
+                    code,
+                    r#"
+                        R.c({})
+                    "#,
+                    StringifyJs(&other_chunk_public_path)
+                )?;
+            }
+        }
+
+        for evaluatable_asset in &*this.evaluatable_assets.await? {
+            if let Some(placeable) =
+                ResolvedVc::try_sidecast::<Box<dyn EcmascriptChunkPlaceable>>(*evaluatable_asset)
+            {
+                let runtime_module_id = placeable
+                    .chunk_item_id(Vc::upcast(*this.chunking_context))
+                    .await?;
+
+                writedoc!(
+                    code,
+                    r#"
+                        R.m({})
+                    "#,
+                    StringifyJs(&*runtime_module_id),
+                )?;
+            }
+        }
+
+        let runtime_module_id = this
+            .exported_module
+            .chunk_item_id(Vc::upcast(*this.chunking_context))
+            .await?;
+        let export_names = esm_export_names(*this.exported_module, *this.chunking_context).await?;
+        match chunk_format {
+            NodeJsChunkFormat::CommonJs => {
+                write_cjs_exports(&mut code, &runtime_module_id, export_names.as_deref())?
+            }
+            NodeJsChunkFormat::EsModule => {
+                report_mutable_esm_exports(*this.exported_module, *this.chunking_context).await?;
+                write_esm_exports(&mut code, &runtime_module_id, export_names.as_deref())?
+            }
+        }
+
+        let mut code = code.build();
+
+        if let MinifyType::Minify(options) = &*this.chunking_context.minify_type().await? {
+            code = minify(code, source_maps, options)?;
+        }
+
+        Ok(code.cell())
+    }
+
+    #[turbo_tasks::function]
+    async fn source_map(self: Vc<Self>) -> Result<Vc<SourceMapAsset>> {
+        let this = self.await?;
+        Ok(SourceMapAsset::new_fixed(
+            this.path.clone(),
+            Vc::upcast(self),
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for EcmascriptBuildNodeLibraryChunk {
+    #[turbo_tasks::function]
+    fn to_string(&self) -> Vc<RcStr> {
+        Vc::cell(rcstr!("Ecmascript Build Node Library Chunk"))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl OutputAsset for EcmascriptBuildNodeLibraryChunk {
+    #[turbo_tasks::function]
+    fn path(&self) -> Vc<FileSystemPath> {
+        self.path.clone().cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn references(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
+        let this = self.await?;
+
+        // The chunks are part of this file, but the assets they reference are not, e.g. async
+        // chunks and static assets.
+        let mut references: Vec<ResolvedVc<Box<dyn OutputAsset>>> = this
+            .chunks
+            .iter()
+            .map(async |chunk| Ok(chunk.references().await?.into_iter().copied()))
+            .try_flat_join()
+            .await?;
+
+        if *this
+            .chunking_context
+            .reference_chunk_source_maps(Vc::upcast(self))
+            .await?
+        {
+            references.push(ResolvedVc::upcast(self.source_map().to_resolved().await?))
+        }
+
+        references.extend(this.other_chunks.await?.iter().copied());
+
+        Ok(Vc::cell(references))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for EcmascriptBuildNodeLibraryChunk {
+    #[turbo_tasks::function]
+    async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
+        let this = self.await?;
+        Ok(self.code().chunk_file_content(
+            Vc::upcast(*this.chunking_context),
+            Vc::upcast(self.source_map()),
+            FooterCommentStyle::JavaScript,
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GenerateSourceMap for EcmascriptBuildNodeLibraryChunk {
+    #[turbo_tasks::function]
+    fn generate_source_map(self: Vc<Self>) -> Vc<OptionStringifiedSourceMap> {
+        self.code().generate_source_map()
+    }
+}
@@ -1,2 +1,4 @@
 pub(crate) mod chunk;
+pub(crate) mod exports;
+pub(crate) mod library;
 pub(crate) mod runtime;
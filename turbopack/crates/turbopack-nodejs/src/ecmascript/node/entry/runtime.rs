@@ -4,11 +4,11 @@ use anyhow::{Result, bail};
 use indoc::writedoc;
 use turbo_rcstr::{RcStr, rcstr};
 use turbo_tasks::{ResolvedVc, ValueToString, Vc};
-use turbo_tasks_fs::{File, FileSystem, FileSystemPath, rope::RopeBuilder};
+use turbo_tasks_fs::{FileSystem, FileSystemPath};
 use turbopack_core::{
     asset::{Asset, AssetContent},
     chunk::ChunkingContext,
-    code_builder::{Code, CodeBuilder},
+    code_builder::{Code, CodeBuilder, FooterCommentStyle},
     ident::AssetIdent,
     output::{OutputAsset, OutputAssets},
     source_map::{GenerateSourceMap, OptionStringifiedSourceMap, SourceMapAsset},
@@ -16,7 +16,7 @@ use turbopack_core::{
 use turbopack_ecmascript::utils::StringifyJs;
 use turbopack_ecmascript_runtime::RuntimeType;
 
-use crate::NodeJsChunkingContext;
+use crate::{NodeJsChunkFormat, NodeJsChunkingContext};
 
 /// An Ecmascript chunk that contains the Node.js runtime code.
 #[turbo_tasks::value(shared)]
@@ -36,7 +36,6 @@ impl EcmascriptBuildNodeRuntimeChunk {
     async fn code(self: Vc<Self>) -> Result<Vc<Code>> {
         let this = self.await?;
 
-        let output_root_to_root_path = this.chunking_context.output_root_to_root_path().await?;
         let output_root = this.chunking_context.output_root().await?;
         let generate_source_map = *this
             .chunking_context
@@ -54,41 +53,38 @@ impl EcmascriptBuildNodeRuntimeChunk {
         };
 
         let mut code = CodeBuilder::default();
-        let asset_prefix = this.chunking_context.asset_prefix().await?;
-        let asset_prefix = asset_prefix.as_deref().unwrap_or("/");
-
-        writedoc!(
-            code,
-            r#"
-                const RUNTIME_PUBLIC_PATH = {};
-                const RELATIVE_ROOT_PATH = {};
-                const ASSET_PREFIX = {};
-            "#,
-            StringifyJs(runtime_public_path),
-            StringifyJs(output_root_to_root_path.as_str()),
-            StringifyJs(asset_prefix),
-        )?;
-
-        match *this.chunking_context.runtime_type().await? {
-            RuntimeType::Development => {
-                let runtime_code = turbopack_ecmascript_runtime::get_nodejs_runtime_code(
-                    this.chunking_context.environment(),
-                    generate_source_map,
-                );
-                code.push_code(&*runtime_code.await?);
-            }
-            RuntimeType::Production => {
-                let runtime_code = turbopack_ecmascript_runtime::get_nodejs_runtime_code(
-                    this.chunking_context.environment(),
-                    generate_source_map,
-                );
-                code.push_code(&*runtime_code.await?);
-            }
-            #[cfg(feature = "test")]
-            RuntimeType::Dummy => {
-                let runtime_code = turbopack_ecmascript_runtime::get_dummy_runtime_code();
-                code.push_code(&runtime_code);
-            }
+        let chunk_format = *this.chunking_context.chunk_format().await?;
+
+        if chunk_format == NodeJsChunkFormat::EsModule {
+            // The runtime is shared with the CommonJS format, so the CommonJS module scope it
+            // relies on is recreated here.
+            writedoc!(
+                code,
+                r#"
+                    import {{ createRequire }} from "node:module";
+                    import {{ fileURLToPath }} from "node:url";
+                    const require = createRequire(import.meta.url);
+                    const __filename = fileURLToPath(import.meta.url);
+                    const module = {{ exports: {{}} }};
+                "#,
+            )?;
+        }
+
+        write_runtime_code(
+            &mut code,
+            *this.chunking_context,
+            runtime_public_path,
+            generate_source_map,
+        )
+        .await?;
+
+        if chunk_format == NodeJsChunkFormat::EsModule {
+            writedoc!(
+                code,
+                r#"
+                    export default module.exports;
+                "#,
+            )?;
         }
 
         Ok(Code::cell(code.build()))
@@ -115,6 +111,55 @@ impl EcmascriptBuildNodeRuntimeChunk {
     }
 }
 
+/// Writes the constants the runtime depends on and the runtime code for a runtime located at
+/// `runtime_public_path`. The runtime exports its API through `module.exports`.
+pub(super) async fn write_runtime_code(
+    code: &mut CodeBuilder,
+    chunking_context: Vc<NodeJsChunkingContext>,
+    runtime_public_path: &str,
+    generate_source_map: bool,
+) -> Result<()> {
+    let output_root_to_root_path = chunking_context.output_root_to_root_path().await?;
+    let asset_prefix = chunking_context.asset_prefix().await?;
+    let asset_prefix = asset_prefix.as_deref().unwrap_or("/");
+
+    writedoc!(
+        code,
+        r#"
+            const RUNTIME_PUBLIC_PATH = {};
+            const RELATIVE_ROOT_PATH = {};
+            const ASSET_PREFIX = {};
+        "#,
+        StringifyJs(runtime_public_path),
+        StringifyJs(output_root_to_root_path.as_str()),
+        StringifyJs(asset_prefix),
+    )?;
+
+    match *chunking_context.runtime_type().await? {
+        RuntimeType::Development => {
+            let runtime_code = turbopack_ecmascript_runtime::get_nodejs_runtime_code(
+                chunking_context.environment(),
+                generate_source_map,
+            );
+            code.push_code(&*runtime_code.await?);
+        }
+        RuntimeType::Production => {
+            let runtime_code = turbopack_ecmascript_runtime::get_nodejs_runtime_code(
+                chunking_context.environment(),
+                generate_source_map,
+            );
+            code.push_code(&*runtime_code.await?);
+        }
+        #[cfg(feature = "test")]
+        RuntimeType::Dummy => {
+            let runtime_code = turbopack_ecmascript_runtime::get_dummy_runtime_code();
+            code.push_code(&runtime_code);
+        }
+    }
+
+    Ok(())
+}
+
 #[turbo_tasks::value_impl]
 impl ValueToString for EcmascriptBuildNodeRuntimeChunk {
     #[turbo_tasks::function]
@@ -129,10 +174,11 @@ impl OutputAsset for EcmascriptBuildNodeRuntimeChunk {
     async fn path(self: Vc<Self>) -> Result<Vc<FileSystemPath>> {
         let this = self.await?;
         let ident = self.ident_for_path();
+        let extension = this.chunking_context.chunk_format().await?.extension();
 
         Ok(this
             .chunking_context
-            .chunk_path(Some(Vc::upcast(self)), ident, None, rcstr!(".js")))
+            .chunk_path(Some(Vc::upcast(self)), ident, None, extension))
     }
 
     #[turbo_tasks::function]
@@ -156,30 +202,22 @@ impl OutputAsset for EcmascriptBuildNodeRuntimeChunk {
 impl Asset for EcmascriptBuildNodeRuntimeChunk {
     #[turbo_tasks::function]
     async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
-        let code = self.code().await?;
-
-        let rope = if code.has_source_map() {
-            let mut rope_builder = RopeBuilder::default();
-            rope_builder.concat(code.source_code());
-            let source_map_path = self.source_map().path().await?;
-            write!(
-                rope_builder,
-                "\n\n//# sourceMappingURL={}",
-                urlencoding::encode(source_map_path.file_name())
-            )?;
-            rope_builder.build()
-        } else {
-            code.source_code().clone()
-        };
-
-        Ok(AssetContent::file(File::from(rope).into()))
+        let this = self.await?;
+        Ok(self.code().chunk_file_content(
+            Vc::upcast(*this.chunking_context),
+            Vc::upcast(self.source_map()),
+            FooterCommentStyle::JavaScript,
+        ))
     }
 }
 
 #[turbo_tasks::value_impl]
 impl GenerateSourceMap for EcmascriptBuildNodeRuntimeChunk {
     #[turbo_tasks::function]
-    fn generate_source_map(self: Vc<Self>) -> Vc<OptionStringifiedSourceMap> {
-        self.code().generate_source_map()
+    async fn generate_source_map(self: Vc<Self>) -> Result<Vc<OptionStringifiedSourceMap>> {
+        let this = self.await?;
+        Ok(self
+            .code()
+            .generate_chunk_source_map(Vc::upcast(*this.chunking_context)))
     }
 }
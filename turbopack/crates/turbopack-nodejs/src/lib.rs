@@ -5,7 +5,9 @@
 pub(crate) mod chunking_context;
 pub(crate) mod ecmascript;
 
-pub use chunking_context::{NodeJsChunkingContext, NodeJsChunkingContextBuilder};
+pub use chunking_context::{
+    NodeJsChunkFormat, NodeJsChunkingContext, NodeJsChunkingContextBuilder,
+};
 
 pub fn register() {
     turbo_tasks::register();
@@ -8,6 +8,7 @@ use turbopack_core::resolve::{
         ConditionValue, ImportMap, ImportMapping, ResolutionConditions, ResolveInPackage,
         ResolveIntoPackage, ResolveModules, ResolveOptions,
     },
+    web_import_map::web_import_map,
 };
 
 use crate::{
@@ -146,6 +147,9 @@ async fn base_resolve_options(
     }
 
     let mut import_map = ImportMap::new(direct_mappings);
+    if let Some(web_import_map_path) = &opt.web_import_map {
+        import_map.extend_ref(&*web_import_map(web_import_map_path.clone()).await?);
+    }
     if let Some(additional_import_map) = opt.import_map {
         let additional_import_map = additional_import_map.await?;
         import_map.extend_ref(&additional_import_map);
@@ -227,16 +231,26 @@ async fn base_resolve_options(
     Ok(ResolveOptions {
         extensions,
         modules: if let Some(environment) = emulating {
+            let mut mods = Vec::new();
+            if let Some(manifest) = &opt.enable_pnp {
+                mods.push(ResolveModules::PnP {
+                    manifest: manifest.clone(),
+                });
+            }
             if *environment.resolve_node_modules().await? {
-                vec![ResolveModules::Nested(
+                mods.push(ResolveModules::Nested(
                     root.clone(),
                     vec![rcstr!("node_modules")],
-                )]
-            } else {
-                Vec::new()
+                ));
             }
+            mods
         } else {
             let mut mods = Vec::new();
+            if let Some(manifest) = &opt.enable_pnp {
+                mods.push(ResolveModules::PnP {
+                    manifest: manifest.clone(),
+                });
+            }
             if let Some(dir) = &opt.enable_node_modules {
                 mods.push(ResolveModules::Nested(
                     dir.clone(),
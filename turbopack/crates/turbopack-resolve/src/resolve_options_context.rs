@@ -32,6 +32,10 @@ pub struct ResolveOptionsContext {
     /// directory
     pub enable_node_modules: Option<FileSystemPath>,
     #[serde(default)]
+    /// Enable resolving of packages through the Yarn Plug'n'Play manifest at the provided path.
+    /// Packages are looked up in the manifest before node_modules folders.
+    pub enable_pnp: Option<FileSystemPath>,
+    #[serde(default)]
     /// A specific path to a tsconfig.json file to use for resolving modules. If `None`, one will
     /// be looked up through the filesystem
     pub tsconfig_path: Option<FileSystemPath>,
@@ -67,6 +71,12 @@ pub struct ResolveOptionsContext {
     /// any mapping defined within will take precedence over any other.
     pub fallback_import_map: Option<ResolvedVc<ImportMap>>,
     #[serde(default)]
+    /// A WHATWG import map JSON file (`{"imports": {...}, "scopes": {...}}`) whose mappings are
+    /// applied before resolving a request, so the same file can be shared with the browser.
+    ///
+    /// Mappings of `import_map` take precedence over the mappings of this file.
+    pub web_import_map: Option<FileSystemPath>,
+    #[serde(default)]
     /// An additional resolved map to use after modules have been resolved.
     pub resolved_map: Option<ResolvedVc<ResolvedMap>>,
     #[serde(default)]
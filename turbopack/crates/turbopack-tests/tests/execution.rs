@@ -31,7 +31,7 @@ use turbopack::{
     module_options::{EcmascriptOptionsContext, ModuleOptionsContext, TypescriptTransformOptions},
 };
 use turbopack_core::{
-    chunk::{ChunkingConfig, MangleType, MinifyType},
+    chunk::{ChunkingConfig, MinifyOptions, MinifyType},
     compile_time_defines,
     compile_time_info::CompileTimeInfo,
     condition::ContextCondition,
@@ -51,7 +51,7 @@ use turbopack_core::{
 };
 use turbopack_ecmascript_runtime::RuntimeType;
 use turbopack_node::{debug::should_debug, evaluate::evaluate};
-use turbopack_nodejs::NodeJsChunkingContext;
+use turbopack_nodejs::{NodeJsChunkFormat, NodeJsChunkingContext};
 use turbopack_resolve::resolve_options_context::ResolveOptionsContext;
 use turbopack_test_utils::{jest::JestRunResult, snapshot::UPDATE};
 use turbopack_trace_utils::{
@@ -254,6 +254,9 @@ struct TestOptions {
     scope_hoisting: Option<bool>,
     #[serde(default)]
     minify: bool,
+    /// Emit the chunks as ES modules instead of CommonJS modules.
+    #[serde(default)]
+    esm_chunks: bool,
 }
 
 fn default_tree_shaking_mode() -> Option<TreeShakingMode> {
@@ -267,6 +270,7 @@ impl Default for TestOptions {
             remove_unused_exports: None,
             scope_hoisting: None,
             minify: false,
+            esm_chunks: false,
         }
     }
 }
@@ -507,10 +511,13 @@ async fn run_test_operation(prepared_test: ResolvedVc<PreparedTest>) -> Result<V
         },
     )
     .module_merging(options.scope_hoisting.unwrap_or(true))
+    .chunk_format(if options.esm_chunks {
+        NodeJsChunkFormat::EsModule
+    } else {
+        NodeJsChunkFormat::CommonJs
+    })
     .minify_type(if options.minify {
-        MinifyType::Minify {
-            mangle: Some(MangleType::OptimalSize),
-        }
+        MinifyType::Minify(MinifyOptions::default())
     } else {
         MinifyType::NoMinify
     })
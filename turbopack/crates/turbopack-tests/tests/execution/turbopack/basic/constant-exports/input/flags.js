@@ -0,0 +1,3 @@
+export const FLAG = false
+export const NAME = 'b'
+export const base = './dir'
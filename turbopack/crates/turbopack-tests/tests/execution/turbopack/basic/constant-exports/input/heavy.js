@@ -0,0 +1 @@
+throw new Error('heavy.js should not be included')
@@ -0,0 +1,17 @@
+import { join } from 'path'
+import { FLAG, NAME, base } from './flags'
+
+it('should not follow dynamic imports behind imported constants', async () => {
+  if (FLAG) {
+    await import('./heavy')
+  }
+  const modules = Array.from(__turbopack_modules__.keys())
+  expect(modules).not.toContainEqual(expect.stringMatching(/input\/heavy/))
+})
+
+it('should resolve requests built from imported constants', () => {
+  expect(require(join(base, NAME))).toBe('b')
+  const modules = Array.from(__turbopack_modules__.keys())
+  expect(modules).toContainEqual(expect.stringMatching(/input\/dir\/b/))
+  expect(modules).not.toContainEqual(expect.stringMatching(/input\/dir\/a/))
+})
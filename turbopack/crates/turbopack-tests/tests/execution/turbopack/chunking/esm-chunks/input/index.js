@@ -0,0 +1,22 @@
+import { join } from 'path'
+import def, { a } from 'esm-external/package'
+import { shared } from './shared.js'
+
+it('should run the entry chunk as an ES module', () => {
+  expect(process.argv[1]).toMatch(/\.mjs$/)
+  expect(shared).toBe('shared')
+})
+
+it('should load async chunks', async () => {
+  const { lazy } = await import('./lazy.js')
+  expect(lazy).toBe('lazy shared')
+})
+
+it('should import ES module externals', () => {
+  expect(def).toBe('default')
+  expect(a).toBe('a')
+})
+
+it('should require CommonJS externals', () => {
+  expect(join('a', 'b')).toBe('a/b')
+})
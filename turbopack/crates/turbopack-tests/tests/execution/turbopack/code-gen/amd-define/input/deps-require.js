@@ -0,0 +1,3 @@
+define(['require', 'exports', './dep'], function (require, exports, dep) {
+  exports.value = `${dep.value} ${require('./other').value}`
+})
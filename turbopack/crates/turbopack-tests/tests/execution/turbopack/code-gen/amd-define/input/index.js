@@ -0,0 +1,15 @@
+it('should support require and exports as AMD dependencies', () => {
+  expect(require('./deps-require').value).toBe('dep other')
+})
+
+it('should support the simplified CommonJS wrapper', () => {
+  expect(require('./sugar').value).toBe('dep')
+})
+
+it('should support a renamed require dependency with an arrow factory', () => {
+  expect(require('./renamed').value).toBe('other')
+})
+
+it('should use an object factory as the module value', () => {
+  expect(require('./object').value).toBe('object')
+})
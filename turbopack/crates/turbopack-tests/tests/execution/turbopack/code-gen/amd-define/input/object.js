@@ -0,0 +1 @@
+define(['./dep'], { value: 'object' })
@@ -0,0 +1 @@
+define(['require'], (req) => ({ value: req('./other').value }))
@@ -0,0 +1,17 @@
+import './setup.js'
+import sheet from './style.css' with { type: 'css' }
+import sameSheet from './style.css' with { type: 'css' }
+import backgroundSheet from './background.css' with { type: 'css' }
+
+it('should import CSS as a constructable style sheet', () => {
+  expect(sheet).toBeInstanceOf(CSSStyleSheet)
+  expect(sheet.text).toBe('.button {\n  color: red;\n}\n')
+})
+
+it('should share the style sheet between imports', () => {
+  expect(sameSheet).toBe(sheet)
+})
+
+it('should pass CSS with urls and imports through unprocessed', () => {
+  expect(backgroundSheet.text).toContain("url('./hero.png')")
+})
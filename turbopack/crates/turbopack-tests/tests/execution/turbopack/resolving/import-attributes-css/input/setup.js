@@ -0,0 +1,6 @@
+// Node.js has no constructable style sheets, so provide a minimal stand-in.
+globalThis.CSSStyleSheet = class CSSStyleSheet {
+  replaceSync(text) {
+    this.text = text
+  }
+}
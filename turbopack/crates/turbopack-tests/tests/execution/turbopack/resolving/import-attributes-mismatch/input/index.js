@@ -0,0 +1,9 @@
+import a from './a.js'
+
+it('should not import a JavaScript module with a json type attribute', async () => {
+  await expect(import('./mismatch.js')).rejects.toThrow()
+})
+
+it('should still import the module without a type attribute', () => {
+  expect(a).toBe('a')
+})
@@ -0,0 +1,3 @@
+import x from './a.js' with { type: 'json' }
+
+export default x
@@ -0,0 +1,18 @@
+import data from './data.json' with { type: 'json' }
+import message from './message.txt' with { type: 'text' }
+import bytes from './bytes.bin' with { type: 'bytes' }
+import dataText from './data.json' with { type: 'text' }
+
+it('should import JSON with a type attribute', () => {
+  expect(data).toEqual({ name: 'data', values: [1, 2, 3] })
+})
+
+it('should import text with a type attribute', () => {
+  expect(message).toBe('Hello World\n')
+  expect(dataText).toBe('{ "name": "data", "values": [1, 2, 3] }\n')
+})
+
+it('should import bytes with a type attribute', () => {
+  expect(bytes).toBeInstanceOf(Uint8Array)
+  expect(Array.from(bytes)).toEqual([97, 98, 99, 10])
+})
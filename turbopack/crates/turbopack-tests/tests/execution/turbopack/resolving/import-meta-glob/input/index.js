@@ -0,0 +1,76 @@
+it('should import matching modules lazily', async () => {
+  const pages = import.meta.glob('./pages/*.js')
+  expect(Object.keys(pages)).toEqual([
+    './pages/a.js',
+    './pages/b.js',
+    './pages/b.test.js',
+  ])
+  const a = await pages['./pages/a.js']()
+  expect(a.default).toBe('a')
+  expect(a.name).toBe('page a')
+})
+
+it('should import matching modules eagerly', () => {
+  const pages = import.meta.glob('./pages/**/*.js', { eager: true })
+  expect(Object.keys(pages)).toEqual([
+    './pages/a.js',
+    './pages/b.js',
+    './pages/b.test.js',
+    './pages/nested/c.js',
+  ])
+  expect(pages['./pages/nested/c.js'].name).toBe('page c')
+})
+
+it('should select an export', async () => {
+  const eager = import.meta.glob('./pages/*.js', {
+    eager: true,
+    import: 'name',
+  })
+  expect(eager['./pages/b.js']).toBe('page b')
+
+  const lazy = import.meta.glob('./pages/*.js', { import: 'default' })
+  expect(await lazy['./pages/a.js']()).toBe('a')
+})
+
+it('should exclude negated patterns', () => {
+  const pages = import.meta.glob(['./pages/**/*.js', '!**/*.test.js'], {
+    eager: true,
+  })
+  expect(Object.keys(pages)).toEqual([
+    './pages/a.js',
+    './pages/b.js',
+    './pages/nested/c.js',
+  ])
+})
+
+it('should resolve absolute patterns from the project root', () => {
+  // The project root is the directory of the closest package.json
+  const pages = import.meta.glob('/pages/*.js', { eager: true })
+  expect(Object.keys(pages)).toEqual([
+    '/pages/a.js',
+    '/pages/b.js',
+    '/pages/b.test.js',
+  ])
+  expect(pages['/pages/b.js'].name).toBe('page b')
+})
+
+it('should import modules with the query', () => {
+  const plain = import.meta.glob('./query/*.js', { eager: true })
+  const withQuery = import.meta.glob('./query/*.js', {
+    eager: true,
+    query: { instance: 'second' },
+  })
+  // The query is given as a string this time
+  const withSameQuery = import.meta.glob('./query/counter.js', {
+    eager: true,
+    query: '?instance=second',
+  })
+  expect(Object.keys(withQuery)).toEqual(['./query/counter.js'])
+  // A request with a query is a separate module instance
+  expect(withQuery['./query/counter.js'].evaluation).not.toBe(
+    plain['./query/counter.js'].evaluation
+  )
+  expect(withSameQuery['./query/counter.js']).toBe(
+    withQuery['./query/counter.js']
+  )
+})
@@ -0,0 +1,2 @@
+export default 'a'
+export const name = 'page a'
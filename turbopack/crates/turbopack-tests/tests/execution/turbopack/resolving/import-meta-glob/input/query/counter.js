@@ -0,0 +1,3 @@
+globalThis.counterEvaluations = (globalThis.counterEvaluations ?? 0) + 1
+
+export const evaluation = globalThis.counterEvaluations
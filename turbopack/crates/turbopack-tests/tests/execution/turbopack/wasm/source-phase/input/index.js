@@ -0,0 +1,10 @@
+const sourceAsyncModule = require('./source')
+
+it('should import the compiled wasm module with a source phase import', async () => {
+  // source.js is an async module, so we require it and await inside this function to make sure the entrypoint isn't async.
+  const { default: addModule } = await sourceAsyncModule
+
+  expect(addModule).toBeInstanceOf(WebAssembly.Module)
+  const instance = await WebAssembly.instantiate(addModule)
+  expect(instance.exports.add(22, 2200)).toEqual(22 + 2200)
+})
@@ -0,0 +1,3 @@
+import source addModule from './add.wasm'
+
+export default addModule
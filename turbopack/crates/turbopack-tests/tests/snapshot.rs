@@ -31,7 +31,9 @@ use turbopack_core::{
     asset::Asset,
     chunk::{
         ChunkingConfig, ChunkingContext, ChunkingContextExt, EvaluatableAsset, EvaluatableAssetExt,
-        EvaluatableAssets, MinifyType, availability_info::AvailabilityInfo,
+        EvaluatableAssets, MinifyType,
+        availability_info::AvailabilityInfo,
+        budget::{ChunkBudgets, check_chunk_group_budgets},
     },
     compile_time_defines,
     compile_time_info::{CompileTimeDefineValue, CompileTimeInfo, DefinableNameSegment},
@@ -41,7 +43,7 @@ use turbopack_core::{
     file_source::FileSource,
     free_var_references,
     ident::Layer,
-    issue::IssueDescriptionExt,
+    issue::{IssueDescriptionExt, IssueSeverity},
     module::Module,
     module_graph::{
         ModuleGraph,
@@ -101,6 +103,9 @@ struct SnapshotOptions {
     scope_hoisting: bool,
     #[serde(default)]
     production_chunking: bool,
+    /// Emits an issue for chunks that are larger than this many bytes.
+    #[serde(default)]
+    max_chunk_size: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -130,6 +135,7 @@ impl Default for SnapshotOptions {
             remove_unused_exports: false,
             scope_hoisting: false,
             production_chunking: false,
+            max_chunk_size: None,
         }
     }
 }
@@ -480,6 +486,15 @@ async fn run_test_operation(resource: RcStr) -> Result<Vc<FileSystemPath>> {
                     },
                 )
             }
+            if let Some(max_chunk_size) = options.max_chunk_size {
+                builder = builder.chunk_budgets(ChunkBudgets {
+                    max_chunk_size: Some(max_chunk_size),
+                    max_chunk_gzip_size: None,
+                    max_entry_size: None,
+                    max_entry_gzip_size: None,
+                    severity: IssueSeverity::Warning,
+                });
+            }
             Vc::upcast(builder.build())
         }
         Runtime::NodeJs => {
@@ -541,6 +556,8 @@ async fn run_test_operation(resource: RcStr) -> Result<Vc<FileSystemPath>> {
         }
     };
 
+    check_chunk_group_budgets(chunking_context, entry_module.ident(), chunks).await?;
+
     let mut seen = FxHashSet::default();
     let mut queue: VecDeque<_> = chunks.await?.iter().copied().collect();
 
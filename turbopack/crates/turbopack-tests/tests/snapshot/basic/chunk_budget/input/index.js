@@ -0,0 +1,4 @@
+import { words } from './words.js'
+import './style.css'
+
+console.log(words.join(' '))
@@ -0,0 +1,62 @@
+export const words = [
+  'lorem-0',
+  'ipsum-1',
+  'dolor-2',
+  'sit-3',
+  'amet-4',
+  'consectetur-5',
+  'adipiscing-6',
+  'elit-7',
+  'sed-8',
+  'do-9',
+  'eiusmod-10',
+  'tempor-11',
+  'incididunt-12',
+  'ut-13',
+  'labore-14',
+  'et-15',
+  'dolore-16',
+  'magna-17',
+  'aliqua-18',
+  'lorem-19',
+  'ipsum-20',
+  'dolor-21',
+  'sit-22',
+  'amet-23',
+  'consectetur-24',
+  'adipiscing-25',
+  'elit-26',
+  'sed-27',
+  'do-28',
+  'eiusmod-29',
+  'tempor-30',
+  'incididunt-31',
+  'ut-32',
+  'labore-33',
+  'et-34',
+  'dolore-35',
+  'magna-36',
+  'aliqua-37',
+  'lorem-38',
+  'ipsum-39',
+  'dolor-40',
+  'sit-41',
+  'amet-42',
+  'consectetur-43',
+  'adipiscing-44',
+  'elit-45',
+  'sed-46',
+  'do-47',
+  'eiusmod-48',
+  'tempor-49',
+  'incididunt-50',
+  'ut-51',
+  'labore-52',
+  'et-53',
+  'dolore-54',
+  'magna-55',
+  'aliqua-56',
+  'lorem-57',
+  'ipsum-58',
+  'dolor-59',
+]
@@ -0,0 +1,5 @@
+(globalThis.TURBOPACK || (globalThis.TURBOPACK = [])).push([
+    "output/ba425_crates_turbopack-tests_tests_snapshot_basic_chunk_budget_input_index_513dcd19.js",
+    {"otherChunks":["output/aaf3a_crates_turbopack-tests_tests_snapshot_basic_chunk_budget_input_f69e47ec._.js","output/aaf3a_crates_turbopack-tests_tests_snapshot_basic_chunk_budget_input_style_1bbc2365.css"],"runtimeModuleIds":["[project]/turbopack/crates/turbopack-tests/tests/snapshot/basic/chunk_budget/input/index.js [test] (ecmascript)"]}
+]);
+// Dummy runtime
\ No newline at end of file
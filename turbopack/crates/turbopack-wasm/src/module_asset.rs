@@ -40,6 +40,9 @@ use crate::{
 pub struct WebAssemblyModuleAsset {
     source: ResolvedVc<WebAssemblySource>,
     asset_context: ResolvedVc<Box<dyn AssetContext>>,
+    /// Whether the compiled `WebAssembly.Module` is exported instead of the exports of the
+    /// instance, for source phase imports (`import source mod from "./mod.wasm"`).
+    source_phase: bool,
 }
 
 #[turbo_tasks::value_impl]
@@ -52,6 +55,20 @@ impl WebAssemblyModuleAsset {
         Self::cell(WebAssemblyModuleAsset {
             source,
             asset_context,
+            source_phase: false,
+        })
+    }
+
+    /// Creates a module which exports the compiled `WebAssembly.Module` without instantiating it.
+    #[turbo_tasks::function]
+    pub fn new_source_phase(
+        source: ResolvedVc<WebAssemblySource>,
+        asset_context: ResolvedVc<Box<dyn AssetContext>>,
+    ) -> Vc<Self> {
+        Self::cell(WebAssemblyModuleAsset {
+            source,
+            asset_context,
+            source_phase: true,
         })
     }
 
@@ -65,7 +82,7 @@ impl WebAssemblyModuleAsset {
         let this = self.await?;
         let query = &this.source.ident().await?.query;
 
-        let loader_source = if query == "?module" {
+        let loader_source = if this.source_phase || query == "?module" {
             compiling_loader_source(*this.source)
         } else {
             instantiating_loader_source(*this.source)
@@ -126,7 +143,11 @@ impl Module for WebAssemblyModuleAsset {
         Ok(self
             .source
             .ident()
-            .with_modifier(rcstr!("wasm module"))
+            .with_modifier(if self.source_phase {
+                rcstr!("wasm module source")
+            } else {
+                rcstr!("wasm module")
+            })
             .with_layer(self.asset_context.into_trait_ref().await?.layer()))
     }
 
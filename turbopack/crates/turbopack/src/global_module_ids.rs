@@ -1,22 +1,80 @@
-use anyhow::{Context, Result};
-use rustc_hash::FxHashMap;
+use std::{collections::BTreeMap, hash::Hash};
+
+use anyhow::{Context, Result, bail};
+use rustc_hash::{FxHashMap, FxHashSet};
 use smallvec::SmallVec;
 use tracing::Instrument;
 use turbo_rcstr::RcStr;
-use turbo_tasks::{ReadRef, ResolvedVc, TryJoinIterExt, ValueToString, Vc};
+use turbo_tasks::{ResolvedVc, TryJoinIterExt, ValueToString, Vc};
+use turbo_tasks_fs::{File, FileContent, FileJsonContent, FileSystemPath};
 use turbo_tasks_hash::hash_xxh3_hash64;
 use turbopack_core::{
-    chunk::{ChunkableModule, ChunkingType, module_id_strategies::GlobalModuleIdStrategy},
+    chunk::{
+        ChunkableModule, ChunkingType,
+        module_id_strategies::{GlobalModuleIdStrategy, ModuleIdRecords},
+    },
     ident::AssetIdent,
     module::Module,
     module_graph::{ModuleGraph, RefData},
 };
 use turbopack_ecmascript::async_chunk::module::AsyncLoaderModule;
 
+/// The numeric ids of all modules of a graph, together with the records that persist them.
+#[turbo_tasks::value]
+struct GlobalModuleIds {
+    module_id_map: FxHashMap<ResolvedVc<AssetIdent>, u64>,
+    /// The updated content of the records file, or `None` if it doesn't need to be written, i.e.
+    /// when there is no records file or no module got a new id.
+    updated_records: Option<BTreeMap<RcStr, u64>>,
+}
+
+/// Assigns a numeric id to every module of the graph.
+///
+/// When `records_path` is set, the ids assigned by previous builds are read from that file and
+/// reused, and only modules that aren't recorded yet get a new id. The updated records are written
+/// back by [write_module_id_records].
 #[turbo_tasks::function]
 pub async fn get_global_module_id_strategy(
     module_graph: ResolvedVc<ModuleGraph>,
+    records_path: Option<FileSystemPath>,
 ) -> Result<Vc<GlobalModuleIdStrategy>> {
+    Ok(GlobalModuleIdStrategy {
+        module_id_map: global_module_ids(*module_graph, records_path)
+            .await?
+            .module_id_map
+            .clone(),
+    }
+    .cell())
+}
+
+/// Writes the module ids assigned by [get_global_module_id_strategy] to the records file at
+/// `records_path`, so that the next build reuses them. This is a separate step of emitting the
+/// output, so that computing the ids doesn't write the file it reads.
+#[turbo_tasks::function]
+pub async fn write_module_id_records(
+    module_graph: ResolvedVc<ModuleGraph>,
+    records_path: FileSystemPath,
+) -> Result<()> {
+    let module_ids = global_module_ids(*module_graph, Some(records_path.clone())).await?;
+    let Some(module_ids) = &module_ids.updated_records else {
+        return Ok(());
+    };
+    let records = ModuleIdRecords {
+        module_ids: module_ids.clone(),
+    };
+    let content = serde_json::to_string_pretty(&records)?;
+    records_path
+        .write(FileContent::Content(File::from(content)).cell())
+        .as_side_effect()
+        .await?;
+    Ok(())
+}
+
+#[turbo_tasks::function]
+async fn global_module_ids(
+    module_graph: ResolvedVc<ModuleGraph>,
+    records_path: Option<FileSystemPath>,
+) -> Result<Vc<GlobalModuleIds>> {
     let span = tracing::info_span!("compute module id map");
     async move {
         let module_graph = module_graph.await?;
@@ -49,7 +107,12 @@ pub async fn get_global_module_id_strategy(
             })
             .await?;
 
-        let mut module_id_map = module_idents
+        let mut records = match &records_path {
+            Some(records_path) => read_module_id_records(records_path).await?,
+            None => ModuleIdRecords::default(),
+        };
+
+        let all_module_ids = module_idents
             .chain(async_idents.into_iter())
             .map(|ident| async move {
                 let ident = ident.to_resolved().await?;
@@ -58,17 +121,40 @@ pub async fn get_global_module_id_strategy(
                 Ok((ident, (ident_str, hash)))
             })
             .try_join()
-            .await?
-            .into_iter()
-            .collect::<FxHashMap<_, _>>();
+            .await?;
 
-        finalize_module_ids(&mut module_id_map);
+        // Modules that already have a recorded id keep it, all others get a new one
+        let mut module_id_map = FxHashMap::default();
+        let mut recorded_module_id_map = FxHashMap::default();
+        for (ident, (ident_str, hash)) in all_module_ids {
+            if let Some(id) = records.module_ids.get(&**ident_str) {
+                recorded_module_id_map.insert(ident, *id);
+            } else {
+                module_id_map.insert(ident, (ident_str, hash));
+            }
+        }
+        let reserved_ids = records.module_ids.values().copied().collect();
+
+        finalize_module_ids(&mut module_id_map, &reserved_ids);
 
-        Ok(GlobalModuleIdStrategy {
+        let updated_records = if records_path.is_some() && !module_id_map.is_empty() {
+            records.module_ids.extend(
+                module_id_map
+                    .values()
+                    .map(|(ident_str, id)| ((**ident_str).clone(), *id)),
+            );
+            Some(records.module_ids)
+        } else {
+            None
+        };
+
+        Ok(GlobalModuleIds {
             module_id_map: module_id_map
                 .into_iter()
                 .map(|(ident, (_, hash))| (ident, hash))
+                .chain(recorded_module_id_map)
                 .collect(),
+            updated_records,
         }
         .cell())
     }
@@ -76,22 +162,50 @@ pub async fn get_global_module_id_strategy(
     .await
 }
 
+async fn read_module_id_records(records_path: &FileSystemPath) -> Result<ModuleIdRecords> {
+    let records: ModuleIdRecords = match &*records_path.read().parse_json().await? {
+        FileJsonContent::Content(value) => serde_json::from_value(value.clone())
+            .with_context(|| format!("invalid module id records file {records_path}"))?,
+        FileJsonContent::NotFound => return Ok(ModuleIdRecords::default()),
+        FileJsonContent::Unparsable(err) => {
+            bail!("unable to parse module id records file {records_path}: {err}")
+        }
+    };
+    validate_module_id_records(&records)
+        .with_context(|| format!("invalid module id records file {records_path}"))?;
+    Ok(records)
+}
+
+/// Checks that no id is recorded for more than one module.
+fn validate_module_id_records(records: &ModuleIdRecords) -> Result<()> {
+    let mut modules_by_id = FxHashMap::default();
+    for (module, id) in &records.module_ids {
+        if let Some(other_module) = modules_by_id.insert(*id, module) {
+            bail!("the modules {other_module} and {module} have the same id {id}");
+        }
+    }
+    Ok(())
+}
+
 const JS_MAX_SAFE_INTEGER: u64 = (1u64 << 53) - 1;
 
-/// Shorten hashes and handle any collisions.
-fn finalize_module_ids(
-    merged_module_ids: &mut FxHashMap<ResolvedVc<AssetIdent>, (ReadRef<RcStr>, u64)>,
+/// Shorten hashes and handle any collisions, without assigning any of the `reserved_ids`.
+///
+/// `merged_module_ids` maps every module to its ident string, used to resolve collisions in a
+/// deterministic order, and its full hash, which is replaced with the final id.
+fn finalize_module_ids<K: Copy + Eq + Hash, S: Ord + Clone>(
+    merged_module_ids: &mut FxHashMap<K, (S, u64)>,
+    reserved_ids: &FxHashSet<u64>,
 ) {
     // 5% fill rate, as done in Webpack
     // https://github.com/webpack/webpack/blob/27cf3e59f5f289dfc4d76b7a1df2edbc4e651589/lib/ids/IdHelpers.js#L366-L405
-    let optimal_range = merged_module_ids.len() * 20;
+    let optimal_range = (merged_module_ids.len() + reserved_ids.len()) * 20;
     let digit_mask = std::cmp::min(
         10u64.pow((optimal_range as f64).log10().ceil() as u32),
         JS_MAX_SAFE_INTEGER,
     );
 
-    let mut used_ids =
-        FxHashMap::<u64, SmallVec<[(ResolvedVc<AssetIdent>, ReadRef<RcStr>); 1]>>::default();
+    let mut used_ids = FxHashMap::<u64, SmallVec<[(K, S); 1]>>::default();
 
     // Run in multiple passes, to not depend on the order of the `merged_module_ids` (i.e. the order
     // of imports). Hashes could still change if modules are added or removed.
@@ -106,10 +220,10 @@ fn finalize_module_ids(
         *full_hash = first_pass_hash;
     }
 
-    // Filter conflicts
+    // Filter conflicts, including the ones with reserved ids
     let mut conflicting_hashes = used_ids
         .iter()
-        .filter(|(_, list)| (list.len() > 1))
+        .filter(|(hash, list)| list.len() > 1 || reserved_ids.contains(*hash))
         .map(|(hash, _)| *hash)
         .collect::<Vec<_>>();
     conflicting_hashes.sort();
@@ -121,8 +235,9 @@ fn finalize_module_ids(
         let mut list = std::mem::take(list);
         list.sort_by(|a, b| a.1.cmp(&b.1));
 
-        // Skip the first one, one module can keep the original hash
-        for (ident, _) in list.into_iter().skip(1) {
+        // Skip the first one, one module can keep the original hash unless it's reserved
+        let skip = if reserved_ids.contains(&hash) { 0 } else { 1 };
+        for (ident, _) in list.into_iter().skip(skip) {
             let hash = &mut merged_module_ids.get_mut(&ident).unwrap().1;
 
             // the original algorithm since all that runs in deterministic order now
@@ -131,7 +246,7 @@ fn finalize_module_ids(
             loop {
                 // If the id is already used, find the next available hash.
                 trimmed_hash = hash_xxh3_hash64((*hash, i)) % digit_mask;
-                if !used_ids.contains_key(&trimmed_hash) {
+                if !used_ids.contains_key(&trimmed_hash) && !reserved_ids.contains(&trimmed_hash) {
                     break;
                 }
                 i += 1;
@@ -142,3 +257,65 @@ fn finalize_module_ids(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rustc_hash::{FxHashMap, FxHashSet};
+    use turbopack_core::chunk::module_id_strategies::ModuleIdRecords;
+
+    use super::{finalize_module_ids, validate_module_id_records};
+
+    #[test]
+    fn colliding_hashes_get_distinct_ids() {
+        // With 2 modules the ids are in the range 0..100, so both hashes shorten to 42
+        let mut module_ids = FxHashMap::from_iter([("a", ("a", 1042)), ("b", ("b", 2042))]);
+        finalize_module_ids(&mut module_ids, &FxHashSet::default());
+
+        // The first module in ident order keeps the shortened hash
+        assert_eq!(module_ids["a"].1, 42);
+        assert_ne!(module_ids["b"].1, 42);
+        assert!(module_ids["b"].1 < 100);
+    }
+
+    #[test]
+    fn reserved_ids_are_never_assigned() {
+        let mut module_ids = FxHashMap::from_iter([("a", ("a", 1042)), ("b", ("b", 2007))]);
+        let reserved_ids = FxHashSet::from_iter([42]);
+        finalize_module_ids(&mut module_ids, &reserved_ids);
+
+        // Even without a collision between new modules, a reserved id moves the module elsewhere
+        assert_ne!(module_ids["a"].1, 42);
+        assert_eq!(module_ids["b"].1, 7);
+        assert_ne!(module_ids["a"].1, module_ids["b"].1);
+    }
+
+    #[test]
+    fn collisions_with_reserved_ids_are_resolved_for_every_module() {
+        let mut module_ids =
+            FxHashMap::from_iter([("a", ("a", 1042)), ("b", ("b", 2042)), ("c", ("c", 3042))]);
+        let reserved_ids = FxHashSet::from_iter([42, 13]);
+        finalize_module_ids(&mut module_ids, &reserved_ids);
+
+        let ids = module_ids
+            .values()
+            .map(|(_, id)| *id)
+            .collect::<FxHashSet<_>>();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.is_disjoint(&reserved_ids));
+    }
+
+    #[test]
+    fn duplicate_recorded_ids_are_rejected() {
+        let mut records = ModuleIdRecords::default();
+        records.module_ids.insert("[project]/a.js".into(), 1);
+        records.module_ids.insert("[project]/b.js".into(), 2);
+        assert!(validate_module_id_records(&records).is_ok());
+
+        records.module_ids.insert("[project]/c.js".into(), 1);
+        let err = validate_module_id_records(&records).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the modules [project]/a.js and [project]/c.js have the same id 1"
+        );
+    }
+}
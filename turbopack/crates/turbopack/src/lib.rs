@@ -56,6 +56,7 @@ use turbopack_ecmascript::{
         CachedExternalModule, CachedExternalTracingMode, CachedExternalType,
     },
     side_effect_optimization::locals::module::EcmascriptModuleLocalsModule,
+    text::{BytesContentFileSource, CssStyleSheetFileSource, TextContentFileSource},
     tree_shake::asset::EcmascriptModulePartAsset,
 };
 use turbopack_json::JsonModuleAsset;
@@ -76,6 +77,7 @@ async fn apply_module_type(
     inner_assets: Option<ResolvedVc<InnerAssets>>,
     css_import_context: Option<Vc<ImportContext>>,
     runtime_code: bool,
+    source_phase: bool,
 ) -> Result<Vc<ProcessResult>> {
     let module_type = &*module_type.await?;
     Ok(ProcessResult::Module(match module_type {
@@ -233,6 +235,19 @@ async fn apply_module_type(
         }
         ModuleType::Json => ResolvedVc::upcast(JsonModuleAsset::new(*source).to_resolved().await?),
         ModuleType::Raw => ResolvedVc::upcast(RawModule::new(*source).to_resolved().await?),
+        ModuleType::Text | ModuleType::Bytes | ModuleType::CssStyleSheet => {
+            // The content is wrapped in an ES module, which is processed like any other JS file
+            let source: Vc<Box<dyn Source>> = match module_type {
+                ModuleType::Text => Vc::upcast(TextContentFileSource::new(*source)),
+                ModuleType::Bytes => Vc::upcast(BytesContentFileSource::new(*source)),
+                _ => Vc::upcast(CssStyleSheetFileSource::new(*source)),
+            };
+            module_asset_context
+                .process(source, ReferenceType::Undefined)
+                .module()
+                .to_resolved()
+                .await?
+        }
         ModuleType::CssModule => ResolvedVc::upcast(
             ModuleCssAsset::new(*source, Vc::upcast(module_asset_context))
                 .to_resolved()
@@ -256,14 +271,19 @@ async fn apply_module_type(
         ModuleType::StaticUrlCss => {
             ResolvedVc::upcast(StaticUrlCssModule::new(*source).to_resolved().await?)
         }
-        ModuleType::WebAssembly { source_ty } => ResolvedVc::upcast(
-            WebAssemblyModuleAsset::new(
-                WebAssemblySource::new(*source, *source_ty),
-                Vc::upcast(module_asset_context),
+        ModuleType::WebAssembly { source_ty } => {
+            let source = WebAssemblySource::new(*source, *source_ty);
+            let asset_context: Vc<Box<dyn AssetContext>> = Vc::upcast(module_asset_context);
+            ResolvedVc::upcast(
+                if source_phase {
+                    WebAssemblyModuleAsset::new_source_phase(source, asset_context)
+                } else {
+                    WebAssemblyModuleAsset::new(source, asset_context)
+                }
+                .to_resolved()
+                .await?,
             )
-            .to_resolved()
-            .await?,
-        ),
+        }
         ModuleType::Custom(custom) => {
             custom
                 .create_module(*source, module_asset_context, part)
@@ -502,22 +522,20 @@ async fn process_default_internal(
         _ => None,
     };
 
-    let mut has_type_attribute = false;
-
-    let mut current_source = source;
-    let mut current_module_type = match &reference_type {
+    // The module type of imports with a type attribute is determined by the first matching rule,
+    // see `ModuleOptions::new`
+    let import_with_type = match &reference_type {
         ReferenceType::EcmaScriptModules(EcmaScriptModulesReferenceSubType::ImportWithType(ty)) => {
-            has_type_attribute = true;
-
-            match ty {
-                ImportWithType::Json => Some(ModuleType::Json),
-            }
+            Some(*ty)
         }
         _ => None,
     };
 
+    let mut current_source = source;
+    let mut current_module_type = None;
+
     for (i, rule) in options.await?.rules.iter().enumerate() {
-        if has_type_attribute && current_module_type.is_some() {
+        if import_with_type.is_some() && current_module_type.is_some() {
             continue;
         }
         if processed_rules.contains(&i) {
@@ -645,10 +663,57 @@ async fn process_default_internal(
         }
     }
 
+    if let Some(ty) = import_with_type
+        && !matches!(
+            (ty, &current_module_type),
+            (ImportWithType::Json, Some(ModuleType::Json))
+                | (ImportWithType::Css, Some(ModuleType::CssStyleSheet))
+                | (ImportWithType::Text, Some(ModuleType::Text))
+                | (ImportWithType::Bytes, Some(ModuleType::Bytes))
+        )
+    {
+        ModuleIssue {
+            ident: ident.to_resolved().await?,
+            title: StyledString::Text(rcstr!("Mismatched import type")).resolved_cell(),
+            description: StyledString::Text(
+                format!(
+                    "The module is imported with `{{ type: \"{ty}\" }}`, but it is not a {ty} \
+                     module."
+                )
+                .into(),
+            )
+            .resolved_cell(),
+            source: Some(IssueSource::from_source_only(current_source)),
+        }
+        .resolved_cell()
+        .emit();
+        return Ok(ProcessResult::Ignore.cell());
+    }
+
     let Some(module_type) = current_module_type else {
         return Ok(ProcessResult::Unknown(current_source).cell());
     };
 
+    let source_phase = matches!(
+        reference_type,
+        ReferenceType::EcmaScriptModules(EcmaScriptModulesReferenceSubType::ImportSourcePhase)
+    );
+    if source_phase && !matches!(module_type, ModuleType::WebAssembly { .. }) {
+        ModuleIssue {
+            ident: ident.to_resolved().await?,
+            title: StyledString::Text(rcstr!("Unsupported source phase import")).resolved_cell(),
+            description: StyledString::Text(rcstr!(
+                "Source phase imports (`import source x from \"...\"`) are only supported for \
+                 WebAssembly modules."
+            ))
+            .resolved_cell(),
+            source: Some(IssueSource::from_source_only(current_source)),
+        }
+        .resolved_cell()
+        .emit();
+        return Ok(ProcessResult::Ignore.cell());
+    }
+
     Ok(apply_module_type(
         *current_source,
         module_asset_context,
@@ -661,6 +726,7 @@ async fn process_default_internal(
             None
         },
         matches!(reference_type, ReferenceType::Runtime),
+        source_phase,
     ))
 }
 
@@ -14,9 +14,11 @@ use turbo_rcstr::{RcStr, rcstr};
 use turbo_tasks::{ResolvedVc, Vc};
 use turbo_tasks_fs::{FileSystemPath, glob::Glob};
 use turbopack_core::{
-    chunk::SourceMapsType,
     ident::Layer,
-    reference_type::{CssReferenceSubType, ReferenceType, UrlReferenceSubType},
+    reference_type::{
+        CssReferenceSubType, EcmaScriptModulesReferenceSubType, ImportWithType, ReferenceType,
+        UrlReferenceSubType,
+    },
     resolve::options::{ImportMap, ImportMapping},
 };
 use turbopack_css::CssModuleAssetType;
@@ -198,7 +200,7 @@ impl ModuleOptions {
             url_rewrite_behavior: esm_url_rewrite_behavior,
             import_externals,
             ignore_dynamic_requests,
-            extract_source_map: matches!(ecmascript_source_maps, SourceMapsType::Full),
+            extract_source_map: ecmascript_source_maps.is_enabled(),
             keep_last_successful_parse,
             ..Default::default()
         };
@@ -266,12 +268,47 @@ impl ModuleOptions {
         let postprocess = ResolvedVc::cell(postprocess);
         let empty = ResolvedVc::<EcmascriptInputTransforms>::cell(vec![]);
 
+        let json_condition = RuleCondition::any(vec![
+            RuleCondition::ResourcePathEndsWith(".json".to_string()),
+            RuleCondition::ContentTypeStartsWith("application/json".to_string()),
+        ]);
+        let import_with_type = |ty| {
+            RuleCondition::ReferenceType(ReferenceType::EcmaScriptModules(
+                EcmaScriptModulesReferenceSubType::ImportWithType(ty),
+            ))
+        };
+
         let mut rules = vec![
-            ModuleRule::new_all(
-                RuleCondition::any(vec![
-                    RuleCondition::ResourcePathEndsWith(".json".to_string()),
-                    RuleCondition::ContentTypeStartsWith("application/json".to_string()),
+            // Imports with a type attribute get the module type of the attribute. The standard
+            // types are only allowed for resources of that type, other imports are reported as
+            // mismatched.
+            ModuleRule::new(
+                RuleCondition::all(vec![
+                    import_with_type(ImportWithType::Json),
+                    json_condition.clone(),
+                ]),
+                vec![ModuleRuleEffect::ModuleType(ModuleType::Json)],
+            ),
+            ModuleRule::new(
+                RuleCondition::all(vec![
+                    import_with_type(ImportWithType::Css),
+                    RuleCondition::any(vec![
+                        RuleCondition::ResourcePathEndsWith(".css".to_string()),
+                        RuleCondition::ContentTypeStartsWith("text/css".to_string()),
+                    ]),
                 ]),
+                vec![ModuleRuleEffect::ModuleType(ModuleType::CssStyleSheet)],
+            ),
+            ModuleRule::new(
+                import_with_type(ImportWithType::Text),
+                vec![ModuleRuleEffect::ModuleType(ModuleType::Text)],
+            ),
+            ModuleRule::new(
+                import_with_type(ImportWithType::Bytes),
+                vec![ModuleRuleEffect::ModuleType(ModuleType::Bytes)],
+            ),
+            ModuleRule::new_all(
+                json_condition,
                 vec![ModuleRuleEffect::ModuleType(ModuleType::Json)],
             ),
             ModuleRule::new_all(
@@ -555,7 +592,7 @@ impl ModuleOptions {
                                 *rule.loaders,
                                 rule.rename_as.clone(),
                                 resolve_options_context,
-                                matches!(ecmascript_source_maps, SourceMapsType::Full),
+                                ecmascript_source_maps.is_enabled(),
                             )
                             .to_resolved()
                             .await?,
@@ -623,7 +660,7 @@ impl ModuleOptions {
                                 ),
                                 *execution_context,
                                 options.config_location,
-                                matches!(css_source_maps, SourceMapsType::Full),
+                                css_source_maps.is_enabled(),
                             )
                             .to_resolved()
                             .await?,
@@ -119,6 +119,14 @@ pub enum ModuleType {
     },
     Json,
     Raw,
+    /// The content as string in the default export, for `import ... with { type: "text" }`.
+    Text,
+    /// The content as `Uint8Array` in the default export, for
+    /// `import ... with { type: "bytes" }`.
+    Bytes,
+    /// A constructable `CSSStyleSheet` in the default export, for
+    /// `import ... with { type: "css" }`.
+    CssStyleSheet,
     CssModule,
     Css {
         ty: CssModuleAssetType,
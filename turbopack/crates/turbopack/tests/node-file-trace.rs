@@ -182,6 +182,7 @@ static ALLOC: turbo_tasks_malloc::TurboMalloc = turbo_tasks_malloc::TurboMalloc;
 #[case::vm2("integration/vm2.js")]
 #[case::vue("integration/vue.js")]
 #[case::webpack_target_node("integration/webpack-target-node/index.js")]
+#[case::webpack_chunks("integration/webpack-chunks/index.js")]
 #[case::whatwg_url("integration/whatwg-url.js")]
 #[case::when("integration/when.js")]
 // TODO PACK-4987
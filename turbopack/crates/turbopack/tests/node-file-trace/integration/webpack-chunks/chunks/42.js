@@ -0,0 +1,18 @@
+'use strict'
+exports.id = 42
+exports.ids = [42]
+exports.modules = {
+  /***/ 500: /***/ (
+    __unused_webpack_module,
+    __webpack_exports__,
+    __webpack_require__
+  ) => {
+    __webpack_require__.r(__webpack_exports__)
+    __webpack_require__.d(__webpack_exports__, {
+      default: () => __WEBPACK_DEFAULT_EXPORT__,
+    })
+    const __WEBPACK_DEFAULT_EXPORT__ = 'lazy'
+
+    /***/
+  },
+}
@@ -0,0 +1,10 @@
+'use strict'
+exports.id = 179
+exports.ids = [179]
+exports.modules = {
+  /***/ 200: /***/ (module) => {
+    module.exports = 'lib'
+
+    /***/
+  },
+}
@@ -0,0 +1,10 @@
+'use strict'
+exports.id = 'vendor-react'
+exports.ids = ['vendor-react']
+exports.modules = {
+  /***/ 300: /***/ (module) => {
+    module.exports = 'react'
+
+    /***/
+  },
+}
@@ -0,0 +1,10 @@
+'use strict'
+exports.id = 'shared-page'
+exports.ids = ['shared-page']
+exports.modules = {
+  /***/ 400: /***/ (module) => {
+    module.exports = 'shared'
+
+    /***/
+  },
+}
@@ -0,0 +1,5 @@
+const render = require('./page.js')
+
+render().then((result) => {
+  console.log(result)
+})
@@ -0,0 +1,33 @@
+'use strict'
+;(() => {
+  var exports = {}
+  exports.id = 820
+  exports.ids = [820]
+  exports.modules = {
+    /***/ 100: /***/ (module, __unused_webpack_exports, __webpack_require__) => {
+      const lib = __webpack_require__(200)
+      const react = __webpack_require__(300)
+      const shared = __webpack_require__(400)
+
+      module.exports = async function render() {
+        const { default: lazy } = await __webpack_require__
+          .e(/* import() */ 42)
+          .then(__webpack_require__.bind(__webpack_require__, 500))
+        return [lib, react, shared, lazy].join(' ')
+      }
+
+      /***/
+    },
+  }
+  // load runtime
+  var __webpack_require__ = require('./webpack-runtime.js')
+  __webpack_require__.C(exports)
+  var __webpack_exec__ = (moduleId) =>
+    __webpack_require__((__webpack_require__.s = moduleId))
+  var __webpack_exports__ = __webpack_require__.X(
+    0,
+    [179, 'vendor-react', `shared-${'page'}`],
+    () => __webpack_exec__(100)
+  )
+  module.exports = __webpack_exports__
+})()
@@ -0,0 +1,207 @@
+/******/ ;(() => {
+  // webpackBootstrap
+  /******/ 'use strict'
+  /******/ var __webpack_modules__ = {}
+  /************************************************************************/
+  /******/ // The module cache
+  /******/ var __webpack_module_cache__ = {}
+  /******/
+  /******/ // The require function
+  /******/ function __webpack_require__(moduleId) {
+    /******/ // Check if module is in cache
+    /******/ var cachedModule = __webpack_module_cache__[moduleId]
+    /******/ if (cachedModule !== undefined) {
+      /******/ return cachedModule.exports
+      /******/
+    }
+    /******/ // Create a new module (and put it into the cache)
+    /******/ var module = (__webpack_module_cache__[moduleId] = {
+      /******/ // no module.id needed
+      /******/ // no module.loaded needed
+      /******/ exports: {},
+      /******/
+    })
+    /******/
+    /******/ // Execute the module function
+    /******/ var threw = true
+    /******/ try {
+      /******/ __webpack_modules__[moduleId](
+        module,
+        module.exports,
+        __webpack_require__
+      )
+      /******/ threw = false
+      /******/
+    } finally {
+      /******/ if (threw) delete __webpack_module_cache__[moduleId]
+      /******/
+    }
+    /******/
+    /******/ // Return the exports of the module
+    /******/ return module.exports
+    /******/
+  }
+  /******/
+  /******/ // expose the modules object (__webpack_modules__)
+  /******/ __webpack_require__.m = __webpack_modules__
+  /******/
+  /************************************************************************/
+  /******/ /* webpack/runtime/define property getters */
+  /******/
+  ;(() => {
+    /******/ // define getter functions for harmony exports
+    /******/ __webpack_require__.d = (exports, definition) => {
+      /******/ for (var key in definition) {
+        /******/ if (
+          __webpack_require__.o(definition, key) &&
+          !__webpack_require__.o(exports, key)
+        ) {
+          /******/ Object.defineProperty(exports, key, {
+            enumerable: true,
+            get: definition[key],
+          })
+          /******/
+        }
+        /******/
+      }
+      /******/
+    }
+    /******/
+  })()
+  /******/
+  /******/ /* webpack/runtime/ensure chunk */
+  /******/
+  ;(() => {
+    /******/ __webpack_require__.f = {}
+    /******/ // This file contains only the entry chunk.
+    /******/ // The chunk loading function for additional chunks
+    /******/ __webpack_require__.e = (chunkId) => {
+      /******/ return Promise.all(
+        Object.keys(__webpack_require__.f).reduce((promises, key) => {
+          /******/ __webpack_require__.f[key](chunkId, promises)
+          /******/ return promises
+          /******/
+        }, [])
+      )
+      /******/
+    }
+    /******/
+  })()
+  /******/
+  /******/ /* webpack/runtime/get javascript chunk filename */
+  /******/
+  ;(() => {
+    /******/ // This function allow to reference async chunks and sibling chunks for the entrypoint
+    /******/ __webpack_require__.u = (chunkId) => {
+      /******/ // return url for filenames based on template
+      /******/ return (
+        '' + ({ 179: 'lib', 'vendor-react': 'react' }[chunkId] || chunkId) + '.js'
+      )
+      /******/
+    }
+    /******/
+  })()
+  /******/
+  /******/ /* webpack/runtime/hasOwnProperty shorthand */
+  /******/
+  ;(() => {
+    /******/ __webpack_require__.o = (obj, prop) =>
+      Object.prototype.hasOwnProperty.call(obj, prop)
+    /******/
+  })()
+  /******/
+  /******/ /* webpack/runtime/make namespace object */
+  /******/
+  ;(() => {
+    /******/ // define __esModule on exports
+    /******/ __webpack_require__.r = (exports) => {
+      /******/ if (typeof Symbol !== 'undefined' && Symbol.toStringTag) {
+        /******/ Object.defineProperty(exports, Symbol.toStringTag, {
+          value: 'Module',
+        })
+        /******/
+      }
+      /******/ Object.defineProperty(exports, '__esModule', { value: true })
+      /******/
+    }
+    /******/
+  })()
+  /******/
+  /******/ /* webpack/runtime/startup entrypoint */
+  /******/
+  ;(() => {
+    /******/ __webpack_require__.X = (result, chunkIds, fn) => {
+      /******/ // arguments: chunkIds, moduleId are deprecated
+      /******/ var moduleId = chunkIds
+      /******/ if (!fn)
+        (chunkIds = result),
+          (fn = () => __webpack_require__((__webpack_require__.s = moduleId)))
+      /******/ chunkIds.map(__webpack_require__.e, __webpack_require__)
+      /******/ var r = fn()
+      /******/ return r === undefined ? result : r
+      /******/
+    }
+    /******/
+  })()
+  /******/
+  /******/ /* webpack/runtime/require chunk loading */
+  /******/
+  ;(() => {
+    /******/ // no baseURI
+    /******/
+    /******/ // object to store loaded chunks
+    /******/ // "1" means "loaded", otherwise not loaded yet
+    /******/ var installedChunks = {
+      /******/ 165: 1,
+      /******/
+    }
+    /******/
+    /******/ // no on chunks loaded
+    /******/
+    /******/ var installChunk = (chunk) => {
+      /******/ var moreModules = chunk.modules,
+        chunkIds = chunk.ids,
+        runtime = chunk.runtime
+      /******/ for (var moduleId in moreModules) {
+        /******/ if (__webpack_require__.o(moreModules, moduleId)) {
+          /******/ __webpack_require__.m[moduleId] = moreModules[moduleId]
+          /******/
+        }
+        /******/
+      }
+      /******/ if (runtime) runtime(__webpack_require__)
+      /******/ for (var i = 0; i < chunkIds.length; i++)
+        /******/ installedChunks[chunkIds[i]] = 1
+      /******/
+      /******/
+    }
+    /******/
+    /******/ // require() chunk loading for javascript
+    /******/ __webpack_require__.f.require = (chunkId, promises) => {
+      /******/ // "1" is the signal for "already loaded"
+      /******/ if (!installedChunks[chunkId]) {
+        /******/ if (165 != chunkId) {
+          /******/ installChunk(
+            require('./chunks/' + __webpack_require__.u(chunkId))
+          )
+          /******/
+        } else installedChunks[chunkId] = 1
+        /******/
+      }
+      /******/
+    }
+    /******/
+    /******/ module.exports = __webpack_require__
+    /******/ __webpack_require__.C = installChunk
+    /******/
+    /******/ // no HMR
+    /******/
+    /******/ // no HMR manifest
+    /******/
+  })()
+  /******/
+  /************************************************************************/
+  /******/
+  /******/
+  /******/
+})()
@@ -0,0 +1,94 @@
+//! Resolves requests with a web import map configured in the `ResolveOptionsContext`, using the
+//! project in `tests/web_import_map`.
+
+use anyhow::Result;
+use turbo_rcstr::{RcStr, rcstr};
+use turbo_tasks_backend::{BackendOptions, TurboTasksBackend, noop_backing_storage};
+use turbo_tasks_fs::{DiskFileSystem, FileSystem, FileSystemPath};
+use turbopack_core::{
+    reference_type::ReferenceType,
+    resolve::{ExternalType, ResolveResultItem, parse::Request, resolve},
+    source::Source,
+};
+use turbopack_resolve::{resolve::resolve_options, resolve_options_context::ResolveOptionsContext};
+
+async fn resolve_request(
+    lookup_path: &FileSystemPath,
+    request: &str,
+    import_map: &FileSystemPath,
+) -> Result<Vec<ResolveResultItem>> {
+    let options = resolve_options(
+        lookup_path.clone(),
+        ResolveOptionsContext {
+            web_import_map: Some(import_map.clone()),
+            ..Default::default()
+        }
+        .cell(),
+    );
+    let result = resolve(
+        lookup_path.clone(),
+        ReferenceType::Undefined,
+        Request::parse_string(request.into()),
+        options,
+    )
+    .await?;
+    Ok(result
+        .primary
+        .iter()
+        .map(|(_, item)| item.clone())
+        .collect())
+}
+
+async fn resolved_path(items: &[ResolveResultItem]) -> Result<Option<RcStr>> {
+    Ok(match items {
+        [ResolveResultItem::Source(source)] => Some(source.ident().path().await?.path.clone()),
+        _ => None,
+    })
+}
+
+#[tokio::test]
+async fn web_import_map_is_applied_when_resolving() {
+    turbopack::register();
+
+    let tt = turbo_tasks::TurboTasks::new(TurboTasksBackend::new(
+        BackendOptions::default(),
+        noop_backing_storage(),
+    ));
+    tt.run_once(async move {
+        let fs = DiskFileSystem::new(
+            rcstr!("test"),
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/web_import_map").into(),
+        );
+        let root = fs.root().owned().await?;
+        let import_map = root.join("importmap.json")?;
+        let src = root.join("src")?;
+        let legacy = root.join("legacy")?;
+
+        assert_eq!(
+            resolved_path(&resolve_request(&src, "react", &import_map).await?).await?,
+            Some(rcstr!("vendor/react.js"))
+        );
+        assert_eq!(
+            resolved_path(&resolve_request(&src, "lodash/map.js", &import_map).await?).await?,
+            Some(rcstr!("vendor/lodash/map.js"))
+        );
+        assert_eq!(
+            resolved_path(&resolve_request(&legacy, "react", &import_map).await?).await?,
+            Some(rcstr!("vendor/react-17.js"))
+        );
+
+        let items = resolve_request(&src, "cdn", &import_map).await?;
+        assert!(
+            matches!(
+                items.as_slice(),
+                [ResolveResultItem::External { name, ty: ExternalType::Url, .. }]
+                    if name == "https://esm.sh/cdn@1"
+            ),
+            "expected a URL external, got {items:?}"
+        );
+
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap();
+}